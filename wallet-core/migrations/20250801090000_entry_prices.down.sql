@@ -0,0 +1,3 @@
+ALTER TABLE transaction_entries DROP COLUMN price_amount_minor;
+ALTER TABLE transaction_entries DROP COLUMN price_currency;
+ALTER TABLE transaction_entries DROP COLUMN price_rate;
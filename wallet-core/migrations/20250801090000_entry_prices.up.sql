@@ -0,0 +1,5 @@
+-- Optional conversion of an entry into a second currency ("EUR 92.10 @ 1.0857 USD").
+-- The rate is kept as text to preserve its full decimal precision.
+ALTER TABLE transaction_entries ADD COLUMN price_rate TEXT;
+ALTER TABLE transaction_entries ADD COLUMN price_currency TEXT;
+ALTER TABLE transaction_entries ADD COLUMN price_amount_minor INTEGER;
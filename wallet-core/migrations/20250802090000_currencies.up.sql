@@ -0,0 +1,173 @@
+-- Registry of currencies and commodities. Seeded with the active ISO 4217
+-- currencies; users can add their own commodities (crypto tokens, stock tickers...).
+CREATE TABLE currencies (
+    code TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    minor_unit_scale INTEGER NOT NULL CHECK (minor_unit_scale BETWEEN 0 AND 18),
+    symbol TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('fiat', 'crypto', 'security', 'commodity')),
+    is_builtin BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO currencies (code, name, minor_unit_scale, symbol, kind, is_builtin) VALUES
+    ('AED', 'UAE Dirham', 2, 'د.إ', 'fiat', TRUE),
+    ('AFN', 'Afghani', 2, '؋', 'fiat', TRUE),
+    ('ALL', 'Lek', 2, 'L', 'fiat', TRUE),
+    ('AMD', 'Armenian Dram', 2, '֏', 'fiat', TRUE),
+    ('ANG', 'Netherlands Antillean Guilder', 2, 'ƒ', 'fiat', TRUE),
+    ('AOA', 'Kwanza', 2, 'Kz', 'fiat', TRUE),
+    ('ARS', 'Argentine Peso', 2, '$', 'fiat', TRUE),
+    ('AUD', 'Australian Dollar', 2, 'A$', 'fiat', TRUE),
+    ('AWG', 'Aruban Florin', 2, 'ƒ', 'fiat', TRUE),
+    ('AZN', 'Azerbaijan Manat', 2, '₼', 'fiat', TRUE),
+    ('BAM', 'Convertible Mark', 2, 'KM', 'fiat', TRUE),
+    ('BBD', 'Barbados Dollar', 2, '$', 'fiat', TRUE),
+    ('BDT', 'Taka', 2, '৳', 'fiat', TRUE),
+    ('BGN', 'Bulgarian Lev', 2, 'лв', 'fiat', TRUE),
+    ('BHD', 'Bahraini Dinar', 3, 'BD', 'fiat', TRUE),
+    ('BIF', 'Burundi Franc', 0, 'FBu', 'fiat', TRUE),
+    ('BMD', 'Bermudian Dollar', 2, '$', 'fiat', TRUE),
+    ('BND', 'Brunei Dollar', 2, '$', 'fiat', TRUE),
+    ('BOB', 'Boliviano', 2, 'Bs', 'fiat', TRUE),
+    ('BRL', 'Brazilian Real', 2, 'R$', 'fiat', TRUE),
+    ('BSD', 'Bahamian Dollar', 2, '$', 'fiat', TRUE),
+    ('BTN', 'Ngultrum', 2, 'Nu.', 'fiat', TRUE),
+    ('BWP', 'Pula', 2, 'P', 'fiat', TRUE),
+    ('BYN', 'Belarusian Ruble', 2, 'Br', 'fiat', TRUE),
+    ('BZD', 'Belize Dollar', 2, '$', 'fiat', TRUE),
+    ('CAD', 'Canadian Dollar', 2, 'CA$', 'fiat', TRUE),
+    ('CDF', 'Congolese Franc', 2, 'FC', 'fiat', TRUE),
+    ('CHF', 'Swiss Franc', 2, 'CHF', 'fiat', TRUE),
+    ('CLP', 'Chilean Peso', 0, '$', 'fiat', TRUE),
+    ('CNY', 'Yuan Renminbi', 2, '¥', 'fiat', TRUE),
+    ('COP', 'Colombian Peso', 2, '$', 'fiat', TRUE),
+    ('CRC', 'Costa Rican Colon', 2, '₡', 'fiat', TRUE),
+    ('CUP', 'Cuban Peso', 2, '$', 'fiat', TRUE),
+    ('CVE', 'Cabo Verde Escudo', 2, '$', 'fiat', TRUE),
+    ('CZK', 'Czech Koruna', 2, 'Kč', 'fiat', TRUE),
+    ('DJF', 'Djibouti Franc', 0, 'Fdj', 'fiat', TRUE),
+    ('DKK', 'Danish Krone', 2, 'kr', 'fiat', TRUE),
+    ('DOP', 'Dominican Peso', 2, '$', 'fiat', TRUE),
+    ('DZD', 'Algerian Dinar', 2, 'DA', 'fiat', TRUE),
+    ('EGP', 'Egyptian Pound', 2, 'E£', 'fiat', TRUE),
+    ('ERN', 'Nakfa', 2, 'Nfk', 'fiat', TRUE),
+    ('ETB', 'Ethiopian Birr', 2, 'Br', 'fiat', TRUE),
+    ('EUR', 'Euro', 2, '€', 'fiat', TRUE),
+    ('FJD', 'Fiji Dollar', 2, '$', 'fiat', TRUE),
+    ('FKP', 'Falkland Islands Pound', 2, '£', 'fiat', TRUE),
+    ('GBP', 'Pound Sterling', 2, '£', 'fiat', TRUE),
+    ('GEL', 'Lari', 2, '₾', 'fiat', TRUE),
+    ('GHS', 'Ghana Cedi', 2, '₵', 'fiat', TRUE),
+    ('GIP', 'Gibraltar Pound', 2, '£', 'fiat', TRUE),
+    ('GMD', 'Dalasi', 2, 'D', 'fiat', TRUE),
+    ('GNF', 'Guinean Franc', 0, 'FG', 'fiat', TRUE),
+    ('GTQ', 'Quetzal', 2, 'Q', 'fiat', TRUE),
+    ('GYD', 'Guyana Dollar', 2, '$', 'fiat', TRUE),
+    ('HKD', 'Hong Kong Dollar', 2, 'HK$', 'fiat', TRUE),
+    ('HNL', 'Lempira', 2, 'L', 'fiat', TRUE),
+    ('HTG', 'Gourde', 2, 'G', 'fiat', TRUE),
+    ('HUF', 'Forint', 2, 'Ft', 'fiat', TRUE),
+    ('IDR', 'Rupiah', 2, 'Rp', 'fiat', TRUE),
+    ('ILS', 'New Israeli Sheqel', 2, '₪', 'fiat', TRUE),
+    ('INR', 'Indian Rupee', 2, '₹', 'fiat', TRUE),
+    ('IQD', 'Iraqi Dinar', 3, 'ع.د', 'fiat', TRUE),
+    ('IRR', 'Iranian Rial', 2, '﷼', 'fiat', TRUE),
+    ('ISK', 'Iceland Krona', 0, 'kr', 'fiat', TRUE),
+    ('JMD', 'Jamaican Dollar', 2, '$', 'fiat', TRUE),
+    ('JOD', 'Jordanian Dinar', 3, 'JD', 'fiat', TRUE),
+    ('JPY', 'Yen', 0, '¥', 'fiat', TRUE),
+    ('KES', 'Kenyan Shilling', 2, 'KSh', 'fiat', TRUE),
+    ('KGS', 'Som', 2, 'сом', 'fiat', TRUE),
+    ('KHR', 'Riel', 2, '៛', 'fiat', TRUE),
+    ('KMF', 'Comorian Franc', 0, 'CF', 'fiat', TRUE),
+    ('KPW', 'North Korean Won', 2, '₩', 'fiat', TRUE),
+    ('KRW', 'Won', 0, '₩', 'fiat', TRUE),
+    ('KWD', 'Kuwaiti Dinar', 3, 'KD', 'fiat', TRUE),
+    ('KYD', 'Cayman Islands Dollar', 2, '$', 'fiat', TRUE),
+    ('KZT', 'Tenge', 2, '₸', 'fiat', TRUE),
+    ('LAK', 'Lao Kip', 2, '₭', 'fiat', TRUE),
+    ('LBP', 'Lebanese Pound', 2, 'LL', 'fiat', TRUE),
+    ('LKR', 'Sri Lanka Rupee', 2, 'Rs', 'fiat', TRUE),
+    ('LRD', 'Liberian Dollar', 2, '$', 'fiat', TRUE),
+    ('LSL', 'Loti', 2, 'L', 'fiat', TRUE),
+    ('LYD', 'Libyan Dinar', 3, 'LD', 'fiat', TRUE),
+    ('MAD', 'Moroccan Dirham', 2, 'DH', 'fiat', TRUE),
+    ('MDL', 'Moldovan Leu', 2, 'L', 'fiat', TRUE),
+    ('MGA', 'Malagasy Ariary', 2, 'Ar', 'fiat', TRUE),
+    ('MKD', 'Denar', 2, 'ден', 'fiat', TRUE),
+    ('MMK', 'Kyat', 2, 'K', 'fiat', TRUE),
+    ('MNT', 'Tugrik', 2, '₮', 'fiat', TRUE),
+    ('MOP', 'Pataca', 2, 'MOP$', 'fiat', TRUE),
+    ('MRU', 'Ouguiya', 2, 'UM', 'fiat', TRUE),
+    ('MUR', 'Mauritius Rupee', 2, '₨', 'fiat', TRUE),
+    ('MVR', 'Rufiyaa', 2, 'Rf', 'fiat', TRUE),
+    ('MWK', 'Malawi Kwacha', 2, 'MK', 'fiat', TRUE),
+    ('MXN', 'Mexican Peso', 2, 'MX$', 'fiat', TRUE),
+    ('MYR', 'Malaysian Ringgit', 2, 'RM', 'fiat', TRUE),
+    ('MZN', 'Mozambique Metical', 2, 'MT', 'fiat', TRUE),
+    ('NAD', 'Namibia Dollar', 2, '$', 'fiat', TRUE),
+    ('NGN', 'Naira', 2, '₦', 'fiat', TRUE),
+    ('NIO', 'Cordoba Oro', 2, 'C$', 'fiat', TRUE),
+    ('NOK', 'Norwegian Krone', 2, 'kr', 'fiat', TRUE),
+    ('NPR', 'Nepalese Rupee', 2, 'Rs', 'fiat', TRUE),
+    ('NZD', 'New Zealand Dollar', 2, 'NZ$', 'fiat', TRUE),
+    ('OMR', 'Rial Omani', 3, 'OMR', 'fiat', TRUE),
+    ('PAB', 'Balboa', 2, 'B/.', 'fiat', TRUE),
+    ('PEN', 'Sol', 2, 'S/', 'fiat', TRUE),
+    ('PGK', 'Kina', 2, 'K', 'fiat', TRUE),
+    ('PHP', 'Philippine Peso', 2, '₱', 'fiat', TRUE),
+    ('PKR', 'Pakistan Rupee', 2, 'Rs', 'fiat', TRUE),
+    ('PLN', 'Zloty', 2, 'zł', 'fiat', TRUE),
+    ('PYG', 'Guarani', 0, '₲', 'fiat', TRUE),
+    ('QAR', 'Qatari Rial', 2, 'QR', 'fiat', TRUE),
+    ('RON', 'Romanian Leu', 2, 'lei', 'fiat', TRUE),
+    ('RSD', 'Serbian Dinar', 2, 'дин.', 'fiat', TRUE),
+    ('RUB', 'Russian Ruble', 2, '₽', 'fiat', TRUE),
+    ('RWF', 'Rwanda Franc', 0, 'FRw', 'fiat', TRUE),
+    ('SAR', 'Saudi Riyal', 2, 'SR', 'fiat', TRUE),
+    ('SBD', 'Solomon Islands Dollar', 2, '$', 'fiat', TRUE),
+    ('SCR', 'Seychelles Rupee', 2, '₨', 'fiat', TRUE),
+    ('SDG', 'Sudanese Pound', 2, 'SDG', 'fiat', TRUE),
+    ('SEK', 'Swedish Krona', 2, 'kr', 'fiat', TRUE),
+    ('SGD', 'Singapore Dollar', 2, 'S$', 'fiat', TRUE),
+    ('SHP', 'Saint Helena Pound', 2, '£', 'fiat', TRUE),
+    ('SLE', 'Leone', 2, 'Le', 'fiat', TRUE),
+    ('SOS', 'Somali Shilling', 2, 'Sh', 'fiat', TRUE),
+    ('SRD', 'Surinam Dollar', 2, '$', 'fiat', TRUE),
+    ('SSP', 'South Sudanese Pound', 2, '£', 'fiat', TRUE),
+    ('STN', 'Dobra', 2, 'Db', 'fiat', TRUE),
+    ('SVC', 'El Salvador Colon', 2, '₡', 'fiat', TRUE),
+    ('SYP', 'Syrian Pound', 2, '£', 'fiat', TRUE),
+    ('SZL', 'Lilangeni', 2, 'L', 'fiat', TRUE),
+    ('THB', 'Baht', 2, '฿', 'fiat', TRUE),
+    ('TJS', 'Somoni', 2, 'SM', 'fiat', TRUE),
+    ('TMT', 'Turkmenistan New Manat', 2, 'm', 'fiat', TRUE),
+    ('TND', 'Tunisian Dinar', 3, 'DT', 'fiat', TRUE),
+    ('TOP', 'Pa''anga', 2, 'T$', 'fiat', TRUE),
+    ('TRY', 'Turkish Lira', 2, '₺', 'fiat', TRUE),
+    ('TTD', 'Trinidad and Tobago Dollar', 2, '$', 'fiat', TRUE),
+    ('TWD', 'New Taiwan Dollar', 2, 'NT$', 'fiat', TRUE),
+    ('TZS', 'Tanzanian Shilling', 2, 'TSh', 'fiat', TRUE),
+    ('UAH', 'Hryvnia', 2, '₴', 'fiat', TRUE),
+    ('UGX', 'Uganda Shilling', 0, 'USh', 'fiat', TRUE),
+    ('USD', 'US Dollar', 2, '$', 'fiat', TRUE),
+    ('UYU', 'Peso Uruguayo', 2, '$', 'fiat', TRUE),
+    ('UZS', 'Uzbekistan Sum', 2, 'soʻm', 'fiat', TRUE),
+    ('VES', 'Bolívar Soberano', 2, 'Bs.S', 'fiat', TRUE),
+    ('VND', 'Dong', 0, '₫', 'fiat', TRUE),
+    ('VUV', 'Vatu', 0, 'VT', 'fiat', TRUE),
+    ('WST', 'Tala', 2, 'T', 'fiat', TRUE),
+    ('XAF', 'CFA Franc BEAC', 0, 'FCFA', 'fiat', TRUE),
+    ('XCD', 'East Caribbean Dollar', 2, '$', 'fiat', TRUE),
+    ('XCG', 'Caribbean Guilder', 2, 'Cg', 'fiat', TRUE),
+    ('XOF', 'CFA Franc BCEAO', 0, 'CFA', 'fiat', TRUE),
+    ('XPF', 'CFP Franc', 0, '₣', 'fiat', TRUE),
+    ('YER', 'Yemeni Rial', 2, '﷼', 'fiat', TRUE),
+    ('ZAR', 'Rand', 2, 'R', 'fiat', TRUE),
+    ('ZMW', 'Zambian Kwacha', 2, 'K', 'fiat', TRUE),
+    ('ZWG', 'Zimbabwe Gold', 2, 'ZiG', 'fiat', TRUE);
+
+-- Bitcoin was supported before the registry existed
+INSERT INTO currencies (code, name, minor_unit_scale, symbol, kind, is_builtin) VALUES
+    ('BTC', 'Bitcoin', 8, '₿', 'crypto', TRUE);
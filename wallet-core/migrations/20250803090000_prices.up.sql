@@ -0,0 +1,16 @@
+-- Historical prices: one unit of base_currency is worth `rate` units of quote_currency.
+-- The rate is kept as text to preserve its full decimal precision.
+CREATE TABLE prices (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    base_currency TEXT NOT NULL REFERENCES currencies(code),
+    quote_currency TEXT NOT NULL REFERENCES currencies(code),
+    price_date DATE NOT NULL,
+    rate TEXT NOT NULL,
+    source TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE(base_currency, quote_currency, price_date, source),
+    CHECK(base_currency != quote_currency)
+);
+
+CREATE INDEX idx_prices_pair_date ON prices(base_currency, quote_currency, price_date);
@@ -0,0 +1,8 @@
+-- Application-wide settings stored as key/value pairs
+CREATE TABLE settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO settings (key, value) VALUES ('reporting_currency', 'EUR');
@@ -0,0 +1,2 @@
+DROP INDEX idx_transactions_reverses;
+ALTER TABLE transactions DROP COLUMN reverses_transaction_id;
@@ -0,0 +1,4 @@
+-- Link a reversing transaction to the transaction it cancels
+ALTER TABLE transactions ADD COLUMN reverses_transaction_id INTEGER REFERENCES transactions(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_transactions_reverses ON transactions(reverses_transaction_id);
@@ -0,0 +1,13 @@
+DROP INDEX idx_transactions_external_id;
+ALTER TABLE transactions DROP COLUMN external_id;
+
+ALTER TABLE transactions ADD COLUMN tags TEXT;
+
+UPDATE transactions
+SET tags = (
+    SELECT group_concat(tag, ',')
+    FROM transaction_tags
+    WHERE transaction_tags.transaction_id = transactions.id
+);
+
+DROP TABLE transaction_tags;
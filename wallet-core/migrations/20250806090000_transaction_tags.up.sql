@@ -0,0 +1,30 @@
+-- Tags move from a free-text column to one row per tag
+CREATE TABLE transaction_tags (
+    transaction_id INTEGER NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (transaction_id, tag)
+);
+
+CREATE INDEX idx_transaction_tags_tag ON transaction_tags(tag);
+
+-- Split existing comma-separated tags
+WITH RECURSIVE split(transaction_id, tag, rest) AS (
+    SELECT id, '', tags || ',' FROM transactions WHERE tags IS NOT NULL
+    UNION ALL
+    SELECT
+        transaction_id,
+        trim(substr(rest, 1, instr(rest, ',') - 1)),
+        substr(rest, instr(rest, ',') + 1)
+    FROM split
+    WHERE rest <> ''
+)
+INSERT OR IGNORE INTO transaction_tags (transaction_id, tag)
+SELECT transaction_id, tag FROM split WHERE tag <> '';
+
+ALTER TABLE transactions DROP COLUMN tags;
+
+-- Identifier of the transaction in an external system (bank export, import file)
+ALTER TABLE transactions ADD COLUMN external_id TEXT;
+
+CREATE UNIQUE INDEX idx_transactions_external_id ON transactions(external_id)
+WHERE external_id IS NOT NULL;
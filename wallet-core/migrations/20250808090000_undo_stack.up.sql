@@ -0,0 +1,7 @@
+-- Changes that can be undone (applied) or redone (undone), by audit log entry
+CREATE TABLE undo_stack (
+    audit_id INTEGER PRIMARY KEY REFERENCES audit_log(id),
+    status TEXT NOT NULL CHECK (status IN ('applied', 'undone'))
+);
+
+CREATE INDEX idx_undo_stack_status ON undo_stack(status, audit_id);
@@ -0,0 +1,4 @@
+DROP INDEX idx_entries_account_status;
+ALTER TABLE transaction_entries DROP COLUMN reconciliation_id;
+ALTER TABLE transaction_entries DROP COLUMN status;
+DROP TABLE reconciliations;
@@ -0,0 +1,19 @@
+-- Bank statements an account was reconciled against
+CREATE TABLE reconciliations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL REFERENCES accounts(id),
+    statement_date DATE NOT NULL,
+    statement_balance_minor INTEGER NOT NULL,
+    currency TEXT NOT NULL REFERENCES currencies(code),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_reconciliations_account ON reconciliations(account_id, statement_date);
+
+-- Entries are ticked off against a statement (cleared), then locked once the
+-- statement balance matches (reconciled)
+ALTER TABLE transaction_entries ADD COLUMN status TEXT NOT NULL DEFAULT 'pending'
+    CHECK (status IN ('pending', 'cleared', 'reconciled'));
+ALTER TABLE transaction_entries ADD COLUMN reconciliation_id INTEGER REFERENCES reconciliations(id);
+
+CREATE INDEX idx_entries_account_status ON transaction_entries(account_id, status);
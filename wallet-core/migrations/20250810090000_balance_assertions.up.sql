@@ -0,0 +1,11 @@
+-- "Account had this balance at the start of this date", checked against the ledger
+CREATE TABLE balance_assertions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL REFERENCES accounts(id),
+    assertion_date DATE NOT NULL,
+    amount_minor INTEGER NOT NULL,
+    currency TEXT NOT NULL REFERENCES currencies(code),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE(account_id, assertion_date, currency)
+);
@@ -0,0 +1,2 @@
+DROP TABLE fiscal_year_closes;
+DROP TABLE closed_periods;
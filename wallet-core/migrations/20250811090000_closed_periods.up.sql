@@ -0,0 +1,16 @@
+-- Transactions dated on or before the latest lock date can no longer change
+CREATE TABLE closed_periods (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    lock_date DATE NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Closing transactions moving the Income and Expense balances of a fiscal year
+-- into retained earnings
+CREATE TABLE fiscal_year_closes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    fiscal_year_end DATE NOT NULL UNIQUE,
+    transaction_id INTEGER NOT NULL UNIQUE REFERENCES transactions(id) ON DELETE CASCADE,
+    retained_earnings_account_id INTEGER NOT NULL REFERENCES accounts(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,10 @@
+DROP TRIGGER transaction_search_entry_delete;
+DROP TRIGGER transaction_search_entry_update;
+DROP TRIGGER transaction_search_entry_insert;
+DROP TRIGGER transaction_search_tag_delete;
+DROP TRIGGER transaction_search_tag_insert;
+DROP TRIGGER transaction_search_delete;
+DROP TRIGGER transaction_search_update;
+DROP TRIGGER transaction_search_insert;
+DROP TABLE transaction_search;
+DROP VIEW transaction_search_source;
@@ -0,0 +1,93 @@
+-- Searchable text of each transaction, one row per transaction
+CREATE VIEW transaction_search_source AS
+SELECT
+    t.id,
+    t.description,
+    t.notes,
+    t.reference,
+    (SELECT group_concat(tag, ' ') FROM transaction_tags WHERE transaction_id = t.id) AS tags,
+    (
+        SELECT group_concat(description, ' ')
+        FROM transaction_entries
+        WHERE transaction_id = t.id AND description IS NOT NULL
+    ) AS entry_descriptions
+FROM transactions t;
+
+-- Full-text index over that text; the rowid is the transaction id
+CREATE VIRTUAL TABLE transaction_search USING fts5(
+    description,
+    notes,
+    reference,
+    tags,
+    entry_descriptions,
+    tokenize = 'unicode61 remove_diacritics 2',
+    prefix = '2 3'
+);
+
+INSERT INTO transaction_search (rowid, description, notes, reference, tags, entry_descriptions)
+SELECT id, description, notes, reference, tags, entry_descriptions FROM transaction_search_source;
+
+-- Keep the index in sync: any change to a transaction, its tags or its entries
+-- rewrites the transaction's row from the view
+CREATE TRIGGER transaction_search_insert AFTER INSERT ON transactions
+BEGIN
+    INSERT INTO transaction_search (rowid, description, notes, reference, tags, entry_descriptions)
+    SELECT id, description, notes, reference, tags, entry_descriptions
+    FROM transaction_search_source WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER transaction_search_update AFTER UPDATE ON transactions
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = OLD.id;
+    INSERT INTO transaction_search (rowid, description, notes, reference, tags, entry_descriptions)
+    SELECT id, description, notes, reference, tags, entry_descriptions
+    FROM transaction_search_source WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER transaction_search_delete AFTER DELETE ON transactions
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = OLD.id;
+END;
+
+CREATE TRIGGER transaction_search_tag_insert AFTER INSERT ON transaction_tags
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = NEW.transaction_id;
+    INSERT INTO transaction_search (rowid, description, notes, reference, tags, entry_descriptions)
+    SELECT id, description, notes, reference, tags, entry_descriptions
+    FROM transaction_search_source WHERE id = NEW.transaction_id;
+END;
+
+CREATE TRIGGER transaction_search_tag_delete AFTER DELETE ON transaction_tags
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = OLD.transaction_id;
+    INSERT INTO transaction_search (rowid, description, notes, reference, tags, entry_descriptions)
+    SELECT id, description, notes, reference, tags, entry_descriptions
+    FROM transaction_search_source WHERE id = OLD.transaction_id;
+END;
+
+-- Entries only matter for their description
+CREATE TRIGGER transaction_search_entry_insert AFTER INSERT ON transaction_entries
+WHEN NEW.description IS NOT NULL
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = NEW.transaction_id;
+    INSERT INTO transaction_search (rowid, description, notes, reference, tags, entry_descriptions)
+    SELECT id, description, notes, reference, tags, entry_descriptions
+    FROM transaction_search_source WHERE id = NEW.transaction_id;
+END;
+
+CREATE TRIGGER transaction_search_entry_update AFTER UPDATE OF description ON transaction_entries
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = NEW.transaction_id;
+    INSERT INTO transaction_search (rowid, description, notes, reference, tags, entry_descriptions)
+    SELECT id, description, notes, reference, tags, entry_descriptions
+    FROM transaction_search_source WHERE id = NEW.transaction_id;
+END;
+
+CREATE TRIGGER transaction_search_entry_delete AFTER DELETE ON transaction_entries
+WHEN OLD.description IS NOT NULL
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = OLD.transaction_id;
+    INSERT INTO transaction_search (rowid, description, notes, reference, tags, entry_descriptions)
+    SELECT id, description, notes, reference, tags, entry_descriptions
+    FROM transaction_search_source WHERE id = OLD.transaction_id;
+END;
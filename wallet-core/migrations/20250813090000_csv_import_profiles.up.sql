@@ -0,0 +1,21 @@
+-- How to read the CSV statements of one bank account. Column indices are
+-- zero-based; amounts come either from one signed column or from separate
+-- withdrawal and deposit columns.
+CREATE TABLE csv_import_profiles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    account_id INTEGER NOT NULL REFERENCES accounts(id),
+    counter_account_id INTEGER NOT NULL REFERENCES accounts(id),
+    delimiter TEXT NOT NULL DEFAULT ',',
+    skip_rows INTEGER NOT NULL DEFAULT 1,
+    date_column INTEGER NOT NULL,
+    date_format TEXT NOT NULL DEFAULT '%Y-%m-%d',
+    description_column INTEGER NOT NULL,
+    reference_column INTEGER,
+    amount_column INTEGER,
+    withdrawal_column INTEGER,
+    deposit_column INTEGER,
+    negate_amounts BOOLEAN NOT NULL DEFAULT 0,
+    decimal_separator TEXT NOT NULL DEFAULT 'point' CHECK (decimal_separator IN ('point', 'comma')),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,3 @@
+-- The original codes are not kept, so normalized codes and the commodities
+-- registered for legacy codes stay in place.
+SELECT 1;
@@ -0,0 +1,47 @@
+-- Before the registry, accounts and entries took any three-character string as
+-- currency code. Codes are trimmed and upper-cased so they match the registry;
+-- codes that still are not valid registry codes get a hex-encoded replacement.
+CREATE TEMP TABLE legacy_currency_codes AS
+SELECT
+    code AS old_code,
+    CASE
+        WHEN UPPER(TRIM(code)) = ''
+            OR LENGTH(UPPER(TRIM(code))) > 10
+            OR UPPER(TRIM(code)) GLOB '*[^A-Z0-9.-]*'
+        THEN SUBSTR('X' || HEX(code), 1, 10)
+        ELSE UPPER(TRIM(code))
+    END AS new_code
+FROM (
+    SELECT currency AS code FROM accounts
+    UNION
+    SELECT currency FROM transaction_entries
+    UNION
+    SELECT price_currency FROM transaction_entries WHERE price_currency IS NOT NULL
+);
+
+UPDATE accounts
+SET currency = (SELECT new_code FROM legacy_currency_codes WHERE old_code = accounts.currency)
+WHERE currency IN (SELECT old_code FROM legacy_currency_codes WHERE old_code <> new_code);
+
+UPDATE transaction_entries
+SET currency = (
+    SELECT new_code FROM legacy_currency_codes WHERE old_code = transaction_entries.currency
+)
+WHERE currency IN (SELECT old_code FROM legacy_currency_codes WHERE old_code <> new_code);
+
+UPDATE transaction_entries
+SET price_currency = (
+    SELECT new_code FROM legacy_currency_codes WHERE old_code = transaction_entries.price_currency
+)
+WHERE price_currency IN (SELECT old_code FROM legacy_currency_codes WHERE old_code <> new_code);
+
+-- Codes unknown to the registry become user commodities, so the accounts and
+-- entries using them stay readable and can be fixed up by hand. Their minor
+-- unit scale was never recorded, so two decimals are assumed.
+INSERT INTO currencies (code, name, minor_unit_scale, symbol, kind, is_builtin)
+SELECT new_code, 'Legacy currency ' || MIN(TRIM(old_code)), 2, new_code, 'commodity', FALSE
+FROM legacy_currency_codes
+WHERE new_code NOT IN (SELECT code FROM currencies)
+GROUP BY new_code;
+
+DROP TABLE legacy_currency_codes;
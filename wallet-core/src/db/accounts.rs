@@ -1,9 +1,70 @@
-use sqlx::Row;
+use chrono::NaiveDate;
+use sqlx::{Row, SqliteConnection};
 use std::sync::Arc;
 
-use crate::AccountNode;
-use crate::errors::Result;
+use crate::db::currencies::registry_currency;
+use crate::db::{audit, undo};
+use crate::errors::{Result, WalletError};
+use crate::models::register::RegisterEntry;
 use crate::{Account, db::connection::Database};
+use crate::{AccountNode, AccountType, AuditAction, AuditEntityType, Currency};
+
+/// Raw debit/credit sums of one account in one currency
+#[derive(Debug, Clone)]
+pub struct AccountSums {
+    pub account_id: i64,
+    pub account_name: String,
+    pub currency: Currency,
+    pub total_debits: i64,
+    pub total_credits: i64,
+}
+
+/// Raw debit/credit sums of one account in one currency on one day, counting
+/// only the transactions that move one of a set of cash accounts
+#[derive(Debug, Clone)]
+pub struct CounterAccountSums {
+    pub transaction_date: NaiveDate,
+    pub account_id: i64,
+    pub account_name: String,
+    pub account_type: AccountType,
+    pub currency: Currency,
+    pub total_debits: i64,
+    pub total_credits: i64,
+}
+
+/// Raw debit/credit sums of one account in one currency on one day
+#[derive(Debug, Clone)]
+pub struct DailyAccountSums {
+    pub transaction_date: NaiveDate,
+    pub account_id: i64,
+    pub account_name: String,
+    pub account_type: AccountType,
+    pub currency: Currency,
+    pub total_debits: i64,
+    pub total_credits: i64,
+}
+
+/// Raw debit/credit sums of one account on one day in the currency its entries
+/// balance in: the price currency for entries with a price, their own otherwise
+#[derive(Debug, Clone)]
+pub struct DailyBalancingSums {
+    pub transaction_date: NaiveDate,
+    pub account_id: i64,
+    pub account_name: String,
+    pub currency: Currency,
+    pub total_debits: i64,
+    pub total_credits: i64,
+}
+
+/// Raw debit/credit sums of one account in one currency within one transaction
+#[derive(Debug, Clone)]
+pub struct TransactionAccountSums {
+    pub transaction_id: i64,
+    pub account_id: i64,
+    pub currency: Currency,
+    pub total_debits: i64,
+    pub total_credits: i64,
+}
 
 pub struct AccountRepository {
     db: Arc<Database>,
@@ -15,6 +76,19 @@ impl AccountRepository {
     }
 
     pub async fn create(&self, account: &Account) -> Result<Account> {
+        let mut tx = self.db.pool.begin().await?;
+
+        let (created, audit_id) = Self::insert(&mut tx, account).await?;
+        undo::push(&mut tx, audit_id).await?;
+
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    /// Insert an account as part of a larger SQL transaction. Returns it with the
+    /// id of its audit record, which the caller pushes on the undo stack if the
+    /// creation can be undone.
+    pub async fn insert(conn: &mut SqliteConnection, account: &Account) -> Result<(Account, i64)> {
         let id = sqlx::query(
             r#"
             INSERT INTO accounts (name, account_type, parent_id, currency, description, is_active)
@@ -27,19 +101,33 @@ impl AccountRepository {
         .bind(account.currency.code())
         .bind(&account.description)
         .bind(account.is_active)
-        .execute(&self.db.pool)
+        .execute(&mut *conn)
         .await?
         .last_insert_rowid();
 
-        self.get_by_id(id).await
+        let created = Self::fetch_by_id(conn, id).await?;
+        let audit_id = audit::record(
+            conn,
+            AuditEntityType::Account,
+            id,
+            AuditAction::Create,
+            None,
+            Some(&created),
+        )
+        .await?;
+
+        Ok((created, audit_id))
     }
 
     pub async fn get_all(&self) -> Result<Vec<Account>> {
         let accounts: Vec<Account> = sqlx::query_as(
             r#"
-            SELECT id, name, account_type, parent_id, currency, description, is_active, created_at, updated_at
-            FROM accounts
-            ORDER BY created_at DESC
+            SELECT a.id, a.name, a.account_type, a.parent_id, a.currency,
+                   c.minor_unit_scale AS currency_scale, c.symbol AS currency_symbol,
+                   a.description, a.is_active, a.created_at, a.updated_at
+            FROM accounts a
+            LEFT JOIN currencies c ON c.code = a.currency
+            ORDER BY a.created_at DESC
             "#,
         )
         .fetch_all(&self.db.pool)
@@ -81,9 +169,11 @@ impl AccountRepository {
                 JOIN account_tree t ON a.parent_id = t.id
             )
             SELECT 
-                id, name, account_type, parent_id, currency, description, 
-                is_active, created_at, updated_at, level, path
-            FROM account_tree 
+                t.id, t.name, t.account_type, t.parent_id, t.currency,
+                c.minor_unit_scale AS currency_scale, c.symbol AS currency_symbol,
+                t.description, t.is_active, t.created_at, t.updated_at, t.level, t.path
+            FROM account_tree t
+            LEFT JOIN currencies c ON c.code = t.currency
             ORDER BY"#
         } else {
             // Only active accounts (current behavior)
@@ -111,22 +201,24 @@ impl AccountRepository {
                 WHERE a.is_active = true
             )
             SELECT 
-                id, name, account_type, parent_id, currency, description, 
-                is_active, created_at, updated_at, level, path
-            FROM account_tree 
+                t.id, t.name, t.account_type, t.parent_id, t.currency,
+                c.minor_unit_scale AS currency_scale, c.symbol AS currency_symbol,
+                t.description, t.is_active, t.created_at, t.updated_at, t.level, t.path
+            FROM account_tree t
+            LEFT JOIN currencies c ON c.code = t.currency
             ORDER BY"#
         };
 
         let full_query = format!(
             "{query} 
-                CASE account_type 
+                CASE t.account_type 
                     WHEN 'Asset' THEN 1 
                     WHEN 'Liability' THEN 2 
                     WHEN 'Equity' THEN 3 
                     WHEN 'Income' THEN 4 
                     WHEN 'Expense' THEN 5 
                 END,
-                path"
+                t.path"
         );
 
         let nodes: Vec<AccountNode> = sqlx::query_as(&full_query).fetch_all(&self.db.pool).await?;
@@ -134,23 +226,34 @@ impl AccountRepository {
     }
 
     pub async fn get_by_id(&self, id: i64) -> Result<Account> {
+        let mut conn = self.db.pool.acquire().await?;
+        Self::fetch_by_id(&mut conn, id).await
+    }
+
+    async fn fetch_by_id(conn: &mut SqliteConnection, id: i64) -> Result<Account> {
         let account: Account = sqlx::query_as(
             r#"
-            SELECT id, name, account_type, parent_id, currency, description, is_active, created_at, updated_at
-            FROM accounts
-            WHERE id = ?1
-            "#).bind(id)
-        .fetch_one(&self.db.pool)
+            SELECT a.id, a.name, a.account_type, a.parent_id, a.currency,
+                   c.minor_unit_scale AS currency_scale, c.symbol AS currency_symbol,
+                   a.description, a.is_active, a.created_at, a.updated_at
+            FROM accounts a
+            LEFT JOIN currencies c ON c.code = a.currency
+            WHERE a.id = ?1
+            "#,
+        )
+        .bind(id)
+        .fetch_one(conn)
         .await?;
         Ok(account)
     }
 
-    /// Get raw debit/credit sums for an account from transaction entries
+    /// Get raw debit/credit sums for an account from transaction entries, one
+    /// per currency
     pub async fn get_account_transaction_sums(
         &self,
         account_id: i64,
-    ) -> Result<Option<(i64, i64, String)>> {
-        let row = sqlx::query(
+    ) -> Result<Vec<(i64, i64, String)>> {
+        let rows = sqlx::query(
             r#"
             SELECT 
                 COALESCE(SUM(CASE WHEN entry_type = 'debit' THEN amount_minor ELSE 0 END), 0) as total_debits,
@@ -162,18 +265,19 @@ impl AccountRepository {
             "#,
         )
         .bind(account_id)
-        .fetch_optional(&self.db.pool)
+        .fetch_all(&self.db.pool)
         .await?;
 
-        match row {
-            Some(row) => {
-                let total_debits: i64 = row.get("total_debits");
-                let total_credits: i64 = row.get("total_credits");
-                let currency: String = row.get("currency");
-                Ok(Some((total_debits, total_credits, currency)))
-            }
-            None => Ok(None),
-        }
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get("total_debits"),
+                    row.get("total_credits"),
+                    row.get("currency"),
+                )
+            })
+            .collect())
     }
 
     /// Get all descendant account IDs using recursive CTE
@@ -205,9 +309,9 @@ impl AccountRepository {
     pub async fn get_multiple_accounts_transaction_sums(
         &self,
         account_ids: &[i64],
-    ) -> Result<Option<(i64, i64, String)>> {
+    ) -> Result<Vec<(i64, i64, String)>> {
         if account_ids.is_empty() {
-            return Ok(None);
+            return Ok(Vec::new());
         }
 
         // Create placeholders for the IN clause
@@ -229,26 +333,77 @@ impl AccountRepository {
             query_builder = query_builder.bind(account_id);
         }
 
-        let row = query_builder.fetch_optional(&self.db.pool).await?;
+        let rows = query_builder.fetch_all(&self.db.pool).await?;
 
-        match row {
-            Some(row) => {
-                let total_debits: i64 = row.get("total_debits");
-                let total_credits: i64 = row.get("total_credits");
-                let currency: String = row.get("currency");
-                Ok(Some((total_debits, total_credits, currency)))
-            }
-            None => Ok(None),
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get("total_debits"),
+                    row.get("total_credits"),
+                    row.get("currency"),
+                )
+            })
+            .collect())
+    }
+
+    /// Raw debit/credit sums for multiple accounts over the transactions dated
+    /// before `before_date`
+    pub async fn get_multiple_accounts_transaction_sums_before_date(
+        &self,
+        account_ids: &[i64],
+        before_date: NaiveDate,
+    ) -> Result<Vec<(i64, i64, String)>> {
+        if account_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let placeholders = vec!["?"; account_ids.len()].join(",");
+        let query = format!(
+            r#"
+            SELECT 
+                COALESCE(SUM(CASE WHEN entry_type = 'debit' THEN amount_minor ELSE 0 END), 0) as total_debits,
+                COALESCE(SUM(CASE WHEN entry_type = 'credit' THEN amount_minor ELSE 0 END), 0) as total_credits,
+                currency
+            FROM transaction_entries te
+            JOIN transactions t ON te.transaction_id = t.id
+            WHERE te.account_id IN ({placeholders}) AND t.transaction_date < ?
+            GROUP BY currency
+            "#
+        );
+
+        let mut query_builder = sqlx::query(&query);
+        for account_id in account_ids {
+            query_builder = query_builder.bind(account_id);
         }
+
+        let rows = query_builder
+            .bind(before_date)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get("total_debits"),
+                    row.get("total_credits"),
+                    row.get("currency"),
+                )
+            })
+            .collect())
     }
 
     pub async fn get_children(&self, parent_id: i64) -> Result<Vec<Account>> {
         let accounts: Vec<Account> = sqlx::query_as(
             r#"
-            SELECT id, name, account_type, parent_id, currency, description, is_active, created_at, updated_at
-            FROM accounts
-            WHERE parent_id = ?1 AND is_active = TRUE
-            ORDER BY name
+            SELECT a.id, a.name, a.account_type, a.parent_id, a.currency,
+                   c.minor_unit_scale AS currency_scale, c.symbol AS currency_symbol,
+                   a.description, a.is_active, a.created_at, a.updated_at
+            FROM accounts a
+            LEFT JOIN currencies c ON c.code = a.currency
+            WHERE a.parent_id = ?1 AND a.is_active = TRUE
+            ORDER BY a.name
             "#,
         )
         .bind(parent_id)
@@ -258,6 +413,9 @@ impl AccountRepository {
     }
 
     pub async fn deactivate(&self, id: i64) -> Result<()> {
+        let mut tx = self.db.pool.begin().await?;
+        let before = Self::fetch_by_id(&mut tx, id).await?;
+
         sqlx::query(
             r#"
             UPDATE accounts 
@@ -266,8 +424,22 @@ impl AccountRepository {
             "#,
         )
         .bind(id)
-        .execute(&self.db.pool)
+        .execute(&mut *tx)
         .await?;
+
+        let after = Self::fetch_by_id(&mut tx, id).await?;
+        let audit_id = audit::record(
+            &mut tx,
+            AuditEntityType::Account,
+            id,
+            AuditAction::Deactivate,
+            Some(&before),
+            Some(&after),
+        )
+        .await?;
+        undo::push(&mut tx, audit_id).await?;
+
+        tx.commit().await?;
         Ok(())
     }
 
@@ -278,6 +450,9 @@ impl AccountRepository {
             )
         })?;
 
+        let mut tx = self.db.pool.begin().await?;
+        let before = Self::fetch_by_id(&mut tx, id).await?;
+
         sqlx::query(
             r#"
             UPDATE accounts 
@@ -288,18 +463,600 @@ impl AccountRepository {
         .bind(id)
         .bind(&account.name)
         .bind(&account.description)
-        .execute(&self.db.pool)
+        .execute(&mut *tx)
+        .await?;
+
+        let after = Self::fetch_by_id(&mut tx, id).await?;
+        let audit_id = audit::record(
+            &mut tx,
+            AuditEntityType::Account,
+            id,
+            AuditAction::Update,
+            Some(&before),
+            Some(&after),
+        )
+        .await?;
+        undo::push(&mut tx, audit_id).await?;
+
+        tx.commit().await?;
+        Ok(after)
+    }
+
+    /// Bring an account to a state recorded in the audit log: recreate it with its
+    /// original id, update it, or remove it when `target` is `None`
+    pub async fn restore(
+        conn: &mut SqliteConnection,
+        id: i64,
+        target: Option<&Account>,
+    ) -> Result<()> {
+        let current = match Self::fetch_by_id(conn, id).await {
+            Ok(account) => Some(account),
+            Err(WalletError::DatabaseError(sqlx::Error::RowNotFound)) => None,
+            Err(e) => return Err(e),
+        };
+
+        let action = match (&current, target) {
+            (None, None) => return Ok(()),
+            (Some(account), None) => {
+                let usage: i64 = sqlx::query_scalar(
+                    r#"
+                    SELECT (SELECT COUNT(*) FROM transaction_entries WHERE account_id = ?1)
+                         + (SELECT COUNT(*) FROM accounts WHERE parent_id = ?1)
+                    "#,
+                )
+                .bind(id)
+                .fetch_one(&mut *conn)
+                .await?;
+                if usage > 0 {
+                    return Err(WalletError::ValidationError(format!(
+                        "Account {} has transactions or sub-accounts and cannot be removed",
+                        account.name
+                    )));
+                }
+
+                sqlx::query("DELETE FROM accounts WHERE id = ?1")
+                    .bind(id)
+                    .execute(&mut *conn)
+                    .await?;
+                AuditAction::Delete
+            }
+            (None, Some(account)) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO accounts (
+                        id, name, account_type, parent_id, currency, description,
+                        is_active, created_at, updated_at
+                    )
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                    "#,
+                )
+                .bind(id)
+                .bind(&account.name)
+                .bind(&account.account_type)
+                .bind(account.parent_id)
+                .bind(account.currency.code())
+                .bind(&account.description)
+                .bind(account.is_active)
+                .bind(account.created_at)
+                .bind(account.updated_at)
+                .execute(&mut *conn)
+                .await?;
+                AuditAction::Create
+            }
+            (Some(_), Some(account)) => {
+                sqlx::query(
+                    r#"
+                    UPDATE accounts
+                    SET name = ?2, description = ?3, is_active = ?4, updated_at = CURRENT_TIMESTAMP
+                    WHERE id = ?1
+                    "#,
+                )
+                .bind(id)
+                .bind(&account.name)
+                .bind(&account.description)
+                .bind(account.is_active)
+                .execute(&mut *conn)
+                .await?;
+                AuditAction::Update
+            }
+        };
+
+        let after = match target {
+            Some(_) => Some(Self::fetch_by_id(conn, id).await?),
+            None => None,
+        };
+        audit::record(
+            conn,
+            AuditEntityType::Account,
+            id,
+            action,
+            current.as_ref(),
+            after.as_ref(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Get raw debit/credit sums of every account of a type, one row per account
+    /// and currency, optionally only counting transactions before a date
+    pub async fn get_account_sums_by_type(
+        &self,
+        account_type: &AccountType,
+        before_date: Option<NaiveDate>,
+    ) -> Result<Vec<AccountSums>> {
+        self.query_account_sums(Some(account_type), None, before_date, false)
+            .await
+    }
+
+    /// Get raw debit/credit sums of every account in one aggregate query, one row
+    /// per account and currency, optionally only counting transactions before a date
+    pub async fn get_all_account_sums(
+        &self,
+        before_date: Option<NaiveDate>,
+    ) -> Result<Vec<AccountSums>> {
+        self.query_account_sums(None, None, before_date, false)
+            .await
+    }
+
+    /// Get raw debit/credit sums of every account for the transactions dated from
+    /// `from_date` up to (excluding) `before_date`. Fiscal year closing
+    /// transactions are left out so Income and Expenses show their activity.
+    pub async fn get_activity_sums(
+        &self,
+        from_date: NaiveDate,
+        before_date: NaiveDate,
+    ) -> Result<Vec<AccountSums>> {
+        self.query_account_sums(None, Some(from_date), Some(before_date), true)
+            .await
+    }
+
+    /// Get raw debit/credit sums of every account of a type for the transactions
+    /// dated from `from_date` up to (excluding) `before_date`, leaving out fiscal
+    /// year closing transactions like `get_activity_sums`
+    pub async fn get_activity_sums_by_type(
+        &self,
+        account_type: &AccountType,
+        from_date: NaiveDate,
+        before_date: NaiveDate,
+    ) -> Result<Vec<AccountSums>> {
+        self.query_account_sums(Some(account_type), Some(from_date), Some(before_date), true)
+            .await
+    }
+
+    async fn query_account_sums(
+        &self,
+        account_type: Option<&AccountType>,
+        from_date: Option<NaiveDate>,
+        before_date: Option<NaiveDate>,
+        exclude_closing: bool,
+    ) -> Result<Vec<AccountSums>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT 
+                a.id as account_id,
+                a.name as account_name,
+                te.currency,
+                c.minor_unit_scale as currency_scale,
+                c.symbol as currency_symbol,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'debit' THEN te.amount_minor ELSE 0 END), 0) as total_debits,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'credit' THEN te.amount_minor ELSE 0 END), 0) as total_credits
+            FROM transaction_entries te
+            JOIN transactions t ON te.transaction_id = t.id
+            JOIN accounts a ON te.account_id = a.id
+            LEFT JOIN currencies c ON c.code = te.currency
+            WHERE (?1 IS NULL OR a.account_type = ?1)
+                AND (?2 IS NULL OR t.transaction_date >= ?2)
+                AND (?3 IS NULL OR t.transaction_date < ?3)
+                AND NOT (?4 AND t.id IN (SELECT transaction_id FROM fiscal_year_closes))
+            GROUP BY a.id, te.currency
+            ORDER BY a.id, te.currency
+            "#,
+        )
+        .bind(account_type)
+        .bind(from_date)
+        .bind(before_date)
+        .bind(exclude_closing)
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(AccountSums {
+                    account_id: row.get("account_id"),
+                    account_name: row.get("account_name"),
+                    currency: registry_currency(
+                        row.get("currency"),
+                        row.get("currency_scale"),
+                        row.get("currency_symbol"),
+                    )?,
+                    total_debits: row.get("total_debits"),
+                    total_credits: row.get("total_credits"),
+                })
+            })
+            .collect()
+    }
+
+    /// Get the total debits and credits of all entries per currency they balance
+    /// in, optionally only counting transactions before a date. Entries with a
+    /// price count at their converted amount, as transactions are balanced.
+    pub async fn get_balancing_totals(
+        &self,
+        before_date: Option<NaiveDate>,
+    ) -> Result<Vec<(Currency, i64, i64)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                COALESCE(te.price_currency, te.currency) as currency,
+                c.minor_unit_scale as currency_scale,
+                c.symbol as currency_symbol,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'debit' THEN COALESCE(te.price_amount_minor, te.amount_minor) ELSE 0 END), 0) as total_debits,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'credit' THEN COALESCE(te.price_amount_minor, te.amount_minor) ELSE 0 END), 0) as total_credits
+            FROM transaction_entries te
+            JOIN transactions t ON te.transaction_id = t.id
+            LEFT JOIN currencies c ON c.code = COALESCE(te.price_currency, te.currency)
+            WHERE ?1 IS NULL OR t.transaction_date < ?1
+            GROUP BY COALESCE(te.price_currency, te.currency)
+            ORDER BY currency
+            "#,
+        )
+        .bind(before_date)
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok((
+                    registry_currency(
+                        row.get("currency"),
+                        row.get("currency_scale"),
+                        row.get("currency_symbol"),
+                    )?,
+                    row.get("total_debits"),
+                    row.get("total_credits"),
+                ))
+            })
+            .collect()
+    }
+
+    /// Get the debit/credit sums of the counter-accounts of a set of cash accounts,
+    /// one row per day, account and currency ordered by date, for the
+    /// transactions dated from `from_date` up to (excluding) `before_date` that
+    /// move one of the cash accounts. Entries with a price count at their
+    /// converted amount, so counter-accounts in another currency are valued as
+    /// booked.
+    pub async fn get_counter_account_sums(
+        &self,
+        cash_account_ids: &[i64],
+        from_date: NaiveDate,
+        before_date: NaiveDate,
+    ) -> Result<Vec<CounterAccountSums>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                t.transaction_date,
+                a.id as account_id,
+                a.name as account_name,
+                a.account_type,
+                COALESCE(te.price_currency, te.currency) as currency,
+                c.minor_unit_scale as currency_scale,
+                c.symbol as currency_symbol,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'debit' THEN COALESCE(te.price_amount_minor, te.amount_minor) ELSE 0 END), 0) as total_debits,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'credit' THEN COALESCE(te.price_amount_minor, te.amount_minor) ELSE 0 END), 0) as total_credits
+            FROM transaction_entries te
+            JOIN transactions t ON te.transaction_id = t.id
+            JOIN accounts a ON te.account_id = a.id
+            LEFT JOIN currencies c ON c.code = COALESCE(te.price_currency, te.currency)
+            WHERE t.transaction_date >= ?2 AND t.transaction_date < ?3
+                AND te.account_id NOT IN (SELECT value FROM json_each(?1))
+                AND t.id IN (
+                    SELECT transaction_id FROM transaction_entries
+                    WHERE account_id IN (SELECT value FROM json_each(?1))
+                )
+            GROUP BY t.transaction_date, a.id, COALESCE(te.price_currency, te.currency)
+            ORDER BY t.transaction_date, a.id, currency
+            "#,
+        )
+        .bind(serde_json::to_string(cash_account_ids)?)
+        .bind(from_date)
+        .bind(before_date)
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(CounterAccountSums {
+                    transaction_date: row.get("transaction_date"),
+                    account_id: row.get("account_id"),
+                    account_name: row.get("account_name"),
+                    account_type: row.get("account_type"),
+                    currency: registry_currency(
+                        row.get("currency"),
+                        row.get("currency_scale"),
+                        row.get("currency_symbol"),
+                    )?,
+                    total_debits: row.get("total_debits"),
+                    total_credits: row.get("total_credits"),
+                })
+            })
+            .collect()
+    }
+
+    /// Get the debit/credit sums of a set of accounts per day, for the
+    /// transactions dated from `from_date` up to (excluding) `before_date`,
+    /// ordered by date
+    pub async fn get_daily_account_sums(
+        &self,
+        account_ids: &[i64],
+        from_date: NaiveDate,
+        before_date: NaiveDate,
+    ) -> Result<Vec<DailyAccountSums>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                t.transaction_date,
+                a.id as account_id,
+                a.name as account_name,
+                a.account_type,
+                te.currency,
+                c.minor_unit_scale as currency_scale,
+                c.symbol as currency_symbol,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'debit' THEN te.amount_minor ELSE 0 END), 0) as total_debits,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'credit' THEN te.amount_minor ELSE 0 END), 0) as total_credits
+            FROM transaction_entries te
+            JOIN transactions t ON te.transaction_id = t.id
+            JOIN accounts a ON te.account_id = a.id
+            LEFT JOIN currencies c ON c.code = te.currency
+            WHERE te.account_id IN (SELECT value FROM json_each(?1))
+                AND t.transaction_date >= ?2 AND t.transaction_date < ?3
+            GROUP BY t.transaction_date, a.id, te.currency
+            ORDER BY t.transaction_date, a.id, te.currency
+            "#,
+        )
+        .bind(serde_json::to_string(account_ids)?)
+        .bind(from_date)
+        .bind(before_date)
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(DailyAccountSums {
+                    transaction_date: row.get("transaction_date"),
+                    account_id: row.get("account_id"),
+                    account_name: row.get("account_name"),
+                    account_type: row.get("account_type"),
+                    currency: registry_currency(
+                        row.get("currency"),
+                        row.get("currency_scale"),
+                        row.get("currency_symbol"),
+                    )?,
+                    total_debits: row.get("total_debits"),
+                    total_credits: row.get("total_credits"),
+                })
+            })
+            .collect()
+    }
+
+    /// Get the debit/credit sums of every Asset and Liability account per day, for
+    /// the transactions dated before `before_date`, ordered by date so balances
+    /// can be accumulated in a single pass
+    pub async fn get_daily_net_worth_sums(
+        &self,
+        before_date: NaiveDate,
+    ) -> Result<Vec<DailyAccountSums>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                t.transaction_date,
+                a.id as account_id,
+                a.name as account_name,
+                a.account_type,
+                te.currency,
+                c.minor_unit_scale as currency_scale,
+                c.symbol as currency_symbol,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'debit' THEN te.amount_minor ELSE 0 END), 0) as total_debits,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'credit' THEN te.amount_minor ELSE 0 END), 0) as total_credits
+            FROM transaction_entries te
+            JOIN transactions t ON te.transaction_id = t.id
+            JOIN accounts a ON te.account_id = a.id
+            LEFT JOIN currencies c ON c.code = te.currency
+            WHERE a.account_type IN ('asset', 'liability') AND t.transaction_date < ?1
+            GROUP BY t.transaction_date, a.id, te.currency
+            ORDER BY t.transaction_date, a.id, te.currency
+            "#,
+        )
+        .bind(before_date)
+        .fetch_all(&self.db.pool)
         .await?;
 
-        self.get_by_id(id).await
+        rows.iter()
+            .map(|row| {
+                Ok(DailyAccountSums {
+                    transaction_date: row.get("transaction_date"),
+                    account_id: row.get("account_id"),
+                    account_name: row.get("account_name"),
+                    account_type: row.get("account_type"),
+                    currency: registry_currency(
+                        row.get("currency"),
+                        row.get("currency_scale"),
+                        row.get("currency_symbol"),
+                    )?,
+                    total_debits: row.get("total_debits"),
+                    total_credits: row.get("total_credits"),
+                })
+            })
+            .collect()
+    }
+
+    /// Get the debit/credit sums of every account per day in the currency entries
+    /// balance in, for the transactions dated before `before_date`, ordered by
+    /// date so amounts can be valued at the rate of their day in a single pass
+    pub async fn get_daily_balancing_sums(
+        &self,
+        before_date: NaiveDate,
+    ) -> Result<Vec<DailyBalancingSums>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                t.transaction_date,
+                a.id as account_id,
+                a.name as account_name,
+                COALESCE(te.price_currency, te.currency) as currency,
+                c.minor_unit_scale as currency_scale,
+                c.symbol as currency_symbol,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'debit' THEN COALESCE(te.price_amount_minor, te.amount_minor) ELSE 0 END), 0) as total_debits,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'credit' THEN COALESCE(te.price_amount_minor, te.amount_minor) ELSE 0 END), 0) as total_credits
+            FROM transaction_entries te
+            JOIN transactions t ON te.transaction_id = t.id
+            JOIN accounts a ON te.account_id = a.id
+            LEFT JOIN currencies c ON c.code = COALESCE(te.price_currency, te.currency)
+            WHERE t.transaction_date < ?1
+            GROUP BY t.transaction_date, a.id, COALESCE(te.price_currency, te.currency)
+            ORDER BY t.transaction_date, a.id, currency
+            "#,
+        )
+        .bind(before_date)
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(DailyBalancingSums {
+                    transaction_date: row.get("transaction_date"),
+                    account_id: row.get("account_id"),
+                    account_name: row.get("account_name"),
+                    currency: registry_currency(
+                        row.get("currency"),
+                        row.get("currency_scale"),
+                        row.get("currency_symbol"),
+                    )?,
+                    total_debits: row.get("total_debits"),
+                    total_credits: row.get("total_credits"),
+                })
+            })
+            .collect()
+    }
+
+    /// Get the debit/credit sums of every account of a type per transaction, for
+    /// the transactions dated from `from_date` up to (excluding) `before_date`.
+    /// Fiscal year closing transactions are left out.
+    pub async fn get_transaction_sums_by_type(
+        &self,
+        account_type: &AccountType,
+        from_date: NaiveDate,
+        before_date: NaiveDate,
+    ) -> Result<Vec<TransactionAccountSums>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                t.id as transaction_id,
+                a.id as account_id,
+                te.currency,
+                c.minor_unit_scale as currency_scale,
+                c.symbol as currency_symbol,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'debit' THEN te.amount_minor ELSE 0 END), 0) as total_debits,
+                COALESCE(SUM(CASE WHEN te.entry_type = 'credit' THEN te.amount_minor ELSE 0 END), 0) as total_credits
+            FROM transaction_entries te
+            JOIN transactions t ON te.transaction_id = t.id
+            JOIN accounts a ON te.account_id = a.id
+            LEFT JOIN currencies c ON c.code = te.currency
+            WHERE a.account_type = ?1
+                AND t.transaction_date >= ?2 AND t.transaction_date < ?3
+                AND t.id NOT IN (SELECT transaction_id FROM fiscal_year_closes)
+            GROUP BY t.id, a.id, te.currency
+            ORDER BY t.id, a.id, te.currency
+            "#,
+        )
+        .bind(account_type)
+        .bind(from_date)
+        .bind(before_date)
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(TransactionAccountSums {
+                    transaction_id: row.get("transaction_id"),
+                    account_id: row.get("account_id"),
+                    currency: registry_currency(
+                        row.get("currency"),
+                        row.get("currency_scale"),
+                        row.get("currency_symbol"),
+                    )?,
+                    total_debits: row.get("total_debits"),
+                    total_credits: row.get("total_credits"),
+                })
+            })
+            .collect()
+    }
+
+    /// Get a page of the register of an account, most recent entries first. The
+    /// running balance is computed over the whole history with a window function,
+    /// per currency and following the account's normal balance.
+    pub async fn get_register(
+        &self,
+        account_id: i64,
+        debit_normal: bool,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Vec<RegisterEntry>> {
+        let entries = sqlx::query_as(
+            r#"
+            SELECT
+                te.id AS entry_id,
+                te.transaction_id,
+                t.transaction_date,
+                t.description,
+                te.description AS entry_description,
+                t.reference,
+                (
+                    SELECT json_group_array(
+                        json_object('account_id', other.id, 'account_name', other.name)
+                    )
+                    FROM (
+                        SELECT DISTINCT a.id, a.name
+                        FROM transaction_entries other_entry
+                        JOIN accounts a ON a.id = other_entry.account_id
+                        WHERE other_entry.transaction_id = te.transaction_id
+                            AND other_entry.account_id != te.account_id
+                        ORDER BY a.name
+                    ) other
+                ) AS counterparts,
+                te.entry_type,
+                te.amount_minor,
+                te.currency,
+                c.minor_unit_scale AS currency_scale,
+                c.symbol AS currency_symbol,
+                te.status,
+                SUM(CASE WHEN (te.entry_type = 'debit') = ?2 THEN te.amount_minor ELSE -te.amount_minor END)
+                    OVER (
+                        PARTITION BY te.currency
+                        ORDER BY t.transaction_date, t.id, te.id
+                        ROWS UNBOUNDED PRECEDING
+                    ) AS balance_minor
+            FROM transaction_entries te
+            JOIN transactions t ON t.id = te.transaction_id
+            LEFT JOIN currencies c ON c.code = te.currency
+            WHERE te.account_id = ?1
+            ORDER BY t.transaction_date DESC, t.id DESC, te.id DESC
+            LIMIT ?3 OFFSET ?4
+            "#,
+        )
+        .bind(account_id)
+        .bind(debit_normal)
+        .bind(limit.map(i64::from).unwrap_or(-1))
+        .bind(offset.unwrap_or(0))
+        .fetch_all(&self.db.pool)
+        .await?;
+        Ok(entries)
     }
 
     pub async fn get_account_transaction_sums_before_date(
         &self,
         account_id: i64,
         before_date: chrono::NaiveDate,
-    ) -> Result<Option<(i64, i64, String)>> {
-        let row = sqlx::query(
+    ) -> Result<Vec<(i64, i64, String)>> {
+        let rows = sqlx::query(
             r#"
             SELECT 
                 COALESCE(SUM(CASE WHEN entry_type = 'debit' THEN amount_minor ELSE 0 END), 0) as total_debits,
@@ -313,18 +1070,19 @@ impl AccountRepository {
         )
         .bind(account_id)
         .bind(before_date)
-        .fetch_optional(&self.db.pool)
+        .fetch_all(&self.db.pool)
         .await?;
 
-        match row {
-            Some(row) => {
-                let total_debits: i64 = row.get("total_debits");
-                let total_credits: i64 = row.get("total_credits");
-                let currency: String = row.get("currency");
-                Ok(Some((total_debits, total_credits, currency)))
-            }
-            None => Ok(None),
-        }
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get("total_debits"),
+                    row.get("total_credits"),
+                    row.get("currency"),
+                )
+            })
+            .collect())
     }
 }
 
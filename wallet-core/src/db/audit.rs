@@ -0,0 +1,95 @@
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::SqliteConnection;
+use std::sync::Arc;
+
+use crate::db::connection::Database;
+use crate::errors::Result;
+use crate::{AuditAction, AuditEntityType, AuditEntry};
+
+/// Name recorded as the author of changes: the operating system user
+pub fn current_actor() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Append a row to the audit log. Takes the connection of the SQL transaction
+/// making the change so the log entry commits or rolls back with it.
+pub async fn record<T: Serialize>(
+    conn: &mut SqliteConnection,
+    entity_type: AuditEntityType,
+    entity_id: i64,
+    action: AuditAction,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> Result<i64> {
+    let before_json = before.map(serde_json::to_string).transpose()?;
+    let after_json = after.map(serde_json::to_string).transpose()?;
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor, before_json, after_json, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(entity_type)
+    .bind(entity_id)
+    .bind(action)
+    .bind(current_actor())
+    .bind(before_json)
+    .bind(after_json)
+    .bind(Utc::now())
+    .execute(conn)
+    .await?
+    .last_insert_rowid();
+
+    Ok(id)
+}
+
+pub struct AuditRepository {
+    db: Arc<Database>,
+}
+
+impl AuditRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Every change made to one entity, oldest first
+    pub async fn get_history(
+        &self,
+        entity_type: AuditEntityType,
+        entity_id: i64,
+    ) -> Result<Vec<AuditEntry>> {
+        let entries = sqlx::query_as(
+            r#"
+            SELECT id, entity_type, entity_id, action, actor, before_json, after_json, created_at
+            FROM audit_log
+            WHERE entity_type = ? AND entity_id = ?
+            ORDER BY id
+            "#,
+        )
+        .bind(entity_type)
+        .bind(entity_id)
+        .fetch_all(&self.db.pool)
+        .await?;
+        Ok(entries)
+    }
+
+    /// Most recent changes across the ledger, newest first
+    pub async fn get_recent(&self, limit: u32) -> Result<Vec<AuditEntry>> {
+        let entries = sqlx::query_as(
+            r#"
+            SELECT id, entity_type, entity_id, action, actor, before_json, after_json, created_at
+            FROM audit_log
+            ORDER BY id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.db.pool)
+        .await?;
+        Ok(entries)
+    }
+}
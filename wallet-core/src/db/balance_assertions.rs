@@ -0,0 +1,82 @@
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::db::connection::Database;
+use crate::errors::Result;
+use crate::models::balance_assertion::BalanceAssertion;
+
+pub struct BalanceAssertionRepository {
+    db: Arc<Database>,
+}
+
+impl BalanceAssertionRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, assertion: &BalanceAssertion) -> Result<BalanceAssertion> {
+        let id = sqlx::query(
+            r#"
+            INSERT INTO balance_assertions (account_id, assertion_date, amount_minor, currency, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(assertion.account_id)
+        .bind(assertion.assertion_date)
+        .bind(assertion.expected.amount_minor())
+        .bind(assertion.expected.currency().code())
+        .bind(Utc::now())
+        .execute(&self.db.pool)
+        .await?
+        .last_insert_rowid();
+
+        self.get_by_id(id).await
+    }
+
+    pub async fn get_by_id(&self, id: i64) -> Result<BalanceAssertion> {
+        let assertion = sqlx::query_as(
+            r#"
+            SELECT b.id, b.account_id, b.assertion_date, b.amount_minor, b.currency,
+                   c.minor_unit_scale AS currency_scale, c.symbol AS currency_symbol,
+                   b.created_at
+            FROM balance_assertions b
+            JOIN currencies c ON c.code = b.currency
+            WHERE b.id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&self.db.pool)
+        .await?;
+        Ok(assertion)
+    }
+
+    /// Assertions of one account, or of all accounts, in date order
+    pub async fn get_all(&self, account_id: Option<i64>) -> Result<Vec<BalanceAssertion>> {
+        let assertions = sqlx::query_as(
+            r#"
+            SELECT b.id, b.account_id, b.assertion_date, b.amount_minor, b.currency,
+                   c.minor_unit_scale AS currency_scale, c.symbol AS currency_symbol,
+                   b.created_at
+            FROM balance_assertions b
+            JOIN currencies c ON c.code = b.currency
+            WHERE ?1 IS NULL OR b.account_id = ?1
+            ORDER BY b.assertion_date, b.account_id, b.id
+            "#,
+        )
+        .bind(account_id)
+        .fetch_all(&self.db.pool)
+        .await?;
+        Ok(assertions)
+    }
+
+    pub async fn delete(&self, id: i64) -> Result<()> {
+        let deleted = sqlx::query("DELETE FROM balance_assertions WHERE id = ?")
+            .bind(id)
+            .execute(&self.db.pool)
+            .await?;
+        if deleted.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        Ok(())
+    }
+}
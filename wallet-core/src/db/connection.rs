@@ -41,6 +41,10 @@ impl Database {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use super::Database;
+    use crate::{AccountService, CurrencyKind, CurrencyService};
 
     #[sqlx::test]
     async fn test_database_creation_and_migration(pool: sqlx::SqlitePool) {
@@ -53,4 +57,53 @@ mod tests {
 
         assert_eq!(count.0, 5); // Should have 5 root accounts from migration
     }
+
+    #[sqlx::test(migrations = false)]
+    async fn test_migrate_legacy_currency_codes(pool: sqlx::SqlitePool) {
+        // A database created before the currency registry
+        let mut legacy = sqlx::migrate!("./migrations");
+        legacy.migrations = legacy
+            .migrations
+            .iter()
+            .filter(|migration| migration.version < 20250802090000)
+            .cloned()
+            .collect();
+        legacy.run(&pool).await.unwrap();
+        for (name, currency) in [("Dollars", "usd"), ("Gold", "GLD"), ("Points", "p$ ")] {
+            sqlx::query(
+                "INSERT INTO accounts (name, account_type, parent_id, currency) VALUES (?, 'asset', 1, ?)",
+            )
+            .bind(name)
+            .bind(currency)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let db = Arc::new(Database { pool });
+        db.migrate().await.unwrap();
+
+        let accounts = AccountService::new(db.clone())
+            .get_accounts()
+            .await
+            .unwrap();
+        let currency = |name: &str| {
+            accounts
+                .iter()
+                .find(|account| account.name == name)
+                .map(|account| account.currency.code().to_string())
+                .unwrap()
+        };
+        assert_eq!(currency("Dollars"), "USD");
+        assert_eq!(currency("Gold"), "GLD");
+        assert_eq!(currency("Points"), "X702420");
+
+        // Codes the registry did not know are user commodities
+        let gold = CurrencyService::new(db)
+            .get_currency_info("GLD")
+            .await
+            .unwrap();
+        assert_eq!(gold.kind, CurrencyKind::Commodity);
+        assert!(!gold.is_builtin);
+    }
 }
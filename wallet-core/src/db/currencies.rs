@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use crate::db::connection::Database;
+use crate::errors::{CurrencyError, Result};
+use crate::models::money::{Currency, CurrencyInfo};
+
+pub struct CurrencyRepository {
+    db: Arc<Database>,
+}
+
+impl CurrencyRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        CurrencyRepository { db }
+    }
+
+    pub async fn create(&self, info: &CurrencyInfo) -> Result<CurrencyInfo> {
+        sqlx::query(
+            r#"
+            INSERT INTO currencies (code, name, minor_unit_scale, symbol, kind, is_builtin)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(info.currency.code())
+        .bind(&info.name)
+        .bind(info.currency.minor_unit_scale())
+        .bind(info.currency.symbol())
+        .bind(&info.kind)
+        .bind(info.is_builtin)
+        .execute(&self.db.pool)
+        .await?;
+
+        self.get_by_code(info.currency.code())
+            .await?
+            .ok_or_else(|| sqlx::Error::RowNotFound.into())
+    }
+
+    pub async fn get_by_code(&self, code: &str) -> Result<Option<CurrencyInfo>> {
+        let info: Option<CurrencyInfo> = sqlx::query_as(
+            r#"
+            SELECT code, name, minor_unit_scale, symbol, kind, is_builtin
+            FROM currencies
+            WHERE code = ?1
+            "#,
+        )
+        .bind(code)
+        .fetch_optional(&self.db.pool)
+        .await?;
+        Ok(info)
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<CurrencyInfo>> {
+        let currencies: Vec<CurrencyInfo> = sqlx::query_as(
+            r#"
+            SELECT code, name, minor_unit_scale, symbol, kind, is_builtin
+            FROM currencies
+            ORDER BY code
+            "#,
+        )
+        .fetch_all(&self.db.pool)
+        .await?;
+        Ok(currencies)
+    }
+}
+
+/// Currency from a code and the scale and symbol joined from the currency
+/// registry, which are missing when the code is not in it
+pub(crate) fn registry_currency(
+    code: String,
+    scale: Option<u8>,
+    symbol: Option<String>,
+) -> Result<Currency> {
+    let (Some(scale), Some(symbol)) = (scale, symbol) else {
+        return Err(CurrencyError::UnknownCurrency(code).into());
+    };
+    Currency::new(&code, scale, &symbol)
+}
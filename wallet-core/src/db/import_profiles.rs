@@ -0,0 +1,119 @@
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::db::connection::Database;
+use crate::errors::Result;
+use crate::models::import::CsvImportProfile;
+
+const PROFILE_COLUMNS: &str = "id, name, account_id, counter_account_id, delimiter, skip_rows, \
+    date_column, date_format, description_column, reference_column, amount_column, \
+    withdrawal_column, deposit_column, negate_amounts, decimal_separator, created_at";
+
+pub struct ImportProfileRepository {
+    db: Arc<Database>,
+}
+
+impl ImportProfileRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, profile: &CsvImportProfile) -> Result<CsvImportProfile> {
+        let id = sqlx::query(
+            r#"
+            INSERT INTO csv_import_profiles (
+                name, account_id, counter_account_id, delimiter, skip_rows, date_column,
+                date_format, description_column, reference_column, amount_column,
+                withdrawal_column, deposit_column, negate_amounts, decimal_separator, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&profile.name)
+        .bind(profile.account_id)
+        .bind(profile.counter_account_id)
+        .bind(profile.delimiter.to_string())
+        .bind(profile.skip_rows)
+        .bind(profile.date_column)
+        .bind(&profile.date_format)
+        .bind(profile.description_column)
+        .bind(profile.reference_column)
+        .bind(profile.amount_column)
+        .bind(profile.withdrawal_column)
+        .bind(profile.deposit_column)
+        .bind(profile.negate_amounts)
+        .bind(profile.decimal_separator)
+        .bind(Utc::now())
+        .execute(&self.db.pool)
+        .await?
+        .last_insert_rowid();
+
+        self.get_by_id(id).await
+    }
+
+    pub async fn update(&self, profile: &CsvImportProfile) -> Result<CsvImportProfile> {
+        let id = profile.id.unwrap_or_default();
+        let updated = sqlx::query(
+            r#"
+            UPDATE csv_import_profiles
+            SET name = ?, account_id = ?, counter_account_id = ?, delimiter = ?, skip_rows = ?,
+                date_column = ?, date_format = ?, description_column = ?, reference_column = ?,
+                amount_column = ?, withdrawal_column = ?, deposit_column = ?,
+                negate_amounts = ?, decimal_separator = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&profile.name)
+        .bind(profile.account_id)
+        .bind(profile.counter_account_id)
+        .bind(profile.delimiter.to_string())
+        .bind(profile.skip_rows)
+        .bind(profile.date_column)
+        .bind(&profile.date_format)
+        .bind(profile.description_column)
+        .bind(profile.reference_column)
+        .bind(profile.amount_column)
+        .bind(profile.withdrawal_column)
+        .bind(profile.deposit_column)
+        .bind(profile.negate_amounts)
+        .bind(profile.decimal_separator)
+        .bind(id)
+        .execute(&self.db.pool)
+        .await?;
+        if updated.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+
+        self.get_by_id(id).await
+    }
+
+    pub async fn get_by_id(&self, id: i64) -> Result<CsvImportProfile> {
+        let profile = sqlx::query_as(&format!(
+            "SELECT {PROFILE_COLUMNS} FROM csv_import_profiles WHERE id = ?"
+        ))
+        .bind(id)
+        .fetch_one(&self.db.pool)
+        .await?;
+        Ok(profile)
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<CsvImportProfile>> {
+        let profiles = sqlx::query_as(&format!(
+            "SELECT {PROFILE_COLUMNS} FROM csv_import_profiles ORDER BY name"
+        ))
+        .fetch_all(&self.db.pool)
+        .await?;
+        Ok(profiles)
+    }
+
+    pub async fn delete(&self, id: i64) -> Result<()> {
+        let deleted = sqlx::query("DELETE FROM csv_import_profiles WHERE id = ?")
+            .bind(id)
+            .execute(&self.db.pool)
+            .await?;
+        if deleted.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        Ok(())
+    }
+}
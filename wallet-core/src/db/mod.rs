@@ -1,3 +1,12 @@
 pub(crate) mod accounts;
+pub(crate) mod audit;
+pub(crate) mod balance_assertions;
 pub mod connection;
+pub(crate) mod currencies;
+pub(crate) mod import_profiles;
+pub(crate) mod periods;
+pub(crate) mod prices;
+pub(crate) mod reconciliations;
+pub(crate) mod settings;
 pub(crate) mod transactions;
+pub(crate) mod undo;
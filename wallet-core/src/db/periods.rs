@@ -0,0 +1,156 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::SqliteConnection;
+use std::sync::Arc;
+
+use crate::db::accounts::AccountRepository;
+use crate::db::connection::Database;
+use crate::db::transactions::TransactionRepository;
+use crate::errors::{Result, WalletError};
+use crate::models::period::{ClosedPeriod, FiscalYearClose};
+use crate::{Account, NewTransaction};
+
+/// Refuse changes to transactions dated in a closed period
+pub async fn ensure_open(conn: &mut SqliteConnection, date: NaiveDate) -> Result<()> {
+    let lock_date: Option<NaiveDate> =
+        sqlx::query_scalar("SELECT MAX(lock_date) FROM closed_periods")
+            .fetch_one(conn)
+            .await?;
+    match lock_date {
+        Some(lock_date) if date <= lock_date => Err(WalletError::ValidationError(format!(
+            "Period is closed up to {lock_date}, transactions dated {date} cannot change"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+pub struct PeriodRepository {
+    db: Arc<Database>,
+}
+
+impl PeriodRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Latest closed period, if any
+    pub async fn get_latest(&self) -> Result<Option<ClosedPeriod>> {
+        let period = sqlx::query_as(
+            r#"
+            SELECT id, lock_date, created_at
+            FROM closed_periods
+            ORDER BY lock_date DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.db.pool)
+        .await?;
+        Ok(period)
+    }
+
+    pub async fn create(&self, lock_date: NaiveDate) -> Result<ClosedPeriod> {
+        let mut conn = self.db.pool.acquire().await?;
+        Self::insert(&mut conn, lock_date).await
+    }
+
+    /// Reopen a closed period. When it was closed by a fiscal year close, the
+    /// closing transaction and the record of the close are removed with it, so
+    /// the year can be closed again.
+    pub async fn delete(&self, period: &ClosedPeriod) -> Result<()> {
+        let mut tx = self.db.pool.begin().await?;
+
+        sqlx::query("DELETE FROM closed_periods WHERE id = ?")
+            .bind(period.id)
+            .execute(&mut *tx)
+            .await?;
+
+        let closing_transaction_id: Option<i64> = sqlx::query_scalar(
+            "DELETE FROM fiscal_year_closes WHERE fiscal_year_end = ? RETURNING transaction_id",
+        )
+        .bind(period.lock_date)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if let Some(transaction_id) = closing_transaction_id {
+            TransactionRepository::restore(&mut tx, transaction_id, None).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Post the closing transaction of a fiscal year and close the period up to
+    /// the end of the year, atomically. The retained earnings account is
+    /// created first when it has no id yet, and its id passed to
+    /// `closing_transaction`. Both are audited but cannot be undone: reopening
+    /// the period removes the closing transaction.
+    pub async fn close_fiscal_year(
+        &self,
+        fiscal_year_end: NaiveDate,
+        retained_earnings: &Account,
+        closing_transaction: impl FnOnce(i64) -> Result<NewTransaction>,
+    ) -> Result<FiscalYearClose> {
+        let mut tx = self.db.pool.begin().await?;
+
+        let retained_earnings_id = match retained_earnings.id {
+            Some(id) => id,
+            None => {
+                let (created, _) = AccountRepository::insert(&mut tx, retained_earnings).await?;
+                created.id.ok_or_else(|| {
+                    WalletError::ValidationError("Retained earnings account has no id".to_string())
+                })?
+            }
+        };
+        let (transaction, _) = TransactionRepository::insert_transaction(
+            &mut tx,
+            closing_transaction(retained_earnings_id)?,
+            None,
+        )
+        .await?;
+        let close = sqlx::query_as(
+            r#"
+            INSERT INTO fiscal_year_closes (
+                fiscal_year_end, transaction_id, retained_earnings_account_id, created_at
+            )
+            VALUES (?, ?, ?, ?)
+            RETURNING id, fiscal_year_end, transaction_id, retained_earnings_account_id, created_at
+            "#,
+        )
+        .bind(fiscal_year_end)
+        .bind(transaction.id)
+        .bind(retained_earnings_id)
+        .bind(Utc::now())
+        .fetch_one(&mut *tx)
+        .await?;
+        Self::insert(&mut tx, fiscal_year_end).await?;
+
+        tx.commit().await?;
+        Ok(close)
+    }
+
+    pub async fn get_fiscal_year_closes(&self) -> Result<Vec<FiscalYearClose>> {
+        let closes = sqlx::query_as(
+            r#"
+            SELECT id, fiscal_year_end, transaction_id, retained_earnings_account_id, created_at
+            FROM fiscal_year_closes
+            ORDER BY fiscal_year_end
+            "#,
+        )
+        .fetch_all(&self.db.pool)
+        .await?;
+        Ok(closes)
+    }
+
+    async fn insert(conn: &mut SqliteConnection, lock_date: NaiveDate) -> Result<ClosedPeriod> {
+        let period = sqlx::query_as(
+            r#"
+            INSERT INTO closed_periods (lock_date, created_at)
+            VALUES (?, ?)
+            RETURNING id, lock_date, created_at
+            "#,
+        )
+        .bind(lock_date)
+        .bind(Utc::now())
+        .fetch_one(conn)
+        .await?;
+        Ok(period)
+    }
+}
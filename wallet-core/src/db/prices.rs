@@ -0,0 +1,102 @@
+use chrono::NaiveDate;
+use std::sync::Arc;
+
+use crate::db::connection::Database;
+use crate::errors::Result;
+use crate::models::price::Price;
+
+const UPSERT: &str = r#"
+    INSERT INTO prices (base_currency, quote_currency, price_date, rate, source)
+    VALUES (?1, ?2, ?3, ?4, ?5)
+    ON CONFLICT(base_currency, quote_currency, price_date, source)
+    DO UPDATE SET rate = excluded.rate
+"#;
+
+pub struct PriceRepository {
+    db: Arc<Database>,
+}
+
+impl PriceRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        PriceRepository { db }
+    }
+
+    /// Insert a price, replacing the rate of an existing price for the same
+    /// pair, date and source, and return the stored row
+    pub async fn upsert(&self, price: &Price) -> Result<Price> {
+        let stored: Price = sqlx::query_as(&format!(
+            "{UPSERT} RETURNING id, base_currency, quote_currency, price_date, rate, source, created_at"
+        ))
+        .bind(&price.base_currency)
+        .bind(&price.quote_currency)
+        .bind(price.price_date)
+        .bind(price.rate.to_string())
+        .bind(&price.source)
+        .fetch_one(&self.db.pool)
+        .await?;
+        Ok(stored)
+    }
+
+    /// Insert prices like `upsert`, all in a single transaction
+    pub async fn upsert_many(&self, prices: &[Price]) -> Result<()> {
+        let mut tx = self.db.pool.begin().await?;
+
+        for price in prices {
+            sqlx::query(UPSERT)
+                .bind(&price.base_currency)
+                .bind(&price.quote_currency)
+                .bind(price.price_date)
+                .bind(price.rate.to_string())
+                .bind(&price.source)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Most recent price of the pair dated on or before the given date
+    pub async fn get_latest_on_or_before(
+        &self,
+        base_currency: &str,
+        quote_currency: &str,
+        date: NaiveDate,
+    ) -> Result<Option<Price>> {
+        let price: Option<Price> = sqlx::query_as(
+            r#"
+            SELECT id, base_currency, quote_currency, price_date, rate, source, created_at
+            FROM prices
+            WHERE base_currency = ?1 AND quote_currency = ?2 AND price_date <= ?3
+            ORDER BY price_date DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(base_currency)
+        .bind(quote_currency)
+        .bind(date)
+        .fetch_optional(&self.db.pool)
+        .await?;
+        Ok(price)
+    }
+
+    pub async fn get_history(
+        &self,
+        base_currency: &str,
+        quote_currency: &str,
+    ) -> Result<Vec<Price>> {
+        let prices: Vec<Price> = sqlx::query_as(
+            r#"
+            SELECT id, base_currency, quote_currency, price_date, rate, source, created_at
+            FROM prices
+            WHERE base_currency = ?1 AND quote_currency = ?2
+            ORDER BY price_date, id
+            "#,
+        )
+        .bind(base_currency)
+        .bind(quote_currency)
+        .fetch_all(&self.db.pool)
+        .await?;
+        Ok(prices)
+    }
+}
@@ -0,0 +1,273 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::{Row, SqliteConnection};
+use std::sync::Arc;
+
+use crate::db::audit;
+use crate::db::connection::Database;
+use crate::db::transactions::TransactionRepository;
+use crate::errors::{Result, WalletError};
+use crate::models::reconciliation::{Reconciliation, ReconciliationEntry};
+use crate::{AuditAction, AuditEntityType, Currency, EntryStatus, EntryType, Money};
+
+pub struct ReconciliationRepository {
+    db: Arc<Database>,
+}
+
+impl ReconciliationRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Entries of an account in its currency dated on or before `statement_date`.
+    /// Amounts are signed from the point of view of the account: debits are
+    /// positive for debit-normal accounts, credits for the others.
+    pub async fn get_entries(
+        &self,
+        account_id: i64,
+        currency: &Currency,
+        debit_normal: bool,
+        statement_date: NaiveDate,
+    ) -> Result<Vec<ReconciliationEntry>> {
+        let mut conn = self.db.pool.acquire().await?;
+        Self::fetch_entries(
+            &mut conn,
+            account_id,
+            currency,
+            debit_normal,
+            statement_date,
+        )
+        .await
+    }
+
+    async fn fetch_entries(
+        conn: &mut SqliteConnection,
+        account_id: i64,
+        currency: &Currency,
+        debit_normal: bool,
+        statement_date: NaiveDate,
+    ) -> Result<Vec<ReconciliationEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                te.id AS entry_id,
+                te.transaction_id,
+                t.transaction_date,
+                COALESCE(te.description, t.description) AS description,
+                te.amount_minor,
+                te.entry_type,
+                te.status
+            FROM transaction_entries te
+            JOIN transactions t ON t.id = te.transaction_id
+            WHERE te.account_id = ? AND te.currency = ? AND t.transaction_date <= ?
+            ORDER BY t.transaction_date, te.id
+            "#,
+        )
+        .bind(account_id)
+        .bind(currency.code())
+        .bind(statement_date)
+        .fetch_all(conn)
+        .await?;
+
+        let entries = rows
+            .into_iter()
+            .map(|row| {
+                let entry_type_str: String = row.get("entry_type");
+                let entry_type = if entry_type_str == "debit" {
+                    EntryType::Debit
+                } else {
+                    EntryType::Credit
+                };
+                let amount_minor: i64 = row.get("amount_minor");
+                let increases = (entry_type == EntryType::Debit) == debit_normal;
+                ReconciliationEntry {
+                    entry_id: row.get("entry_id"),
+                    transaction_id: row.get("transaction_id"),
+                    transaction_date: row.get("transaction_date"),
+                    description: row.get("description"),
+                    entry_type,
+                    amount: Money::from_minor_units(
+                        if increases {
+                            amount_minor
+                        } else {
+                            -amount_minor
+                        },
+                        currency.clone(),
+                    ),
+                    status: row.get("status"),
+                }
+            })
+            .collect();
+        Ok(entries)
+    }
+
+    /// Mark an entry as cleared or back to pending. Reconciled entries are
+    /// locked. The change is audited but not undoable: statuses follow the
+    /// bank statement, and undoing ledger edits leaves them alone.
+    pub async fn set_entry_status(&self, entry_id: i64, status: EntryStatus) -> Result<()> {
+        let mut tx = self.db.pool.begin().await?;
+
+        let row =
+            sqlx::query("SELECT transaction_id, status FROM transaction_entries WHERE id = ?")
+                .bind(entry_id)
+                .fetch_one(&mut *tx)
+                .await?;
+        let transaction_id: i64 = row.get("transaction_id");
+        let current: EntryStatus = row.get("status");
+        if current == EntryStatus::Reconciled {
+            return Err(WalletError::ValidationError(format!(
+                "Entry {entry_id} is reconciled and cannot change"
+            )));
+        }
+
+        let before = TransactionRepository::fetch_transaction(&mut tx, transaction_id).await?;
+        sqlx::query("UPDATE transaction_entries SET status = ? WHERE id = ?")
+            .bind(status)
+            .bind(entry_id)
+            .execute(&mut *tx)
+            .await?;
+        let after = TransactionRepository::fetch_transaction(&mut tx, transaction_id).await?;
+
+        audit::record(
+            &mut tx,
+            AuditEntityType::Transaction,
+            transaction_id,
+            AuditAction::Update,
+            Some(&before),
+            Some(&after),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Record a reconciliation and lock the cleared entries it covers,
+    /// provided their balance matches the statement closing balance. Every
+    /// transaction with a newly locked entry gets an audit record.
+    pub async fn create(
+        &self,
+        account_id: i64,
+        debit_normal: bool,
+        statement_date: NaiveDate,
+        statement_balance: &Money,
+    ) -> Result<Reconciliation> {
+        let mut tx = self.db.pool.begin().await?;
+        let currency = statement_balance.currency();
+
+        let entries =
+            Self::fetch_entries(&mut tx, account_id, currency, debit_normal, statement_date)
+                .await?;
+        let cleared_balance = Money::sum_in(
+            currency.clone(),
+            entries
+                .iter()
+                .filter(|entry| entry.status != EntryStatus::Pending)
+                .map(|entry| &entry.amount),
+        )?;
+        let difference = statement_balance.checked_sub(&cleared_balance)?;
+        if !difference.is_zero() {
+            return Err(WalletError::ValidationError(format!(
+                "Cleared balance differs from the statement balance by {} {}",
+                difference.amount_minor(),
+                currency.code()
+            )));
+        }
+
+        let id = sqlx::query(
+            r#"
+            INSERT INTO reconciliations (
+                account_id, statement_date, statement_balance_minor, currency, created_at
+            )
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(account_id)
+        .bind(statement_date)
+        .bind(statement_balance.amount_minor())
+        .bind(currency.code())
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+
+        let mut locked: Vec<i64> = entries
+            .iter()
+            .filter(|entry| entry.status == EntryStatus::Cleared)
+            .map(|entry| entry.transaction_id)
+            .collect();
+        locked.sort_unstable();
+        locked.dedup();
+        let mut before = Vec::with_capacity(locked.len());
+        for transaction_id in &locked {
+            before.push(TransactionRepository::fetch_transaction(&mut tx, *transaction_id).await?);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE transaction_entries
+            SET status = 'reconciled', reconciliation_id = ?
+            WHERE account_id = ? AND currency = ? AND status = 'cleared'
+              AND transaction_id IN (SELECT id FROM transactions WHERE transaction_date <= ?)
+            "#,
+        )
+        .bind(id)
+        .bind(account_id)
+        .bind(currency.code())
+        .bind(statement_date)
+        .execute(&mut *tx)
+        .await?;
+
+        for (transaction_id, before) in locked.into_iter().zip(before) {
+            let after = TransactionRepository::fetch_transaction(&mut tx, transaction_id).await?;
+            audit::record(
+                &mut tx,
+                AuditEntityType::Transaction,
+                transaction_id,
+                AuditAction::Update,
+                Some(&before),
+                Some(&after),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        self.get_by_id(id).await
+    }
+
+    pub async fn get_by_id(&self, id: i64) -> Result<Reconciliation> {
+        let reconciliation = sqlx::query_as(
+            r#"
+            SELECT r.id, r.account_id, r.statement_date, r.statement_balance_minor, r.currency,
+                   c.minor_unit_scale AS currency_scale, c.symbol AS currency_symbol,
+                   r.created_at
+            FROM reconciliations r
+            JOIN currencies c ON c.code = r.currency
+            WHERE r.id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&self.db.pool)
+        .await?;
+        Ok(reconciliation)
+    }
+
+    /// Reconciliations of an account, latest statement first
+    pub async fn get_by_account(&self, account_id: i64) -> Result<Vec<Reconciliation>> {
+        let reconciliations = sqlx::query_as(
+            r#"
+            SELECT r.id, r.account_id, r.statement_date, r.statement_balance_minor, r.currency,
+                   c.minor_unit_scale AS currency_scale, c.symbol AS currency_symbol,
+                   r.created_at
+            FROM reconciliations r
+            JOIN currencies c ON c.code = r.currency
+            WHERE r.account_id = ?
+            ORDER BY r.statement_date DESC, r.id DESC
+            "#,
+        )
+        .bind(account_id)
+        .fetch_all(&self.db.pool)
+        .await?;
+        Ok(reconciliations)
+    }
+}
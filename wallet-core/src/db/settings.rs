@@ -0,0 +1,39 @@
+use sqlx::Row;
+use std::sync::Arc;
+
+use crate::db::connection::Database;
+use crate::errors::Result;
+
+pub const REPORTING_CURRENCY: &str = "reporting_currency";
+
+pub struct SettingsRepository {
+    db: Arc<Database>,
+}
+
+impl SettingsRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        SettingsRepository { db }
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Option<String>> {
+        let row = sqlx::query("SELECT value FROM settings WHERE key = ?1")
+            .bind(key)
+            .fetch_optional(&self.db.pool)
+            .await?;
+        Ok(row.map(|row| row.get("value")))
+    }
+
+    pub async fn set(&self, key: &str, value: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO settings (key, value) VALUES (?1, ?2)
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(key)
+        .bind(value)
+        .execute(&self.db.pool)
+        .await?;
+        Ok(())
+    }
+}
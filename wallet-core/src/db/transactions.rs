@@ -1,10 +1,80 @@
-use chrono::NaiveDate;
-use sqlx::Row;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection};
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::db::connection::Database;
-use crate::errors::Result;
-use crate::{Currency, EntryType, Money, Transaction, TransactionEntry};
+use crate::db::currencies::registry_currency;
+use crate::db::{audit, periods, undo};
+use crate::errors::{Result, WalletError};
+use crate::services::transaction_service::{
+    AccountMatch, NewTransaction, TransactionEntryInput, TransactionFilters, TransactionSort,
+};
+use crate::{
+    AuditAction, AuditEntityType, EntryPrice, EntryStatus, EntryType, Money, SnippetSegment,
+    Transaction, TransactionEntry, TransactionSearchHit,
+};
+
+/// Minor units in one unit of currency `c`, 10 to the power of its scale,
+/// without relying on SQLite's optional math functions
+const MINOR_UNITS_PER_UNIT: &str =
+    "CAST('1' || substr('000000000000000000', 1, c.minor_unit_scale) AS INTEGER)";
+
+/// Columns of `transactions t` read by `listing_transaction`, with the tags
+/// aggregated into a JSON array
+const LISTING_COLUMNS: &str = r#"
+    t.id as transaction_id,
+    t.description as transaction_description,
+    t.reference,
+    t.transaction_date,
+    t.created_at as transaction_created_at,
+    t.notes,
+    t.external_id,
+    t.reverses_transaction_id,
+    (
+        SELECT json_group_array(tag)
+        FROM (SELECT tag FROM transaction_tags WHERE transaction_id = t.id ORDER BY tag)
+    ) as tags"#;
+
+/// Value of `transaction_entries.entry_type` for an entry type
+fn entry_type_name(entry_type: &EntryType) -> &'static str {
+    match entry_type {
+        EntryType::Debit => "debit",
+        EntryType::Credit => "credit",
+    }
+}
+
+/// Split a snippet marked with `char(2)`/`char(3)` around each match into
+/// plain and highlighted segments
+fn snippet_segments(snippet: &str) -> Vec<SnippetSegment> {
+    let mut segments = Vec::new();
+    let mut push = |text: &str, highlighted| {
+        if !text.is_empty() {
+            segments.push(SnippetSegment {
+                text: text.to_string(),
+                highlighted,
+            });
+        }
+    };
+    let mut parts = snippet.split('\u{2}');
+    push(parts.next().unwrap_or_default(), false);
+    for part in parts {
+        let (matched, rest) = part.split_once('\u{3}').unwrap_or((part, ""));
+        push(matched, true);
+        push(rest, false);
+    }
+    segments
+}
+
+/// Value bound to a placeholder of a dynamically built query
+enum FilterValue {
+    Int(i64),
+    Text(String),
+    Date(NaiveDate),
+}
 
 pub struct TransactionRepository {
     db: Arc<Database>,
@@ -15,25 +85,122 @@ impl TransactionRepository {
         Self { db }
     }
 
+    /// List transactions matching the filters in two set-based queries: one for
+    /// the transactions and their tags, one for all their entries. Each account
+    /// group holds the ids that count as one selected account.
     pub async fn get_transactions(
         &self,
-        account_id: Option<i64>,
-        from_date: Option<NaiveDate>,
-        to_date: Option<NaiveDate>,
-        limit: Option<u32>,
-        offset: Option<u32>,
+        filters: &TransactionFilters,
+        account_groups: &[Vec<i64>],
     ) -> Result<Vec<Transaction>> {
-        // Build dynamic WHERE clause based on filters
+        // Build dynamic WHERE clause based on filters, with the values bound in order
         let mut where_conditions = Vec::new();
+        let mut binds = Vec::new();
+
+        const ENTRY_EXISTS: &str =
+            "EXISTS (SELECT 1 FROM transaction_entries te WHERE te.transaction_id = t.id";
+        match filters.account_match {
+            AccountMatch::Any if !account_groups.is_empty() => {
+                let account_ids: Vec<i64> = account_groups.concat();
+                where_conditions.push(format!(
+                    "{ENTRY_EXISTS} AND te.account_id IN (SELECT value FROM json_each(?)))"
+                ));
+                binds.push(FilterValue::Text(serde_json::to_string(&account_ids)?));
+            }
+            AccountMatch::All => {
+                for group in account_groups {
+                    where_conditions.push(format!(
+                        "{ENTRY_EXISTS} AND te.account_id IN (SELECT value FROM json_each(?)))"
+                    ));
+                    binds.push(FilterValue::Text(serde_json::to_string(group)?));
+                }
+            }
+            AccountMatch::Any => {}
+        }
+
+        // Conditions on a single entry, which must also be one of the selected accounts
+        let mut entry_conditions = Vec::new();
+        if !account_groups.is_empty()
+            && (filters.entry_type.is_some()
+                || filters.currency.is_some()
+                || filters.min_amount.is_some()
+                || filters.max_amount.is_some())
+        {
+            entry_conditions.push("te.account_id IN (SELECT value FROM json_each(?))".to_string());
+            binds.push(FilterValue::Text(serde_json::to_string(
+                &account_groups.concat(),
+            )?));
+        }
+        if let Some(entry_type) = &filters.entry_type {
+            entry_conditions.push("te.entry_type = ?".to_string());
+            binds.push(FilterValue::Text(entry_type_name(entry_type).to_string()));
+        }
+        if let Some(currency) = &filters.currency {
+            entry_conditions.push("te.currency = ?".to_string());
+            binds.push(FilterValue::Text(currency.to_uppercase()));
+        }
+        // Amounts are compared in minor units of the entry's own currency
+        if let Some(min_amount) = filters.min_amount {
+            entry_conditions.push(format!(
+                "te.amount_minor >= ROUND(? * (SELECT {MINOR_UNITS_PER_UNIT} FROM currencies c WHERE c.code = te.currency))"
+            ));
+            binds.push(FilterValue::Text(min_amount.to_string()));
+        }
+        if let Some(max_amount) = filters.max_amount {
+            entry_conditions.push(format!(
+                "te.amount_minor <= ROUND(? * (SELECT {MINOR_UNITS_PER_UNIT} FROM currencies c WHERE c.code = te.currency))"
+            ));
+            binds.push(FilterValue::Text(max_amount.to_string()));
+        }
+        if !entry_conditions.is_empty() {
+            where_conditions.push(format!(
+                "{ENTRY_EXISTS} AND {})",
+                entry_conditions.join(" AND ")
+            ));
+        }
 
-        if account_id.is_some() {
-            where_conditions.push("te.account_id = ?".to_string());
+        for tag in filters.tag.iter().chain(&filters.tags) {
+            where_conditions.push(
+                "EXISTS (SELECT 1 FROM transaction_tags tt WHERE tt.transaction_id = t.id AND tt.tag = ?)"
+                    .to_string(),
+            );
+            binds.push(FilterValue::Text(tag.trim().to_string()));
+        }
+        if let Some(text) = filters.text.as_deref().map(str::trim)
+            && !text.is_empty()
+        {
+            where_conditions.push(
+                "(t.description LIKE ? ESCAPE '\\' OR t.notes LIKE ? ESCAPE '\\' OR t.reference LIKE ? ESCAPE '\\')"
+                    .to_string(),
+            );
+            let escaped = text
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            let pattern = format!("%{escaped}%");
+            for _ in 0..3 {
+                binds.push(FilterValue::Text(pattern.clone()));
+            }
         }
-        if from_date.is_some() {
+        if let Some(fd) = filters.from_date {
             where_conditions.push("t.transaction_date >= ?".to_string());
+            binds.push(FilterValue::Date(fd));
         }
-        if to_date.is_some() {
+        if let Some(td) = filters.to_date {
             where_conditions.push("t.transaction_date <= ?".to_string());
+            binds.push(FilterValue::Date(td));
+        }
+        if let Some(cursor) = &filters.cursor {
+            // Keyset pagination: everything listed after the cursor. The row value
+            // comparison lets SQLite seek in the date index, which also orders by id.
+            let direction = if filters.sort == TransactionSort::DateAsc {
+                ">"
+            } else {
+                "<"
+            };
+            where_conditions.push(format!("(t.transaction_date, t.id) {direction} (?, ?)"));
+            binds.push(FilterValue::Date(cursor.transaction_date));
+            binds.push(FilterValue::Int(cursor.id));
         }
 
         let where_clause = if where_conditions.is_empty() {
@@ -42,77 +209,156 @@ impl TransactionRepository {
             format!("WHERE {}", where_conditions.join(" AND "))
         };
 
-        let limit_clause = limit.map(|l| format!("LIMIT {l}")).unwrap_or_default();
-        let offset_clause = offset.map(|o| format!("OFFSET {o}")).unwrap_or_default();
+        let order_clause = match filters.sort {
+            TransactionSort::DateDesc => "t.transaction_date DESC, t.id DESC",
+            TransactionSort::DateAsc => "t.transaction_date ASC, t.id ASC",
+            TransactionSort::AmountDesc => "debit_total DESC, t.id DESC",
+            TransactionSort::AmountAsc => "debit_total ASC, t.id DESC",
+            TransactionSort::Description => "t.description COLLATE NOCASE ASC, t.id DESC",
+        };
+        // Only computed when sorting by it, as it touches every entry
+        let debit_total = if matches!(
+            filters.sort,
+            TransactionSort::AmountDesc | TransactionSort::AmountAsc
+        ) {
+            format!(
+                r#",
+                (
+                    SELECT SUM(te.amount_minor * 1.0 / {MINOR_UNITS_PER_UNIT})
+                    FROM transaction_entries te
+                    LEFT JOIN currencies c ON c.code = te.currency
+                    WHERE te.transaction_id = t.id AND te.entry_type = 'debit'
+                ) as debit_total"#
+            )
+        } else {
+            String::new()
+        };
+
+        let limit_clause = filters
+            .limit
+            .map(|l| format!("LIMIT {l}"))
+            .unwrap_or_default();
+        let offset_clause = match (filters.limit, filters.offset) {
+            (Some(_), Some(o)) => format!("OFFSET {o}"),
+            (None, Some(o)) => format!("LIMIT -1 OFFSET {o}"),
+            _ => String::new(),
+        };
 
         let query = format!(
             r#"
-            SELECT DISTINCT
-                t.id as transaction_id,
-                t.description as transaction_description,
-                t.reference,
-                t.transaction_date,
-                t.created_at as transaction_created_at,
-                t.tags,
-                t.notes
+            SELECT {LISTING_COLUMNS}{debit_total}
             FROM transactions t
-            JOIN transaction_entries te ON t.id = te.transaction_id
             {where_clause}
-            ORDER BY t.transaction_date DESC, t.id DESC
+            ORDER BY {order_clause}
             {limit_clause} {offset_clause}
             "#
         );
 
         // Execute query with parameters
         let mut query_builder = sqlx::query(&query);
-
-        if let Some(aid) = account_id {
-            query_builder = query_builder.bind(aid);
-        }
-        if let Some(fd) = from_date {
-            query_builder = query_builder.bind(fd);
-        }
-        if let Some(td) = to_date {
-            query_builder = query_builder.bind(td);
+        for value in binds {
+            query_builder = match value {
+                FilterValue::Int(value) => query_builder.bind(value),
+                FilterValue::Text(value) => query_builder.bind(value),
+                FilterValue::Date(value) => query_builder.bind(value),
+            };
         }
 
-        let rows = query_builder.fetch_all(&self.db.pool).await?;
+        let mut conn = self.db.pool.acquire().await?;
+        let rows = query_builder.fetch_all(&mut *conn).await?;
 
-        let mut transactions = Vec::new();
-        for row in rows {
-            let transaction_id: i64 = row.get("transaction_id");
+        let transaction_ids: Vec<i64> = rows.iter().map(|row| row.get("transaction_id")).collect();
+        let mut entries = Self::get_entries_for_transactions(&mut conn, &transaction_ids).await?;
 
-            // Get entries for this transaction
-            let entries = self.get_entries_for_transaction(transaction_id).await?;
-
-            transactions.push(Transaction {
-                id: Some(transaction_id),
-                description: row.get("transaction_description"),
-                reference: row.get("reference"),
-                transaction_date: row.get("transaction_date"),
-                created_at: row.get("transaction_created_at"),
-                tags: row.get("tags"),
-                notes: row.get("notes"),
-                entries,
-            });
-        }
+        rows.iter()
+            .map(|row| Self::listing_transaction(row, &mut entries))
+            .collect()
+    }
+
+    /// Full-text search over the transaction text, best matches first.
+    /// `fts_query` is in FTS5 query syntax.
+    pub async fn search(&self, fts_query: &str, limit: u32) -> Result<Vec<TransactionSearchHit>> {
+        // Matches in the description weigh most, then reference, tags, notes and
+        // entry descriptions. Snippet matches are marked with control characters,
+        // which cannot occur in the indexed text, and split into segments below.
+        let query = format!(
+            r#"
+            SELECT {LISTING_COLUMNS},
+                bm25(transaction_search, 10.0, 2.0, 5.0, 3.0, 1.0) as search_rank,
+                snippet(transaction_search, -1, char(2), char(3), '…', 12) as snippet
+            FROM transaction_search
+            JOIN transactions t ON t.id = transaction_search.rowid
+            WHERE transaction_search MATCH ?
+            ORDER BY search_rank, t.transaction_date DESC, t.id DESC
+            LIMIT ?
+            "#
+        );
+
+        let mut conn = self.db.pool.acquire().await?;
+        let rows = sqlx::query(&query)
+            .bind(fts_query)
+            .bind(limit)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let transaction_ids: Vec<i64> = rows.iter().map(|row| row.get("transaction_id")).collect();
+        let mut entries = Self::get_entries_for_transactions(&mut conn, &transaction_ids).await?;
 
-        Ok(transactions)
+        rows.iter()
+            .map(|row| {
+                let snippet: String = row.get("snippet");
+                Ok(TransactionSearchHit {
+                    transaction: Self::listing_transaction(row, &mut entries)?,
+                    rank: row.get("search_rank"),
+                    snippet: snippet_segments(&snippet),
+                })
+            })
+            .collect()
+    }
+
+    /// Build a transaction from a row selecting `LISTING_COLUMNS`, taking its
+    /// entries out of the batch loaded for the listing
+    fn listing_transaction(
+        row: &SqliteRow,
+        entries: &mut HashMap<i64, Vec<TransactionEntry>>,
+    ) -> Result<Transaction> {
+        let transaction_id: i64 = row.get("transaction_id");
+        let tags: String = row.get("tags");
+        Ok(Transaction {
+            id: Some(transaction_id),
+            description: row.get("transaction_description"),
+            reference: row.get("reference"),
+            transaction_date: row.get("transaction_date"),
+            created_at: row.get("transaction_created_at"),
+            tags: serde_json::from_str(&tags)?,
+            notes: row.get("notes"),
+            external_id: row.get("external_id"),
+            reverses_transaction_id: row.get("reverses_transaction_id"),
+            entries: entries.remove(&transaction_id).unwrap_or_default(),
+        })
     }
 
     pub async fn get_transaction(&self, id: i64) -> Result<Transaction> {
+        let mut conn = self.db.pool.acquire().await?;
+        Self::fetch_transaction(&mut conn, id).await
+    }
+
+    /// Load a transaction as part of a larger SQL transaction
+    pub async fn fetch_transaction(conn: &mut SqliteConnection, id: i64) -> Result<Transaction> {
         let row = sqlx::query(
             r#"
-            SELECT id, description, reference, transaction_date, created_at, tags, notes
+            SELECT id, description, reference, transaction_date, created_at, notes,
+                   external_id, reverses_transaction_id
             FROM transactions
             WHERE id = ?
             "#,
         )
         .bind(id)
-        .fetch_one(&self.db.pool)
+        .fetch_one(&mut *conn)
         .await?;
 
-        let entries = self.get_entries_for_transaction(id).await?;
+        let entries = Self::get_entries_for_transaction(conn, id).await?;
+        let tags = Self::get_tags_for_transaction(conn, id).await?;
 
         Ok(Transaction {
             id: Some(row.get("id")),
@@ -120,97 +366,621 @@ impl TransactionRepository {
             reference: row.get("reference"),
             transaction_date: row.get("transaction_date"),
             created_at: row.get("created_at"),
-            tags: row.get("tags"),
+            tags,
             notes: row.get("notes"),
+            external_id: row.get("external_id"),
+            reverses_transaction_id: row.get("reverses_transaction_id"),
             entries,
         })
     }
 
     pub async fn create_transaction(
         &self,
-        description: String,
-        transaction_date: NaiveDate,
-        entries: Vec<crate::services::transaction_service::TransactionEntryInput>,
+        new_transaction: NewTransaction,
+        reverses_transaction_id: Option<i64>,
     ) -> Result<Transaction> {
-        use chrono::Utc;
-
         // Start transaction
         let mut tx = self.db.pool.begin().await?;
 
+        let (created, audit_id) =
+            Self::insert_transaction(&mut tx, new_transaction, reverses_transaction_id).await?;
+        undo::push(&mut tx, audit_id).await?;
+
+        // Commit transaction
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    /// Insert a transaction with its entries and tags as part of a larger SQL
+    /// transaction. Returns it with the id of its audit record, which the caller
+    /// pushes on the undo stack if the creation can be undone.
+    pub async fn insert_transaction(
+        conn: &mut SqliteConnection,
+        new_transaction: NewTransaction,
+        reverses_transaction_id: Option<i64>,
+    ) -> Result<(Transaction, i64)> {
+        let NewTransaction {
+            description,
+            transaction_date,
+            reference,
+            notes,
+            tags,
+            external_id,
+            entries,
+        } = new_transaction;
+
+        periods::ensure_open(conn, transaction_date).await?;
+        Self::ensure_account_currencies(conn, &entries).await?;
+
         // Insert transaction record
         let transaction_result = sqlx::query(
             r#"
-            INSERT INTO transactions (description, transaction_date, created_at)
-            VALUES (?, ?, ?)
+            INSERT INTO transactions (
+                description, reference, transaction_date, created_at, notes,
+                external_id, reverses_transaction_id
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&description)
+        .bind(&reference)
         .bind(transaction_date)
         .bind(Utc::now())
-        .execute(&mut *tx)
+        .bind(&notes)
+        .bind(&external_id)
+        .bind(reverses_transaction_id)
+        .execute(&mut *conn)
         .await?;
 
         let transaction_id = transaction_result.last_insert_rowid();
 
-        // Insert transaction entries
-        let mut created_entries = Vec::new();
-        for entry_input in entries {
-            let entry_type_str = match entry_input.entry_type {
-                crate::EntryType::Debit => "debit",
-                crate::EntryType::Credit => "credit",
-            };
+        // Insert transaction entries and tags
+        Self::insert_entries(conn, transaction_id, entries).await?;
+        Self::insert_tags(conn, transaction_id, &tags).await?;
+
+        let created = Self::fetch_transaction(conn, transaction_id).await?;
+        let audit_id = audit::record(
+            conn,
+            AuditEntityType::Transaction,
+            transaction_id,
+            AuditAction::Create,
+            None,
+            Some(&created),
+        )
+        .await?;
+
+        Ok((created, audit_id))
+    }
 
-            let entry_result = sqlx::query(
+    /// Id of the transaction imported with the given external id, if any
+    pub async fn get_transaction_id_by_external_id(
+        &self,
+        external_id: &str,
+    ) -> Result<Option<i64>> {
+        let row = sqlx::query("SELECT id FROM transactions WHERE external_id = ?")
+            .bind(external_id)
+            .fetch_optional(&self.db.pool)
+            .await?;
+        Ok(row.map(|row| row.get("id")))
+    }
+
+    /// Ids of the transactions using any of the external ids, by external id
+    pub async fn get_transaction_ids_by_external_ids(
+        &self,
+        external_ids: &[String],
+    ) -> Result<HashMap<String, i64>> {
+        let rows = sqlx::query(
+            "SELECT external_id, id FROM transactions WHERE external_id IN (SELECT value FROM json_each(?))",
+        )
+        .bind(serde_json::to_string(external_ids)?)
+        .fetch_all(&self.db.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get("external_id"), row.get("id")))
+            .collect())
+    }
+
+    /// All tags in use, alphabetically
+    pub async fn get_tags(&self) -> Result<Vec<String>> {
+        let rows = sqlx::query("SELECT DISTINCT tag FROM transaction_tags ORDER BY tag")
+            .fetch_all(&self.db.pool)
+            .await?;
+        Ok(rows.iter().map(|row| row.get("tag")).collect())
+    }
+
+    /// Replace the description, date, reference, notes, tags and entries of a
+    /// transaction atomically. The external id is kept.
+    pub async fn update_transaction(
+        &self,
+        id: i64,
+        transaction: NewTransaction,
+    ) -> Result<Transaction> {
+        let NewTransaction {
+            description,
+            transaction_date,
+            reference,
+            notes,
+            tags,
+            external_id: _,
+            entries,
+        } = transaction;
+
+        let mut tx = self.db.pool.begin().await?;
+        let before = Self::fetch_transaction(&mut tx, id).await?;
+        Self::ensure_not_reconciled(&before)?;
+        periods::ensure_open(&mut tx, before.transaction_date).await?;
+        periods::ensure_open(&mut tx, transaction_date).await?;
+        Self::ensure_account_currencies(&mut tx, &entries).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE transactions
+            SET description = ?, transaction_date = ?, reference = ?, notes = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&description)
+        .bind(transaction_date)
+        .bind(&reference)
+        .bind(&notes)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        // Entries are updated in place so they keep their id and status
+        let mut current_entries = before.entries.iter();
+        let mut added_entries = Vec::new();
+        for entry in entries {
+            match current_entries.next() {
+                Some(current) => Self::update_entry(&mut tx, current, entry).await?,
+                None => added_entries.push(entry),
+            }
+        }
+        for removed in current_entries {
+            sqlx::query("DELETE FROM transaction_entries WHERE id = ?")
+                .bind(removed.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        Self::insert_entries(&mut tx, id, added_entries).await?;
+        sqlx::query("DELETE FROM transaction_tags WHERE transaction_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        Self::insert_tags(&mut tx, id, &tags).await?;
+
+        let after = Self::fetch_transaction(&mut tx, id).await?;
+        let audit_id = audit::record(
+            &mut tx,
+            AuditEntityType::Transaction,
+            id,
+            AuditAction::Update,
+            Some(&before),
+            Some(&after),
+        )
+        .await?;
+        undo::push(&mut tx, audit_id).await?;
+
+        tx.commit().await?;
+        Ok(after)
+    }
+
+    pub async fn delete_transaction(&self, id: i64) -> Result<()> {
+        let mut tx = self.db.pool.begin().await?;
+        let before = Self::fetch_transaction(&mut tx, id).await?;
+        Self::ensure_not_reconciled(&before)?;
+        Self::ensure_not_reversed(&mut tx, id).await?;
+        periods::ensure_open(&mut tx, before.transaction_date).await?;
+
+        sqlx::query("DELETE FROM transaction_entries WHERE transaction_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM transactions WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        let audit_id = audit::record(
+            &mut tx,
+            AuditEntityType::Transaction,
+            id,
+            AuditAction::Delete,
+            Some(&before),
+            None,
+        )
+        .await?;
+        undo::push(&mut tx, audit_id).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Id of the transaction reversing the given one, if any
+    pub async fn get_reversal_id(&self, id: i64) -> Result<Option<i64>> {
+        let row = sqlx::query("SELECT id FROM transactions WHERE reverses_transaction_id = ?")
+            .bind(id)
+            .fetch_optional(&self.db.pool)
+            .await?;
+        Ok(row.map(|row| row.get("id")))
+    }
+
+    /// Bring a transaction to a state recorded in the audit log: recreate it with
+    /// its original id, replace it, or remove it when `target` is `None`
+    pub async fn restore(
+        conn: &mut SqliteConnection,
+        id: i64,
+        target: Option<&Transaction>,
+    ) -> Result<()> {
+        let current = match Self::fetch_transaction(conn, id).await {
+            Ok(transaction) => Some(transaction),
+            Err(WalletError::DatabaseError(sqlx::Error::RowNotFound)) => None,
+            Err(e) => return Err(e),
+        };
+
+        let action = match (&current, target) {
+            (None, None) => return Ok(()),
+            (Some(_), None) => AuditAction::Delete,
+            (None, Some(_)) => AuditAction::Create,
+            (Some(_), Some(_)) => AuditAction::Update,
+        };
+
+        if let Some(transaction) = target {
+            periods::ensure_open(conn, transaction.transaction_date).await?;
+        }
+        if let Some(transaction) = &current {
+            Self::ensure_not_reconciled(transaction)?;
+            periods::ensure_open(conn, transaction.transaction_date).await?;
+            if target.is_none() {
+                Self::ensure_not_reversed(conn, id).await?;
+            }
+            sqlx::query("DELETE FROM transaction_tags WHERE transaction_id = ?")
+                .bind(id)
+                .execute(&mut *conn)
+                .await?;
+            sqlx::query("DELETE FROM transaction_entries WHERE transaction_id = ?")
+                .bind(id)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        match (&current, target) {
+            (Some(_), None) => {
+                sqlx::query("DELETE FROM transactions WHERE id = ?")
+                    .bind(id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+            (None, Some(transaction)) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO transactions (
+                        id, description, reference, transaction_date, created_at, notes,
+                        external_id, reverses_transaction_id
+                    )
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(id)
+                .bind(&transaction.description)
+                .bind(&transaction.reference)
+                .bind(transaction.transaction_date)
+                .bind(transaction.created_at)
+                .bind(&transaction.notes)
+                .bind(&transaction.external_id)
+                .bind(transaction.reverses_transaction_id)
+                .execute(&mut *conn)
+                .await?;
+            }
+            (Some(_), Some(transaction)) => {
+                sqlx::query(
+                    r#"
+                    UPDATE transactions
+                    SET description = ?, reference = ?, transaction_date = ?, notes = ?,
+                        external_id = ?, reverses_transaction_id = ?
+                    WHERE id = ?
+                    "#,
+                )
+                .bind(&transaction.description)
+                .bind(&transaction.reference)
+                .bind(transaction.transaction_date)
+                .bind(&transaction.notes)
+                .bind(&transaction.external_id)
+                .bind(transaction.reverses_transaction_id)
+                .bind(id)
+                .execute(&mut *conn)
+                .await?;
+            }
+            (None, None) => {}
+        }
+
+        let after = match target {
+            Some(transaction) => {
+                // Entry statuses are not undoable: an entry that is still there
+                // keeps its status unless the restore changes what it books
+                let entries: Vec<TransactionEntry> = transaction
+                    .entries
+                    .iter()
+                    .cloned()
+                    .map(|mut entry| {
+                        let existing = current
+                            .iter()
+                            .flat_map(|current| &current.entries)
+                            .find(|existing| existing.id == entry.id);
+                        if let Some(existing) = existing {
+                            entry.status = if existing.account_id == entry.account_id
+                                && existing.amount == entry.amount
+                                && existing.entry_type == entry.entry_type
+                            {
+                                existing.status
+                            } else {
+                                EntryStatus::Pending
+                            };
+                        }
+                        entry
+                    })
+                    .collect();
+                Self::restore_entries(conn, id, &entries).await?;
+                Self::insert_tags(conn, id, &transaction.tags).await?;
+                Some(Self::fetch_transaction(conn, id).await?)
+            }
+            None => None,
+        };
+
+        audit::record(
+            conn,
+            AuditEntityType::Transaction,
+            id,
+            action,
+            current.as_ref(),
+            after.as_ref(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// A reversed transaction stays as long as its reversal, which would
+    /// otherwise no longer say what it reverses
+    async fn ensure_not_reversed(conn: &mut SqliteConnection, id: i64) -> Result<()> {
+        let reversal_id: Option<i64> =
+            sqlx::query_scalar("SELECT id FROM transactions WHERE reverses_transaction_id = ?")
+                .bind(id)
+                .fetch_optional(conn)
+                .await?;
+        if let Some(reversal_id) = reversal_id {
+            return Err(WalletError::ValidationError(format!(
+                "Transaction {id} is reversed by transaction {reversal_id} and cannot be deleted"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reconciled entries are locked: their transaction can no longer change
+    fn ensure_not_reconciled(transaction: &Transaction) -> Result<()> {
+        if transaction
+            .entries
+            .iter()
+            .any(|entry| entry.status == EntryStatus::Reconciled)
+        {
+            return Err(WalletError::ValidationError(format!(
+                "Transaction {} has reconciled entries and cannot be changed",
+                transaction.id.unwrap_or_default()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Entries are recorded in the currency of their account, so that account
+    /// balances stay in one currency. An amount in another currency is entered
+    /// as the account currency amount with a price.
+    async fn ensure_account_currencies(
+        conn: &mut SqliteConnection,
+        entries: &[TransactionEntryInput],
+    ) -> Result<()> {
+        let account_ids: Vec<i64> = entries.iter().map(|entry| entry.account_id).collect();
+        let rows = sqlx::query(
+            "SELECT id, currency FROM accounts WHERE id IN (SELECT value FROM json_each(?))",
+        )
+        .bind(serde_json::to_string(&account_ids)?)
+        .fetch_all(&mut *conn)
+        .await?;
+        let currencies: HashMap<i64, String> = rows
+            .into_iter()
+            .map(|row| (row.get("id"), row.get("currency")))
+            .collect();
+
+        for entry in entries {
+            let currency = currencies.get(&entry.account_id).ok_or_else(|| {
+                WalletError::ValidationError(format!("Account {} not found", entry.account_id))
+            })?;
+            if currency != entry.amount.currency().code() {
+                return Err(WalletError::ValidationError(format!(
+                    "Entry in {} cannot be posted to account {} in {currency}; enter it in \
+                     {currency} with a price",
+                    entry.amount.currency().code(),
+                    entry.account_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    async fn insert_tags(
+        conn: &mut SqliteConnection,
+        transaction_id: i64,
+        tags: &[String],
+    ) -> Result<()> {
+        for tag in tags {
+            sqlx::query("INSERT INTO transaction_tags (transaction_id, tag) VALUES (?, ?)")
+                .bind(transaction_id)
+                .bind(tag)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn insert_entries(
+        conn: &mut SqliteConnection,
+        transaction_id: i64,
+        entries: Vec<TransactionEntryInput>,
+    ) -> Result<()> {
+        for entry_input in entries {
+            sqlx::query(
                 r#"
                 INSERT INTO transaction_entries (
                     transaction_id, account_id, amount_minor, currency, 
-                    entry_type, description, created_at
+                    entry_type, description, price_rate, price_currency,
+                    price_amount_minor, created_at
                 )
-                VALUES (?, ?, ?, ?, ?, ?, ?)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 "#,
             )
             .bind(transaction_id)
             .bind(entry_input.account_id)
             .bind(entry_input.amount.amount_minor())
             .bind(entry_input.amount.currency().code())
-            .bind(entry_type_str)
+            .bind(entry_type_name(&entry_input.entry_type))
             .bind(&entry_input.description)
+            .bind(entry_input.price.as_ref().map(|p| p.rate.to_string()))
+            .bind(
+                entry_input
+                    .price
+                    .as_ref()
+                    .map(|p| p.amount.currency().code().to_string()),
+            )
+            .bind(entry_input.price.as_ref().map(|p| p.amount.amount_minor()))
             .bind(Utc::now())
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
+        }
+
+        Ok(())
+    }
 
-            let entry_id = entry_result.last_insert_rowid();
+    /// Overwrite an entry with its edited version. A cleared entry stays
+    /// cleared unless its account, amount or side changed, as it then no
+    /// longer matches the statement line it was ticked off against.
+    async fn update_entry(
+        conn: &mut SqliteConnection,
+        current: &TransactionEntry,
+        entry: TransactionEntryInput,
+    ) -> Result<()> {
+        let status = if current.account_id == entry.account_id
+            && current.amount == entry.amount
+            && current.entry_type == entry.entry_type
+        {
+            current.status
+        } else {
+            EntryStatus::Pending
+        };
 
-            created_entries.push(crate::TransactionEntry {
-                id: Some(entry_id),
-                transaction_id,
-                account_id: entry_input.account_id,
-                amount: entry_input.amount,
-                entry_type: entry_input.entry_type,
-                description: entry_input.description,
-                created_at: Utc::now(),
-            });
+        sqlx::query(
+            r#"
+            UPDATE transaction_entries
+            SET account_id = ?, amount_minor = ?, currency = ?, entry_type = ?,
+                description = ?, price_rate = ?, price_currency = ?,
+                price_amount_minor = ?, status = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(entry.account_id)
+        .bind(entry.amount.amount_minor())
+        .bind(entry.amount.currency().code())
+        .bind(entry_type_name(&entry.entry_type))
+        .bind(&entry.description)
+        .bind(entry.price.as_ref().map(|p| p.rate.to_string()))
+        .bind(
+            entry
+                .price
+                .as_ref()
+                .map(|p| p.amount.currency().code().to_string()),
+        )
+        .bind(entry.price.as_ref().map(|p| p.amount.amount_minor()))
+        .bind(status)
+        .bind(current.id)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Insert entries as recorded in the audit log, with their original ids,
+    /// status and reconciliation
+    async fn restore_entries(
+        conn: &mut SqliteConnection,
+        transaction_id: i64,
+        entries: &[TransactionEntry],
+    ) -> Result<()> {
+        for entry in entries {
+            sqlx::query(
+                r#"
+                INSERT INTO transaction_entries (
+                    id, transaction_id, account_id, amount_minor, currency,
+                    entry_type, description, price_rate, price_currency,
+                    price_amount_minor, status, reconciliation_id, created_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(entry.id)
+            .bind(transaction_id)
+            .bind(entry.account_id)
+            .bind(entry.amount.amount_minor())
+            .bind(entry.amount.currency().code())
+            .bind(entry_type_name(&entry.entry_type))
+            .bind(&entry.description)
+            .bind(entry.price.as_ref().map(|p| p.rate.to_string()))
+            .bind(
+                entry
+                    .price
+                    .as_ref()
+                    .map(|p| p.amount.currency().code().to_string()),
+            )
+            .bind(entry.price.as_ref().map(|p| p.amount.amount_minor()))
+            .bind(entry.status)
+            .bind(entry.reconciliation_id)
+            .bind(entry.created_at)
+            .execute(&mut *conn)
+            .await?;
         }
 
-        // Commit transaction
-        tx.commit().await?;
+        Ok(())
+    }
 
-        Ok(crate::Transaction {
-            id: Some(transaction_id),
-            description,
-            reference: None,
-            transaction_date,
-            created_at: Utc::now(),
-            tags: None,
-            notes: None,
-            entries: created_entries,
-        })
+    async fn get_tags_for_transaction(
+        conn: &mut SqliteConnection,
+        transaction_id: i64,
+    ) -> Result<Vec<String>> {
+        let rows =
+            sqlx::query("SELECT tag FROM transaction_tags WHERE transaction_id = ? ORDER BY tag")
+                .bind(transaction_id)
+                .fetch_all(conn)
+                .await?;
+        Ok(rows.iter().map(|row| row.get("tag")).collect())
     }
 
-    async fn get_entries_for_transaction(
-        &self,
+    /// Entries of one transaction. Listings load their entries with
+    /// `get_entries_for_transactions` instead of calling this once per row.
+    pub(crate) async fn get_entries_for_transaction(
+        conn: &mut SqliteConnection,
         transaction_id: i64,
     ) -> Result<Vec<TransactionEntry>> {
+        let mut entries = Self::get_entries_for_transactions(conn, &[transaction_id]).await?;
+        Ok(entries.remove(&transaction_id).unwrap_or_default())
+    }
+
+    /// Entries of several transactions in one query, grouped by transaction
+    async fn get_entries_for_transactions(
+        conn: &mut SqliteConnection,
+        transaction_ids: &[i64],
+    ) -> Result<HashMap<i64, Vec<TransactionEntry>>> {
+        if transaction_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
         let rows = sqlx::query(
             r#"
             SELECT 
@@ -219,24 +989,38 @@ impl TransactionRepository {
                 te.account_id,
                 te.amount_minor,
                 te.currency,
+                c.minor_unit_scale AS currency_scale,
+                c.symbol AS currency_symbol,
                 te.entry_type,
                 te.description,
+                te.price_rate,
+                te.price_currency,
+                pc.minor_unit_scale AS price_currency_scale,
+                pc.symbol AS price_currency_symbol,
+                te.price_amount_minor,
+                te.status,
+                te.reconciliation_id,
                 te.created_at
             FROM transaction_entries te
-            WHERE te.transaction_id = ?
-            ORDER BY te.id
+            LEFT JOIN currencies c ON c.code = te.currency
+            LEFT JOIN currencies pc ON pc.code = te.price_currency
+            WHERE te.transaction_id IN (SELECT value FROM json_each(?))
+            ORDER BY te.transaction_id, te.id
             "#,
         )
-        .bind(transaction_id)
-        .fetch_all(&self.db.pool)
+        .bind(serde_json::to_string(transaction_ids)?)
+        .fetch_all(conn)
         .await?;
 
-        let mut entries = Vec::new();
+        let mut entries: HashMap<i64, Vec<TransactionEntry>> = HashMap::new();
         for row in rows {
             // Reconstruct Money from database fields
             let amount_minor: i64 = row.get("amount_minor");
-            let currency_code: String = row.get("currency");
-            let currency = Currency::from_code(&currency_code)?;
+            let currency = registry_currency(
+                row.get("currency"),
+                row.get("currency_scale"),
+                row.get("currency_symbol"),
+            )?;
             let money = Money::from_minor_units(amount_minor, currency);
 
             // Parse entry type
@@ -245,21 +1029,50 @@ impl TransactionRepository {
                 "debit" => EntryType::Debit,
                 "credit" => EntryType::Credit,
                 _ => {
-                    return Err(crate::errors::WalletError::ValidationError(format!(
+                    return Err(WalletError::ValidationError(format!(
                         "Invalid entry type: {entry_type_str}"
                     )));
                 }
             };
 
-            entries.push(TransactionEntry {
-                id: Some(row.get("id")),
-                transaction_id: row.get("transaction_id"),
-                account_id: row.get("account_id"),
-                amount: money,
-                entry_type,
-                description: row.get("description"),
-                created_at: row.get("created_at"),
-            });
+            // Reconstruct the optional conversion into a second currency
+            let price_rate: Option<String> = row.get("price_rate");
+            let price_currency: Option<String> = row.get("price_currency");
+            let price_amount_minor: Option<i64> = row.get("price_amount_minor");
+            let price = match (price_rate, price_currency, price_amount_minor) {
+                (Some(rate), Some(currency_code), Some(amount_minor)) => {
+                    let rate = Decimal::from_str(&rate).map_err(|e| {
+                        WalletError::ValidationError(format!("Invalid exchange rate {rate}: {e}"))
+                    })?;
+                    let currency = registry_currency(
+                        currency_code,
+                        row.get("price_currency_scale"),
+                        row.get("price_currency_symbol"),
+                    )?;
+                    Some(EntryPrice {
+                        rate,
+                        amount: Money::from_minor_units(amount_minor, currency),
+                    })
+                }
+                _ => None,
+            };
+
+            let transaction_id: i64 = row.get("transaction_id");
+            entries
+                .entry(transaction_id)
+                .or_default()
+                .push(TransactionEntry {
+                    id: Some(row.get("id")),
+                    transaction_id,
+                    account_id: row.get("account_id"),
+                    amount: money,
+                    entry_type,
+                    description: row.get("description"),
+                    price,
+                    status: row.get("status"),
+                    reconciliation_id: row.get("reconciliation_id"),
+                    created_at: row.get("created_at"),
+                });
         }
 
         Ok(entries)
@@ -0,0 +1,159 @@
+use sqlx::{Connection, SqliteConnection};
+use std::sync::Arc;
+
+use crate::db::accounts::AccountRepository;
+use crate::db::connection::Database;
+use crate::db::transactions::TransactionRepository;
+use crate::errors::{Result, WalletError};
+use crate::services::transaction_service::TransactionEntryInput;
+use crate::{Account, AuditEntityType, AuditEntry, Transaction, TransactionService};
+
+/// Make a change recorded in the audit log undoable. A new change discards
+/// the changes that were undone, so they can no longer be redone.
+pub async fn push(conn: &mut SqliteConnection, audit_id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM undo_stack WHERE status = 'undone'")
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("INSERT INTO undo_stack (audit_id, status) VALUES (?, 'applied')")
+        .bind(audit_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+pub struct UndoRepository {
+    db: Arc<Database>,
+}
+
+impl UndoRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Revert the last `count` changes, newest first, in one SQL transaction.
+    /// Returns the audit entries of the reverted changes.
+    pub async fn undo(&self, count: u32) -> Result<Vec<AuditEntry>> {
+        self.replay(count, true).await
+    }
+
+    /// Re-apply the last `count` undone changes, oldest first, in one SQL
+    /// transaction. Returns the audit entries of the re-applied changes.
+    pub async fn redo(&self, count: u32) -> Result<Vec<AuditEntry>> {
+        self.replay(count, false).await
+    }
+
+    /// Undo or redo `count` changes. A change that can no longer be applied,
+    /// such as an edit of a transaction that has been reconciled since, is
+    /// dropped from the stack and the next one is taken instead.
+    async fn replay(&self, count: u32, undo: bool) -> Result<Vec<AuditEntry>> {
+        let (from, to, order, operation) = if undo {
+            ("applied", "undone", "DESC", "undo")
+        } else {
+            ("undone", "applied", "ASC", "redo")
+        };
+        let mut tx = self.db.pool.begin().await?;
+
+        let candidates: Vec<AuditEntry> = sqlx::query_as(&format!(
+            r#"
+            SELECT a.id, a.entity_type, a.entity_id, a.action, a.actor,
+                   a.before_json, a.after_json, a.created_at
+            FROM undo_stack u
+            JOIN audit_log a ON a.id = u.audit_id
+            WHERE u.status = ?
+            ORDER BY u.audit_id {order}
+            "#
+        ))
+        .bind(from)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut entries = Vec::new();
+        for entry in candidates {
+            if entries.len() == count as usize {
+                break;
+            }
+            // A savepoint keeps a change that fails halfway from leaving traces
+            let mut savepoint = tx.begin().await?;
+            match Self::restore(&mut savepoint, &entry, undo).await {
+                Ok(()) => {
+                    savepoint.commit().await?;
+                    Self::set_status(&mut tx, entry.id, to).await?;
+                    entries.push(entry);
+                }
+                Err(WalletError::ValidationError(_)) => {
+                    savepoint.rollback().await?;
+                    sqlx::query("DELETE FROM undo_stack WHERE audit_id = ?")
+                        .bind(entry.id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if entries.len() < count as usize {
+            return Err(WalletError::ValidationError(format!(
+                "Cannot {operation} {count} changes, only {} can still be applied",
+                entries.len()
+            )));
+        }
+
+        tx.commit().await?;
+        Ok(entries)
+    }
+
+    /// Number of changes that can be undone and redone
+    pub async fn get_counts(&self) -> Result<(u32, u32)> {
+        let counts: (u32, u32) = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(SUM(CASE WHEN status = 'applied' THEN 1 ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN status = 'undone' THEN 1 ELSE 0 END), 0)
+            FROM undo_stack
+            "#,
+        )
+        .fetch_one(&self.db.pool)
+        .await?;
+        Ok(counts)
+    }
+
+    /// Bring the entity of an audit entry back to its state before the change
+    /// (undo) or after it (redo)
+    async fn restore(conn: &mut SqliteConnection, entry: &AuditEntry, undo: bool) -> Result<()> {
+        match entry.entity_type {
+            AuditEntityType::Account => {
+                let target: Option<Account> = if undo {
+                    entry.before()?
+                } else {
+                    entry.after()?
+                };
+                AccountRepository::restore(conn, entry.entity_id, target.as_ref()).await
+            }
+            AuditEntityType::Transaction => {
+                let target: Option<Transaction> = if undo {
+                    entry.before()?
+                } else {
+                    entry.after()?
+                };
+                if let Some(transaction) = &target {
+                    let entries: Vec<TransactionEntryInput> = transaction
+                        .entries
+                        .iter()
+                        .cloned()
+                        .map(TransactionEntryInput::from)
+                        .collect();
+                    TransactionService::validate_transaction_balance(&entries)?;
+                }
+                TransactionRepository::restore(conn, entry.entity_id, target.as_ref()).await
+            }
+        }
+    }
+
+    async fn set_status(conn: &mut SqliteConnection, audit_id: i64, status: &str) -> Result<()> {
+        sqlx::query("UPDATE undo_stack SET status = ? WHERE audit_id = ?")
+            .bind(status)
+            .bind(audit_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
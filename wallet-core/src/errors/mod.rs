@@ -9,7 +9,13 @@ pub enum WalletError {
     #[error(transparent)]
     DatabaseError(#[from] sqlx::Error),
     #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
     MigrationError(#[from] sqlx::migrate::MigrateError),
+    #[error(transparent)]
+    MoneyError(#[from] MoneyError),
+    #[error(transparent)]
+    SerializationError(#[from] serde_json::Error),
     #[error("Validation error: {0}")]
     ValidationError(String),
 }
@@ -18,4 +24,20 @@ pub enum WalletError {
 pub enum CurrencyError {
     #[error("Invalid currency code: {0}")]
     InvalidCurrencyCode(String),
+    #[error("Invalid minor unit scale: {0}")]
+    InvalidMinorUnitScale(u8),
+    #[error("Unknown currency: {0}")]
+    UnknownCurrency(String),
+}
+
+#[derive(Error, Debug)]
+pub enum MoneyError {
+    #[error("Currency mismatch: expected {expected}, found {found}")]
+    CurrencyMismatch { expected: String, found: String },
+    #[error("Amount overflow")]
+    Overflow,
+    #[error("Invalid allocation: {0}")]
+    InvalidAllocation(String),
+    #[error("Cannot sum an empty list of amounts: it has no currency")]
+    EmptySum,
 }
@@ -0,0 +1,823 @@
+use chrono::NaiveDate;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::Event;
+use quick_xml::{Reader, XmlVersion};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::Currency;
+use crate::errors::{Result, WalletError};
+use crate::import::{FALLBACK_DESCRIPTION, fingerprint, statement_amount};
+use crate::models::import::{DraftTransaction, ImportPreview};
+
+/// Statement (`Stmt`, camt.053) or account report (`Rpt`, camt.052) of one
+/// account
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CamtStatement {
+    /// IBAN of the account, or its other id when it has none
+    pub account: String,
+    /// Currency the account is held in
+    pub currency: Option<String>,
+    pub balances: Vec<CamtBalance>,
+    pub entries: Vec<CamtEntry>,
+    /// Balances and entries that could not be read
+    pub issues: Vec<String>,
+}
+
+/// One `Bal` of a statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct CamtBalance {
+    /// Balance type, e.g. `OPBD` for the opening and `CLBD` for the closing
+    /// booked balance
+    pub code: String,
+    pub date: NaiveDate,
+    /// Signed from the account holder's view, negative when overdrawn
+    pub amount: Decimal,
+    pub currency: Option<String>,
+}
+
+/// One `Ntry`: a booking on the account, which for a batch such as a
+/// collective direct debit covers several transactions
+#[derive(Debug, Clone, PartialEq)]
+pub struct CamtEntry {
+    /// `AcctSvcrRef` or `NtryRef`, the bank's reference of the booking
+    pub reference: Option<String>,
+    /// Booking date, or the value date for entries not booked yet
+    pub booking_date: NaiveDate,
+    /// Signed from the account holder's view, positive for money coming in
+    pub amount: Decimal,
+    pub currency: Option<String>,
+    /// False for pending and informational entries
+    pub booked: bool,
+    pub additional_info: Option<String>,
+    pub details: Vec<CamtTransactionDetails>,
+}
+
+/// One `TxDtls` of an entry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CamtTransactionDetails {
+    /// Signed like the entry amount. Only set when in the entry's currency, as
+    /// only then can it be booked on its own.
+    pub amount: Option<Decimal>,
+    pub reference: Option<String>,
+    pub end_to_end_id: Option<String>,
+    pub debtor_name: Option<String>,
+    pub debtor_iban: Option<String>,
+    pub creditor_name: Option<String>,
+    pub creditor_iban: Option<String>,
+    /// Unstructured remittance information, its lines joined
+    pub remittance_info: Option<String>,
+    /// Structured creditor reference, such as an RF reference
+    pub creditor_reference: Option<String>,
+    pub additional_info: Option<String>,
+}
+
+/// Values of an aggregate by their path below it, e.g. `RltdPties/Dbtr/Nm`
+/// within `TxDtls`. Attributes are stored as `path@name`.
+#[derive(Debug, Default)]
+struct Fields(HashMap<String, Vec<String>>);
+
+impl Fields {
+    fn insert(&mut self, path: String, value: String) {
+        self.0.entry(path).or_default().push(value);
+    }
+
+    fn get(&self, path: &str) -> Option<&str> {
+        self.0
+            .get(path)
+            .and_then(|values| values.first())
+            .map(String::as_str)
+    }
+
+    fn first_of(&self, paths: &[&str]) -> Option<&str> {
+        paths.iter().find_map(|path| self.get(path))
+    }
+
+    /// Value that carries a meaning, as banks fill mandatory references they
+    /// do not have with placeholders
+    fn reference(&self, paths: &[&str]) -> Option<String> {
+        self.first_of(paths)
+            .filter(|value| !matches!(*value, "NOTPROVIDED" | "NONREF"))
+            .map(str::to_string)
+    }
+}
+
+/// Aggregate being read, with the depth of its element in the document
+#[derive(Debug, Default)]
+struct Open {
+    depth: usize,
+    fields: Fields,
+}
+
+#[derive(Debug, Default)]
+struct OpenStatement {
+    open: Open,
+    balances: Vec<CamtBalance>,
+    entries: Vec<CamtEntry>,
+    issues: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct OpenEntry {
+    open: Open,
+    details: Vec<Fields>,
+}
+
+/// Aggregates being read. They nest, so only the innermost one collects the
+/// values met.
+#[derive(Debug, Default)]
+struct State {
+    statements: Vec<CamtStatement>,
+    statement: Option<OpenStatement>,
+    balance: Option<Open>,
+    entry: Option<OpenEntry>,
+    details: Option<Open>,
+}
+
+impl State {
+    fn innermost(&mut self) -> Option<&mut Open> {
+        if self.details.is_some() {
+            return self.details.as_mut();
+        }
+        if self.entry.is_some() {
+            return self.entry.as_mut().map(|entry| &mut entry.open);
+        }
+        if self.balance.is_some() {
+            return self.balance.as_mut();
+        }
+        self.statement.as_mut().map(|statement| &mut statement.open)
+    }
+
+    fn start(&mut self, path: &[String]) {
+        let depth = path.len();
+        let open = Open {
+            depth,
+            fields: Fields::default(),
+        };
+        let parent = depth
+            .checked_sub(2)
+            .map_or("", |index| path[index].as_str());
+        match (parent, path[depth - 1].as_str()) {
+            ("BkToCstmrStmt", "Stmt") | ("BkToCstmrAcctRpt", "Rpt") => {
+                self.statement = Some(OpenStatement {
+                    open,
+                    ..Default::default()
+                });
+            }
+            ("Stmt" | "Rpt", "Bal") if self.statement.is_some() => self.balance = Some(open),
+            ("Stmt" | "Rpt", "Ntry") if self.statement.is_some() => {
+                self.entry = Some(OpenEntry {
+                    open,
+                    details: Vec::new(),
+                });
+            }
+            ("NtryDtls", "TxDtls") if self.entry.is_some() => self.details = Some(open),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, depth: usize) {
+        let ends = |open: Option<&Open>| open.is_some_and(|open| open.depth == depth);
+
+        if ends(self.details.as_ref()) {
+            if let (Some(entry), Some(details)) = (self.entry.as_mut(), self.details.take()) {
+                entry.details.push(details.fields);
+            }
+        } else if ends(self.entry.as_ref().map(|entry| &entry.open)) {
+            let (Some(statement), Some(entry)) = (self.statement.as_mut(), self.entry.take())
+            else {
+                return;
+            };
+            match read_entry(&entry.open.fields, &entry.details) {
+                Ok(read) => statement.entries.push(read),
+                Err(reason) => {
+                    let position = statement.entries.len() + statement.issues.len() + 1;
+                    let label = entry
+                        .open
+                        .fields
+                        .reference(&["AcctSvcrRef", "NtryRef"])
+                        .unwrap_or_else(|| format!("#{position}"));
+                    statement.issues.push(format!("Entry {label}: {reason}"));
+                }
+            }
+        } else if ends(self.balance.as_ref()) {
+            let (Some(statement), Some(balance)) = (self.statement.as_mut(), self.balance.take())
+            else {
+                return;
+            };
+            match read_balance(&balance.fields) {
+                Ok(read) => statement.balances.push(read),
+                Err(reason) => statement.issues.push(format!("Balance: {reason}")),
+            }
+        } else if ends(self.statement.as_ref().map(|statement| &statement.open))
+            && let Some(statement) = self.statement.take()
+        {
+            let fields = &statement.open.fields;
+            self.statements.push(CamtStatement {
+                account: fields
+                    .first_of(&["Acct/Id/IBAN", "Acct/Id/Othr/Id"])
+                    .unwrap_or_default()
+                    .to_string(),
+                currency: fields.get("Acct/Ccy").map(str::to_uppercase),
+                balances: statement.balances,
+                entries: statement.entries,
+                issues: statement.issues,
+            });
+        }
+    }
+}
+
+/// Parse the statements of an ISO 20022 camt.053 bank-to-customer statement
+/// or the reports of a camt.052 account report, any message version. Entries
+/// and balances that cannot be read are reported as issues.
+pub fn parse_statements(content: &str) -> Result<Vec<CamtStatement>> {
+    let mut reader = Reader::from_str(content);
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+    let mut state = State::default();
+    let mut is_camt = false;
+
+    loop {
+        let event = reader.read_event().map_err(|e| {
+            WalletError::ValidationError(format!(
+                "Invalid XML at byte {}: {e}",
+                reader.error_position()
+            ))
+        })?;
+        match event {
+            Event::Start(start) => {
+                let name = start.local_name().into_inner().to_string();
+                if path.is_empty() && name != "Document" {
+                    break;
+                }
+                is_camt |= matches!(name.as_str(), "BkToCstmrStmt" | "BkToCstmrAcctRpt");
+                path.push(name);
+                text.clear();
+                state.start(&path);
+
+                let currency = start
+                    .try_get_attribute("Ccy")
+                    .ok()
+                    .flatten()
+                    .and_then(|attribute| attribute.normalized_value(XmlVersion::Implicit1_0).ok());
+                if let (Some(currency), Some(open)) = (currency, state.innermost())
+                    && path.len() > open.depth
+                {
+                    let key = format!("{}@Ccy", path[open.depth..].join("/"));
+                    open.fields.insert(key, currency.trim().to_uppercase());
+                }
+            }
+            Event::End(_) => {
+                let value = text.trim();
+                if let Some(open) = state.innermost()
+                    && path.len() > open.depth
+                    && !value.is_empty()
+                {
+                    open.fields
+                        .insert(path[open.depth..].join("/"), value.to_string());
+                }
+                text.clear();
+                state.end(path.len());
+                path.pop();
+            }
+            Event::Text(content) => text.push_str(&content.xml10_content()),
+            Event::CData(content) => text.push_str(&content.into_inner()),
+            Event::GeneralRef(reference) => match reference.resolve_char_ref() {
+                Ok(Some(c)) => text.push(c),
+                _ => text.push_str(resolve_predefined_entity(&reference).unwrap_or_default()),
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !is_camt {
+        return Err(WalletError::ValidationError(
+            "Not a camt.052 or camt.053 file: no BkToCstmrStmt or BkToCstmrAcctRpt element"
+                .to_string(),
+        ));
+    }
+    Ok(state.statements)
+}
+
+/// `YYYY-MM-DD` of a `Dt` or the date part of a `DtTm`
+fn read_date(fields: &Fields, prefix: &str) -> Option<NaiveDate> {
+    let value = fields
+        .get(&format!("{prefix}/Dt"))
+        .or_else(|| fields.get(&format!("{prefix}/DtTm")))?;
+    value
+        .get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
+
+fn read_amount(value: &str) -> std::result::Result<Decimal, String> {
+    Decimal::from_str(value).map_err(|_| format!("invalid amount '{value}'"))
+}
+
+/// Apply a `CdtDbtInd`, as camt amounts are unsigned
+fn signed(amount: Decimal, indicator: Option<&str>) -> std::result::Result<Decimal, String> {
+    match indicator {
+        Some("CRDT") => Ok(amount),
+        Some("DBIT") => Ok(-amount),
+        Some(other) => Err(format!("invalid credit/debit indicator '{other}'")),
+        None => Err("no credit/debit indicator".to_string()),
+    }
+}
+
+fn read_balance(fields: &Fields) -> std::result::Result<CamtBalance, String> {
+    let code = fields
+        .first_of(&["Tp/CdOrPrtry/Cd", "Tp/CdOrPrtry/Prtry"])
+        .ok_or("no balance type")?;
+    let date = read_date(fields, "Dt").ok_or_else(|| format!("{code} has no valid date"))?;
+    let amount = read_amount(
+        fields
+            .get("Amt")
+            .ok_or_else(|| format!("{code} has no amount"))?,
+    )?;
+
+    Ok(CamtBalance {
+        code: code.to_string(),
+        date,
+        amount: signed(amount, fields.get("CdtDbtInd"))?,
+        currency: fields.get("Amt@Ccy").map(str::to_string),
+    })
+}
+
+fn read_entry(fields: &Fields, details: &[Fields]) -> std::result::Result<CamtEntry, String> {
+    let amount = read_amount(fields.get("Amt").ok_or("no amount")?)?;
+    let indicator = fields.get("CdtDbtInd");
+    let amount = signed(amount, indicator)?;
+    let currency = fields.get("Amt@Ccy").map(str::to_string);
+    // Version 2 to 4 have the code as the value, later ones in a `Cd`
+    let status = fields.first_of(&["Sts", "Sts/Cd"]).unwrap_or("BOOK");
+    let booking_date = read_date(fields, "BookgDt")
+        .or_else(|| read_date(fields, "ValDt"))
+        .ok_or("no valid booking or value date")?;
+
+    let details = details
+        .iter()
+        .map(|details| read_details(details, indicator, currency.as_deref()))
+        .collect::<std::result::Result<Vec<_>, String>>()?;
+
+    Ok(CamtEntry {
+        reference: fields.reference(&["AcctSvcrRef", "NtryRef"]),
+        booking_date,
+        amount,
+        currency,
+        booked: status == "BOOK",
+        additional_info: fields.get("AddtlNtryInf").map(str::to_string),
+        details,
+    })
+}
+
+fn read_details(
+    fields: &Fields,
+    entry_indicator: Option<&str>,
+    entry_currency: Option<&str>,
+) -> std::result::Result<CamtTransactionDetails, String> {
+    // Version 2 only has the amount within `AmtDtls`, later ones also directly
+    let amount = match ["Amt", "AmtDtls/TxAmt/Amt"]
+        .into_iter()
+        .find(|path| fields.get(path).is_some())
+    {
+        Some(path) if fields.get(&format!("{path}@Ccy")) == entry_currency => {
+            let amount = read_amount(fields.get(path).unwrap_or_default())?;
+            let indicator = fields.get("CdtDbtInd").or(entry_indicator);
+            Some(signed(amount, indicator)?)
+        }
+        _ => None,
+    };
+    let text = |path: &str| fields.get(path).map(str::to_string);
+    let remittance_lines = fields.0.get("RmtInf/Ustrd").map(|lines| {
+        lines
+            .iter()
+            .flat_map(|line| line.split_whitespace())
+            .collect::<Vec<_>>()
+            .join(" ")
+    });
+
+    Ok(CamtTransactionDetails {
+        amount,
+        reference: fields.reference(&["Refs/AcctSvcrRef"]),
+        end_to_end_id: fields.reference(&["Refs/EndToEndId"]),
+        // Version 8 and later wrap the party in a `Pty`
+        debtor_name: fields
+            .first_of(&["RltdPties/Dbtr/Nm", "RltdPties/Dbtr/Pty/Nm"])
+            .map(str::to_string),
+        debtor_iban: text("RltdPties/DbtrAcct/Id/IBAN"),
+        creditor_name: fields
+            .first_of(&["RltdPties/Cdtr/Nm", "RltdPties/Cdtr/Pty/Nm"])
+            .map(str::to_string),
+        creditor_iban: text("RltdPties/CdtrAcct/Id/IBAN"),
+        remittance_info: remittance_lines.filter(|info| !info.is_empty()),
+        creditor_reference: text("RmtInf/Strd/CdtrRefInf/Ref"),
+        additional_info: text("AddtlTxInf"),
+    })
+}
+
+/// Drafts for the booked entries of a statement on `account_id`, each booked
+/// against `counter_account_id`. A batch whose transaction details add up to
+/// the entry becomes one draft per transaction. The external id combines the
+/// account and the bank's reference of the entry, falling back to a hash of
+/// its content for banks that give none.
+pub fn statement_drafts(
+    statement: CamtStatement,
+    account_id: i64,
+    counter_account_id: i64,
+    currency: &Currency,
+) -> ImportPreview {
+    let mut drafts = Vec::new();
+    let mut issues = statement.issues;
+    let mut occurrences: HashMap<u64, u32> = HashMap::new();
+
+    for entry in statement.entries {
+        let label = entry
+            .reference
+            .clone()
+            .unwrap_or_else(|| format!("of {}", entry.booking_date));
+        if !entry.booked {
+            issues.push(format!("Entry {label}: not booked yet"));
+            continue;
+        }
+        if let Some(entry_currency) = &entry.currency
+            && entry_currency != currency.code()
+        {
+            issues.push(format!(
+                "Entry {label}: in {entry_currency}, not {}",
+                currency.code()
+            ));
+            continue;
+        }
+
+        let key = match &entry.reference {
+            Some(reference) => reference.clone(),
+            None => {
+                let hash = fingerprint(&format!(
+                    "{}|{}|{}|{}",
+                    entry.booking_date,
+                    entry.amount,
+                    entry.additional_info.as_deref().unwrap_or_default(),
+                    entry
+                        .details
+                        .iter()
+                        .filter_map(|details| details.remittance_info.as_deref())
+                        .collect::<Vec<_>>()
+                        .join("|")
+                ));
+                let occurrence = occurrences.entry(hash).or_default();
+                *occurrence += 1;
+                format!("{hash:016x}:{occurrence}")
+            }
+        };
+        let external_id = format!("camt:{}:{key}", statement.account);
+
+        let batch_total: Option<Decimal> = entry.details.iter().map(|details| details.amount).sum();
+        let parts: Vec<(Decimal, Option<&CamtTransactionDetails>, String)> =
+            if entry.details.len() > 1 && batch_total == Some(entry.amount) {
+                entry
+                    .details
+                    .iter()
+                    .enumerate()
+                    .map(|(index, details)| {
+                        (
+                            details.amount.unwrap_or_default(),
+                            Some(details),
+                            format!("{external_id}:{}", index + 1),
+                        )
+                    })
+                    .collect()
+            } else {
+                // The details of a batch that is not split up describe only part of it
+                let details = match entry.details.as_slice() {
+                    [details] => Some(details),
+                    _ => None,
+                };
+                vec![(entry.amount, details, external_id)]
+            };
+
+        for (amount, details, external_id) in parts {
+            let amount = match statement_amount(amount, currency) {
+                Ok(amount) => amount,
+                Err(reason) => {
+                    issues.push(format!("Entry {label}: {reason}"));
+                    continue;
+                }
+            };
+            let (description, reference, notes) = describe(&entry, details, !amount.is_negative());
+            drafts.push(DraftTransaction {
+                transaction_date: entry.booking_date,
+                description,
+                reference,
+                notes,
+                account_id,
+                counter_account_id,
+                amount,
+                external_id,
+                duplicate_of: None,
+            });
+        }
+    }
+
+    ImportPreview {
+        drafts,
+        issues,
+        balance_checks: Vec::new(),
+    }
+}
+
+/// Description, reference and notes of a draft. Money coming in is described
+/// by the debtor, money going out by the creditor.
+fn describe(
+    entry: &CamtEntry,
+    details: Option<&CamtTransactionDetails>,
+    incoming: bool,
+) -> (String, Option<String>, Option<String>) {
+    let Some(details) = details else {
+        let description = entry
+            .additional_info
+            .clone()
+            .unwrap_or_else(|| FALLBACK_DESCRIPTION.to_string());
+        return (description, None, None);
+    };
+
+    let (name, iban) = if incoming {
+        (&details.debtor_name, &details.debtor_iban)
+    } else {
+        (&details.creditor_name, &details.creditor_iban)
+    };
+    let description = name
+        .clone()
+        .or_else(|| details.remittance_info.clone())
+        .or_else(|| details.additional_info.clone())
+        .or_else(|| entry.additional_info.clone())
+        .unwrap_or_else(|| FALLBACK_DESCRIPTION.to_string());
+
+    let mut notes = Vec::new();
+    if let Some(info) = &details.remittance_info
+        && *info != description
+    {
+        notes.push(info.clone());
+    }
+    if let Some(iban) = iban {
+        notes.push(format!("IBAN {iban}"));
+    }
+    let reference = details
+        .end_to_end_id
+        .clone()
+        .or_else(|| details.creditor_reference.clone());
+
+    (
+        description,
+        reference,
+        (!notes.is_empty()).then(|| notes.join("\n")),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAMT_053: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+  <BkToCstmrStmt>
+    <GrpHdr><MsgId>STMT-2025-07-03</MsgId><CreDtTm>2025-07-04T06:00:00</CreDtTm></GrpHdr>
+    <Stmt>
+      <Id>2025-07-03-EUR</Id>
+      <Acct><Id><IBAN>DE89370400440532013000</IBAN></Id><Ccy>EUR</Ccy></Acct>
+      <Bal>
+        <Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp>
+        <Amt Ccy="EUR">1000.00</Amt><CdtDbtInd>CRDT</CdtDbtInd>
+        <Dt><Dt>2025-07-01</Dt></Dt>
+      </Bal>
+      <Bal>
+        <Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp>
+        <Amt Ccy="EUR">3337.70</Amt><CdtDbtInd>CRDT</CdtDbtInd>
+        <Dt><Dt>2025-07-03</Dt></Dt>
+      </Bal>
+      <Ntry>
+        <Amt Ccy="EUR">2500.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Sts>BOOK</Sts>
+        <BookgDt><Dt>2025-07-01</Dt></BookgDt><ValDt><Dt>2025-07-01</Dt></ValDt>
+        <AcctSvcrRef>2025070100001</AcctSvcrRef>
+        <NtryDtls><TxDtls>
+          <Refs><EndToEndId>SAL-2025-07</EndToEndId></Refs>
+          <AmtDtls><TxAmt><Amt Ccy="EUR">2500.00</Amt></TxAmt></AmtDtls>
+          <RltdPties>
+            <Dbtr><Nm>Muster &amp; Co. GmbH</Nm></Dbtr>
+            <DbtrAcct><Id><IBAN>DE02120300000000202051</IBAN></Id></DbtrAcct>
+          </RltdPties>
+          <RmtInf><Ustrd>Gehalt Juli</Ustrd><Ustrd>2025</Ustrd></RmtInf>
+        </TxDtls></NtryDtls>
+        <AddtlNtryInf>GUTSCHRIFT</AddtlNtryInf>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">150.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts>BOOK</Sts>
+        <BookgDt><Dt>2025-07-02</Dt></BookgDt>
+        <AcctSvcrRef>2025070200002</AcctSvcrRef>
+        <NtryDtls>
+          <Btch><NbOfTxs>2</NbOfTxs></Btch>
+          <TxDtls>
+            <Refs><EndToEndId>NOTPROVIDED</EndToEndId></Refs>
+            <AmtDtls><TxAmt><Amt Ccy="EUR">100.00</Amt></TxAmt></AmtDtls>
+            <RltdPties>
+              <Cdtr><Nm>Stadtwerke</Nm></Cdtr>
+              <CdtrAcct><Id><IBAN>DE44500105175407324931</IBAN></Id></CdtrAcct>
+            </RltdPties>
+            <RmtInf><Strd><CdtrRefInf><Ref>RF18539007547034</Ref></CdtrRefInf></Strd></RmtInf>
+          </TxDtls>
+          <TxDtls>
+            <AmtDtls><TxAmt><Amt Ccy="EUR">50.00</Amt></TxAmt></AmtDtls>
+            <RltdPties><Cdtr><Nm>Sportverein</Nm></Cdtr></RltdPties>
+            <RmtInf><Ustrd>Beitrag Q3</Ustrd></RmtInf>
+          </TxDtls>
+        </NtryDtls>
+        <AddtlNtryInf>SAMMEL-LASTSCHRIFT</AddtlNtryInf>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">12.30</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts>BOOK</Sts>
+        <BookgDt><DtTm>2025-07-03T12:15:00+02:00</DtTm></BookgDt>
+        <AcctSvcrRef>NONREF</AcctSvcrRef>
+        <AddtlNtryInf><![CDATA[Kartenzahlung <Bäckerei>]]></AddtlNtryInf>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">80.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts>PDNG</Sts>
+        <ValDt><Dt>2025-07-04</Dt></ValDt>
+        <AcctSvcrRef>2025070400004</AcctSvcrRef>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">abc</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts>BOOK</Sts>
+        <BookgDt><Dt>2025-07-03</Dt></BookgDt>
+        <AcctSvcrRef>2025070300005</AcctSvcrRef>
+      </Ntry>
+    </Stmt>
+  </BkToCstmrStmt>
+</Document>
+"#;
+
+    const CAMT_052: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<camt:Document xmlns:camt="urn:iso:std:iso:20022:tech:xsd:camt.052.001.08">
+  <camt:BkToCstmrAcctRpt>
+    <camt:Rpt>
+      <camt:Acct><camt:Id><camt:Othr><camt:Id>0532013000</camt:Id></camt:Othr></camt:Id></camt:Acct>
+      <camt:Ntry>
+        <camt:Amt Ccy="EUR">60.00</camt:Amt><camt:CdtDbtInd>DBIT</camt:CdtDbtInd>
+        <camt:Sts><camt:Cd>BOOK</camt:Cd></camt:Sts>
+        <camt:BookgDt><camt:Dt>2025-07-05</camt:Dt></camt:BookgDt>
+        <camt:NtryDtls>
+          <camt:TxDtls>
+            <camt:Amt Ccy="EUR">40.00</camt:Amt>
+            <camt:RltdPties><camt:Cdtr><camt:Pty><camt:Nm>Buchhandlung</camt:Nm></camt:Pty></camt:Cdtr></camt:RltdPties>
+          </camt:TxDtls>
+          <camt:TxDtls>
+            <camt:AmtDtls><camt:InstdAmt><camt:Amt Ccy="USD">22.00</camt:Amt></camt:InstdAmt></camt:AmtDtls>
+            <camt:RltdPties><camt:Cdtr><camt:Pty><camt:Nm>Web Shop Inc.</camt:Nm></camt:Pty></camt:Cdtr></camt:RltdPties>
+          </camt:TxDtls>
+        </camt:NtryDtls>
+        <camt:AddtlNtryInf>SAMMELBUCHUNG</camt:AddtlNtryInf>
+      </camt:Ntry>
+    </camt:Rpt>
+  </camt:BkToCstmrAcctRpt>
+</camt:Document>
+"#;
+
+    #[test]
+    fn test_parse_statements() {
+        let statements = parse_statements(CAMT_053).unwrap();
+        assert_eq!(statements.len(), 1);
+        let statement = &statements[0];
+        assert_eq!(statement.account, "DE89370400440532013000");
+        assert_eq!(statement.currency.as_deref(), Some("EUR"));
+        assert_eq!(
+            statement.balances[1],
+            CamtBalance {
+                code: "CLBD".to_string(),
+                date: NaiveDate::from_ymd_opt(2025, 7, 3).unwrap(),
+                amount: Decimal::new(333770, 2),
+                currency: Some("EUR".to_string()),
+            }
+        );
+        assert_eq!(statement.entries.len(), 4);
+        assert_eq!(
+            statement.issues,
+            vec!["Entry 2025070300005: invalid amount 'abc'"]
+        );
+
+        let salary = &statement.entries[0].details[0];
+        assert_eq!(salary.amount, Some(Decimal::new(250000, 2)));
+        assert_eq!(salary.debtor_name.as_deref(), Some("Muster & Co. GmbH"));
+        assert_eq!(
+            salary.debtor_iban.as_deref(),
+            Some("DE02120300000000202051")
+        );
+        assert_eq!(salary.remittance_info.as_deref(), Some("Gehalt Juli 2025"));
+        let batch = &statement.entries[1];
+        assert_eq!(batch.amount, Decimal::new(-15000, 2));
+        assert_eq!(batch.details[0].amount, Some(Decimal::new(-10000, 2)));
+        assert_eq!(batch.details[0].end_to_end_id, None);
+        let card = &statement.entries[2];
+        assert_eq!(card.reference, None);
+        assert_eq!(
+            card.booking_date,
+            NaiveDate::from_ymd_opt(2025, 7, 3).unwrap()
+        );
+        assert_eq!(
+            card.additional_info.as_deref(),
+            Some("Kartenzahlung <Bäckerei>")
+        );
+        assert!(!statement.entries[3].booked);
+
+        assert!(parse_statements("<Document><pain/></Document>").is_err());
+        assert!(parse_statements("<OFX></OFX>").is_err());
+        assert!(parse_statements("<Document><BkToCstmrStmt></Document>").is_err());
+    }
+
+    #[test]
+    fn test_statement_drafts() {
+        let statement = parse_statements(CAMT_053).unwrap().remove(0);
+        let preview = statement_drafts(statement, 10, 20, &Currency::eur());
+
+        let summary: Vec<(&str, i64, &str)> = preview
+            .drafts
+            .iter()
+            .map(|draft| {
+                (
+                    draft.description.as_str(),
+                    draft.amount.amount_minor(),
+                    draft.external_id.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            &summary[..3],
+            [
+                (
+                    "Muster & Co. GmbH",
+                    250000,
+                    "camt:DE89370400440532013000:2025070100001"
+                ),
+                (
+                    "Stadtwerke",
+                    -10000,
+                    "camt:DE89370400440532013000:2025070200002:1"
+                ),
+                (
+                    "Sportverein",
+                    -5000,
+                    "camt:DE89370400440532013000:2025070200002:2"
+                ),
+            ]
+        );
+        assert_eq!(summary[3].0, "Kartenzahlung <Bäckerei>");
+        assert_eq!(summary.len(), 4);
+
+        let salary = &preview.drafts[0];
+        assert_eq!(salary.reference.as_deref(), Some("SAL-2025-07"));
+        assert_eq!(
+            salary.notes.as_deref(),
+            Some("Gehalt Juli 2025\nIBAN DE02120300000000202051")
+        );
+        assert_eq!(
+            preview.drafts[1].reference.as_deref(),
+            Some("RF18539007547034")
+        );
+        assert_eq!(preview.drafts[2].notes.as_deref(), Some("Beitrag Q3"));
+        assert_eq!(
+            preview.issues,
+            vec![
+                "Entry 2025070300005: invalid amount 'abc'".to_string(),
+                "Entry 2025070400004: not booked yet".to_string(),
+            ]
+        );
+
+        // The entry without a bank reference keeps its id across parses
+        let again = statement_drafts(
+            parse_statements(CAMT_053).unwrap().remove(0),
+            10,
+            20,
+            &Currency::eur(),
+        );
+        assert_eq!(preview.drafts, again.drafts);
+    }
+
+    #[test]
+    fn test_report_with_partial_batch() {
+        let statement = parse_statements(CAMT_052).unwrap().remove(0);
+        assert_eq!(statement.account, "0532013000");
+        assert_eq!(
+            statement.entries[0].details[0].creditor_name.as_deref(),
+            Some("Buchhandlung")
+        );
+        // Only part of the batch is in the entry's currency, so it stays whole
+        assert_eq!(statement.entries[0].details[1].amount, None);
+
+        let preview = statement_drafts(statement, 10, 20, &Currency::eur());
+        assert_eq!(preview.drafts.len(), 1);
+        assert_eq!(preview.drafts[0].description, "SAMMELBUCHUNG");
+        assert_eq!(preview.drafts[0].amount.amount_minor(), -6000);
+        assert!(
+            preview.drafts[0]
+                .external_id
+                .starts_with("camt:0532013000:")
+        );
+    }
+}
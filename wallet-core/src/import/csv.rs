@@ -0,0 +1,355 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::errors::{Result, WalletError};
+use crate::import::{FALLBACK_DESCRIPTION, fingerprint, statement_amount};
+use crate::models::import::{CsvImportProfile, DecimalSeparator, DraftTransaction, ImportPreview};
+use crate::{Currency, Money};
+
+/// Split CSV content into records, each with the line number it starts on.
+/// Fields can be quoted with `"` to hold the delimiter, line breaks or `""`
+/// for a quote. Blank lines are skipped.
+pub fn parse_records(content: &str, delimiter: char) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line_number = 1;
+    let mut record_line = 1;
+    let mut chars = content.chars().peekable();
+
+    let mut end_record = |record: &mut Vec<String>, field: &mut String, record_line: usize| {
+        record.push(std::mem::take(field));
+        let record = std::mem::take(record);
+        if record.len() > 1 || !record[0].trim().is_empty() {
+            records.push((record_line, record));
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line_number += 1;
+                    field.push(c);
+                }
+                c => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                end_record(&mut record, &mut field, record_line);
+                line_number += 1;
+                record_line = line_number;
+            }
+            c => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(WalletError::ValidationError(format!(
+            "Line {record_line}: quoted field is never closed"
+        )));
+    }
+    end_record(&mut record, &mut field, record_line);
+
+    Ok(records)
+}
+
+/// Parse an amount written with the given decimal separator. Digit grouping,
+/// a trailing minus (`12.50-`) and accounting parentheses (`(12.50)`) are
+/// understood.
+pub fn parse_amount(value: &str, separator: DecimalSeparator) -> Option<Decimal> {
+    let mut value = value.trim();
+    let mut negative = false;
+    if let Some(inner) = value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+        negative = true;
+        value = inner.trim();
+    }
+    if let Some(rest) = value.strip_suffix('-') {
+        negative = !negative;
+        value = rest.trim_end();
+    }
+
+    let (decimal, grouping) = match separator {
+        DecimalSeparator::Point => ('.', ','),
+        DecimalSeparator::Comma => (',', '.'),
+    };
+    let normalized: String = value
+        .chars()
+        .filter(|&c| c != grouping && c != '\'' && !c.is_whitespace())
+        .map(|c| if c == decimal { '.' } else { c })
+        .collect();
+
+    let amount = Decimal::from_str(&normalized).ok()?;
+    Some(if negative { -amount } else { amount })
+}
+
+/// Read a CSV statement with a mapping profile into drafts booked against the
+/// profile's counter-account. Lines that cannot be read are reported as issues
+/// so the rest of the file can still be imported.
+pub fn parse_statement(
+    content: &str,
+    profile: &CsvImportProfile,
+    currency: &Currency,
+) -> Result<ImportPreview> {
+    let records = parse_records(content, profile.delimiter)?;
+
+    let mut drafts = Vec::new();
+    let mut issues = Vec::new();
+    // Identical lines, such as two coffees on the same day, are told apart by
+    // how many came before them in the file
+    let mut occurrences: HashMap<u64, u32> = HashMap::new();
+
+    for (line_number, fields) in records.into_iter().skip(profile.skip_rows as usize) {
+        let (transaction_date, description, reference, amount) =
+            match read_row(&fields, profile, currency) {
+                Ok(row) => row,
+                Err(reason) => {
+                    issues.push(format!("Line {line_number}: {reason}"));
+                    continue;
+                }
+            };
+
+        let line_hash = fingerprint(&format!(
+            "{transaction_date}|{}|{description}|{}",
+            amount.amount_minor(),
+            reference.as_deref().unwrap_or_default()
+        ));
+        let occurrence = occurrences.entry(line_hash).or_default();
+        *occurrence += 1;
+
+        drafts.push(DraftTransaction {
+            transaction_date,
+            description,
+            reference,
+            notes: None,
+            account_id: profile.account_id,
+            counter_account_id: profile.counter_account_id,
+            amount,
+            external_id: format!("csv:{}:{line_hash:016x}:{occurrence}", profile.account_id),
+            duplicate_of: None,
+        });
+    }
+
+    Ok(ImportPreview {
+        drafts,
+        issues,
+        balance_checks: Vec::new(),
+    })
+}
+
+type Row = (NaiveDate, String, Option<String>, Money);
+
+fn read_row(
+    fields: &[String],
+    profile: &CsvImportProfile,
+    currency: &Currency,
+) -> std::result::Result<Row, String> {
+    let field = |column: u32| {
+        fields
+            .get(column as usize)
+            .map(|value| value.trim())
+            .ok_or_else(|| format!("missing column {column}"))
+    };
+    let amount_field = |column: u32| {
+        let value = field(column)?;
+        if value.is_empty() {
+            return Ok(Decimal::ZERO);
+        }
+        parse_amount(value, profile.decimal_separator)
+            .ok_or_else(|| format!("invalid amount '{value}'"))
+    };
+
+    let date = field(profile.date_column)?;
+    let transaction_date = NaiveDate::parse_from_str(date, &profile.date_format)
+        .map_err(|_| format!("invalid date '{date}'"))?;
+
+    let amount = match (
+        profile.amount_column,
+        profile.withdrawal_column,
+        profile.deposit_column,
+    ) {
+        (Some(column), _, _) if profile.negate_amounts => -amount_field(column)?,
+        (Some(column), _, _) => amount_field(column)?,
+        (None, withdrawal, deposit) => {
+            let withdrawal = withdrawal.map(amount_field).transpose()?;
+            let deposit = deposit.map(amount_field).transpose()?;
+            deposit.unwrap_or_default().abs() - withdrawal.unwrap_or_default().abs()
+        }
+    };
+    let amount = statement_amount(amount, currency)?;
+
+    // Multi-line descriptions are joined into one line
+    let description = field(profile.description_column)?
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let reference = profile
+        .reference_column
+        .map(field)
+        .transpose()?
+        .filter(|reference| !reference.is_empty())
+        .map(str::to_string);
+    let description = if description.is_empty() {
+        reference
+            .clone()
+            .unwrap_or_else(|| FALLBACK_DESCRIPTION.to_string())
+    } else {
+        description
+    };
+
+    Ok((transaction_date, description, reference, amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn profile() -> CsvImportProfile {
+        CsvImportProfile {
+            id: None,
+            name: "Bank".to_string(),
+            account_id: 10,
+            counter_account_id: 20,
+            delimiter: ';',
+            skip_rows: 1,
+            date_column: 0,
+            date_format: "%d.%m.%Y".to_string(),
+            description_column: 1,
+            reference_column: None,
+            amount_column: Some(2),
+            withdrawal_column: None,
+            deposit_column: None,
+            negate_amounts: false,
+            decimal_separator: DecimalSeparator::Comma,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let content = "a,\"b, with comma\",c\r\n\r\n\"multi\nline\",\"say \"\"hi\"\"\",\n last";
+        let records = parse_records(content, ',').unwrap();
+        assert_eq!(
+            records,
+            vec![
+                (
+                    1,
+                    vec![
+                        "a".to_string(),
+                        "b, with comma".to_string(),
+                        "c".to_string()
+                    ]
+                ),
+                (
+                    3,
+                    vec![
+                        "multi\nline".to_string(),
+                        "say \"hi\"".to_string(),
+                        String::new()
+                    ]
+                ),
+                (5, vec![" last".to_string()]),
+            ]
+        );
+        assert!(parse_records("a,\"open\nb", ',').is_err());
+    }
+
+    #[test]
+    fn test_parse_amount() {
+        let comma = DecimalSeparator::Comma;
+        let point = DecimalSeparator::Point;
+        assert_eq!(
+            parse_amount("1.234,56", comma),
+            Some(Decimal::new(123456, 2))
+        );
+        assert_eq!(parse_amount("-12,5", comma), Some(Decimal::new(-125, 1)));
+        assert_eq!(
+            parse_amount("1,234.56", point),
+            Some(Decimal::new(123456, 2))
+        );
+        assert_eq!(
+            parse_amount("1 234.56", point),
+            Some(Decimal::new(123456, 2))
+        );
+        assert_eq!(
+            parse_amount("1'234.56", point),
+            Some(Decimal::new(123456, 2))
+        );
+        assert_eq!(parse_amount("+7.00", point), Some(Decimal::new(700, 2)));
+        assert_eq!(parse_amount("12.50-", point), Some(Decimal::new(-1250, 2)));
+        assert_eq!(parse_amount("(12.50)", point), Some(Decimal::new(-1250, 2)));
+        assert_eq!(parse_amount("12a", point), None);
+    }
+
+    #[test]
+    fn test_parse_statement() {
+        let content = "\u{feff}Datum;Text;Betrag\n\
+            01.07.2025;\"Coffee\";-3,50\n\
+            01.07.2025;Coffee;-3,50\n\
+            02.07.2025;Salary;2.500,00\n\
+            2025-07-03;Bad date;1,00\n\
+            04.07.2025;Too precise;1,005\n";
+        let content = crate::import::decode_text(content.as_bytes().to_vec());
+        let preview = parse_statement(&content, &profile(), &Currency::eur()).unwrap();
+
+        let amounts: Vec<i64> = preview
+            .drafts
+            .iter()
+            .map(|draft| draft.amount.amount_minor())
+            .collect();
+        assert_eq!(amounts, vec![-350, -350, 250000]);
+        assert_eq!(preview.drafts[0].counter_account_id, 20);
+        // Identical lines get distinct external ids, stable across parses
+        assert_ne!(preview.drafts[0].external_id, preview.drafts[1].external_id);
+        let again = parse_statement(&content, &profile(), &Currency::eur()).unwrap();
+        assert_eq!(preview.drafts, again.drafts);
+        assert_eq!(
+            preview.issues,
+            vec![
+                "Line 5: invalid date '2025-07-03'".to_string(),
+                "Line 6: amount 1.005 has more decimals than EUR allows".to_string(),
+            ]
+        );
+
+        // Separate withdrawal and deposit columns
+        let split = CsvImportProfile {
+            delimiter: ',',
+            skip_rows: 0,
+            amount_column: None,
+            withdrawal_column: Some(2),
+            deposit_column: Some(3),
+            decimal_separator: DecimalSeparator::Point,
+            ..profile()
+        };
+        let preview = parse_statement(
+            "01.07.2025,Rent,800.00,\n02.07.2025,Refund,,\"1,000.00\"\n",
+            &split,
+            &Currency::eur(),
+        )
+        .unwrap();
+        let amounts: Vec<i64> = preview
+            .drafts
+            .iter()
+            .map(|draft| draft.amount.amount_minor())
+            .collect();
+        assert_eq!(amounts, vec![-80000, 100000]);
+    }
+}
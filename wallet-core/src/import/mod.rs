@@ -0,0 +1,52 @@
+//! Parsers turning bank statement files into draft transactions. They only
+//! read the file; `ImportService` looks up accounts and duplicates and commits
+//! the drafts.
+
+use rust_decimal::Decimal;
+
+use crate::{Currency, Money};
+
+pub mod camt;
+pub mod csv;
+pub mod ofx;
+
+/// Description of a statement line that has no text at all
+pub(crate) const FALLBACK_DESCRIPTION: &str = "Bank transaction";
+
+/// Text of a statement file. Bank exports that are not UTF-8 are nearly always
+/// Latin-1 or Windows-1252, so those bytes are read as Latin-1.
+pub fn decode_text(bytes: Vec<u8>) -> String {
+    let text = String::from_utf8(bytes)
+        .unwrap_or_else(|e| e.into_bytes().iter().map(|&b| char::from(b)).collect());
+    match text.strip_prefix('\u{feff}') {
+        Some(text) => text.to_string(),
+        None => text,
+    }
+}
+
+/// Amount of a statement line in the account currency. Zero amounts and more
+/// decimals than the currency has are rejected rather than rounded away, as
+/// they usually mean the file was misread, e.g. with the wrong decimal separator.
+pub(crate) fn statement_amount(
+    amount: Decimal,
+    currency: &Currency,
+) -> std::result::Result<Money, String> {
+    if amount.is_zero() {
+        return Err("amount is zero".to_string());
+    }
+    if amount.normalize().scale() > u32::from(currency.minor_unit_scale()) {
+        return Err(format!(
+            "amount {amount} has more decimals than {} allows",
+            currency.code()
+        ));
+    }
+    Money::new(amount, currency.clone()).map_err(|e| e.to_string())
+}
+
+/// FNV-1a hash, which unlike the std hasher stays the same across builds, so
+/// external ids computed from it do too
+pub(crate) fn fingerprint(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
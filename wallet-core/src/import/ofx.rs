@@ -0,0 +1,432 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::Currency;
+use crate::errors::{Result, WalletError};
+use crate::import::{FALLBACK_DESCRIPTION, statement_amount};
+use crate::models::import::{DraftTransaction, ImportPreview};
+
+/// Bank or credit card statement of one account in an OFX file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OfxStatement {
+    /// `ACCTID` of the account at the bank
+    pub account_number: String,
+    /// `CURDEF`, the currency of all amounts in the statement
+    pub currency: Option<String>,
+    pub transactions: Vec<OfxTransaction>,
+    /// Transactions that could not be read
+    pub issues: Vec<String>,
+}
+
+/// One `STMTTRN` record
+#[derive(Debug, Clone, PartialEq)]
+pub struct OfxTransaction {
+    /// Id the bank gives the transaction, unique within the account
+    pub fitid: String,
+    pub date_posted: NaiveDate,
+    /// Signed from the account holder's view, positive for money coming in
+    pub amount: Decimal,
+    pub transaction_type: Option<String>,
+    pub name: Option<String>,
+    pub memo: Option<String>,
+    /// Check or reference number
+    pub reference: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open(String),
+    Close(String),
+    Text(String),
+}
+
+/// Split OFX into tags and text. OFX 1.x is SGML, where elements holding a
+/// value have no closing tag, and starts with a `KEY:VALUE` header; OFX 2.x is
+/// XML. Both come out as the same tokens: the header, processing instructions
+/// and comments are dropped and tag names are uppercased.
+fn tokenize(content: &str) -> Vec<Token> {
+    fn push_text(tokens: &mut Vec<Token>, text: &str) {
+        let text = text.trim();
+        if !text.is_empty() {
+            tokens.push(Token::Text(decode_entities(text)));
+        }
+    }
+
+    let mut tokens = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find('<') {
+        push_text(&mut tokens, &rest[..start]);
+        rest = &rest[start + 1..];
+
+        if let Some(comment) = rest.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = rest[..end].trim();
+        rest = &rest[end + 1..];
+
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_uppercase()));
+            continue;
+        }
+        let (tag, self_closing) = match tag.strip_suffix('/') {
+            Some(tag) => (tag, true),
+            None => (tag, false),
+        };
+        let name = tag
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_uppercase();
+        tokens.push(Token::Open(name.clone()));
+        if self_closing {
+            tokens.push(Token::Close(name));
+        }
+    }
+    push_text(&mut tokens, rest);
+
+    tokens
+}
+
+/// Replace the XML entities and character references in a value
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                name => {
+                    let code = match name.strip_prefix("#x").or(name.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => name.strip_prefix('#').and_then(|dec| dec.parse().ok()),
+                    };
+                    code.and_then(char::from_u32)
+                }
+            };
+            c.map(|c| (c, end))
+        });
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            // A bare ampersand, common in SGML files
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Parse the bank (`STMTRS`) and credit card (`CCSTMTRS`) statements of an
+/// OFX or QFX file, version 1.x or 2.x. Missing closing tags of transactions
+/// are tolerated; transactions that cannot be read are reported as issues.
+pub fn parse_statements(content: &str) -> Result<Vec<OfxStatement>> {
+    let tokens = tokenize(content);
+    if !tokens.contains(&Token::Open("OFX".to_string())) {
+        return Err(WalletError::ValidationError(
+            "Not an OFX file: no <OFX> element".to_string(),
+        ));
+    }
+
+    let mut statements = Vec::new();
+    let mut statement: Option<OfxStatement> = None;
+    let mut transaction: Option<HashMap<String, String>> = None;
+
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Open(name) if name == "STMTRS" || name == "CCSTMTRS" => {
+                finish_statement(&mut statements, &mut statement, &mut transaction);
+                statement = Some(OfxStatement::default());
+            }
+            Token::Open(name) if name == "STMTTRN" => {
+                finish_transaction(&mut statement, &mut transaction);
+                transaction = Some(HashMap::new());
+            }
+            Token::Open(name) => {
+                // An element holding a value, closed or not
+                let Some(Token::Text(value)) =
+                    tokens.next_if(|token| matches!(token, Token::Text(_)))
+                else {
+                    continue;
+                };
+                if let Some(fields) = transaction.as_mut() {
+                    fields.entry(name).or_insert(value);
+                } else if let Some(statement) = statement.as_mut() {
+                    match name.as_str() {
+                        "ACCTID" => statement.account_number = value,
+                        "CURDEF" => statement.currency = Some(value.to_uppercase()),
+                        _ => {}
+                    }
+                }
+            }
+            Token::Close(name) if name == "STMTTRN" || name == "BANKTRANLIST" => {
+                finish_transaction(&mut statement, &mut transaction);
+            }
+            Token::Close(name) if name == "STMTRS" || name == "CCSTMTRS" => {
+                finish_statement(&mut statements, &mut statement, &mut transaction);
+            }
+            Token::Close(_) | Token::Text(_) => {}
+        }
+    }
+    finish_statement(&mut statements, &mut statement, &mut transaction);
+
+    Ok(statements)
+}
+
+fn finish_statement(
+    statements: &mut Vec<OfxStatement>,
+    statement: &mut Option<OfxStatement>,
+    transaction: &mut Option<HashMap<String, String>>,
+) {
+    finish_transaction(statement, transaction);
+    statements.extend(statement.take());
+}
+
+fn finish_transaction(
+    statement: &mut Option<OfxStatement>,
+    transaction: &mut Option<HashMap<String, String>>,
+) {
+    let (Some(statement), Some(fields)) = (statement.as_mut(), transaction.take()) else {
+        return;
+    };
+    match read_transaction(&fields) {
+        Ok(transaction) => statement.transactions.push(transaction),
+        Err(reason) => {
+            let position = statement.transactions.len() + statement.issues.len() + 1;
+            let label = fields
+                .get("FITID")
+                .cloned()
+                .unwrap_or_else(|| format!("#{position}"));
+            statement
+                .issues
+                .push(format!("Transaction {label}: {reason}"));
+        }
+    }
+}
+
+fn read_transaction(
+    fields: &HashMap<String, String>,
+) -> std::result::Result<OfxTransaction, String> {
+    let field = |name: &str| {
+        fields
+            .get(name)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+
+    let fitid = field("FITID").ok_or("no FITID")?;
+    // YYYYMMDD, optionally followed by the time and time zone
+    let date_posted = field("DTPOSTED").ok_or("no DTPOSTED")?;
+    let date_posted = date_posted
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| format!("invalid date '{date_posted}'"))?;
+    // Some banks write a decimal comma
+    let amount = field("TRNAMT").ok_or("no TRNAMT")?;
+    let normalized = if amount.contains('.') {
+        amount.to_string()
+    } else {
+        amount.replace(',', ".")
+    };
+    let amount =
+        Decimal::from_str(&normalized).map_err(|_| format!("invalid amount '{amount}'"))?;
+
+    Ok(OfxTransaction {
+        fitid: fitid.to_string(),
+        date_posted,
+        amount,
+        transaction_type: field("TRNTYPE").map(str::to_string),
+        name: field("NAME").map(str::to_string),
+        memo: field("MEMO").map(str::to_string),
+        reference: field("CHECKNUM").or(field("REFNUM")).map(str::to_string),
+    })
+}
+
+/// Drafts for the transactions of a statement on `account_id`, each booked
+/// against `counter_account_id`. The external id combines the bank account
+/// number and the FITID, which banks keep stable across downloads.
+pub fn statement_drafts(
+    statement: OfxStatement,
+    account_id: i64,
+    counter_account_id: i64,
+    currency: &Currency,
+) -> ImportPreview {
+    let mut drafts = Vec::new();
+    let mut issues = statement.issues;
+
+    for transaction in statement.transactions {
+        let amount = match statement_amount(transaction.amount, currency) {
+            Ok(amount) => amount,
+            Err(reason) => {
+                issues.push(format!("Transaction {}: {reason}", transaction.fitid));
+                continue;
+            }
+        };
+        let description = transaction
+            .name
+            .clone()
+            .or_else(|| transaction.memo.clone())
+            .unwrap_or_else(|| FALLBACK_DESCRIPTION.to_string());
+        let notes = transaction.memo.filter(|memo| *memo != description);
+
+        drafts.push(DraftTransaction {
+            transaction_date: transaction.date_posted,
+            description,
+            reference: transaction.reference,
+            notes,
+            account_id,
+            counter_account_id,
+            amount,
+            external_id: format!("ofx:{}:{}", statement.account_number, transaction.fitid),
+            duplicate_of: None,
+        });
+    }
+
+    ImportPreview {
+        drafts,
+        issues,
+        balance_checks: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SGML: &str = "OFXHEADER:100\r
+DATA:OFXSGML\r
+VERSION:102\r
+ENCODING:USASCII\r
+\r
+<OFX>
+<SIGNONMSGSRSV1><SONRS><STATUS><CODE>0<SEVERITY>INFO</STATUS><DTSERVER>20250705</SONRS></SIGNONMSGSRSV1>
+<BANKMSGSRSV1><STMTTRNRS><TRNUID>1<STMTRS>
+<CURDEF>USD
+<BANKACCTFROM><BANKID>121000248<ACCTID>12345678<ACCTTYPE>CHECKING</BANKACCTFROM>
+<BANKTRANLIST><DTSTART>20250701<DTEND>20250705
+<STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20250701120000.000[-5:EST]<TRNAMT>-42.17<FITID>2025070101<NAME>AT&T WIRELESS<MEMO>Monthly bill</STMTTRN>
+<STMTTRN><TRNTYPE>CHECK<DTPOSTED>20250702<TRNAMT>-100,00<FITID>2025070201<CHECKNUM>1042
+<STMTTRN><TRNTYPE>CREDIT<DTPOSTED>20250703<TRNAMT>abc<FITID>2025070301<NAME>Broken
+</BANKTRANLIST>
+<LEDGERBAL><BALAMT>1000.00<DTASOF>20250705</LEDGERBAL>
+</STMTRS></STMTTRNRS></BANKMSGSRSV1>
+</OFX>
+";
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<?OFX OFXHEADER="200" VERSION="220" SECURITY="NONE" OLDFILEUID="NONE" NEWFILEUID="NONE"?>
+<OFX>
+  <CREDITCARDMSGSRSV1>
+    <CCSTMTTRNRS>
+      <CCSTMTRS>
+        <CURDEF>eur</CURDEF>
+        <CCACCTFROM><ACCTID>4111********1111</ACCTID></CCACCTFROM>
+        <BANKTRANLIST>
+          <!-- purchases are negative -->
+          <STMTTRN>
+            <TRNTYPE>DEBIT</TRNTYPE>
+            <DTPOSTED>20250710</DTPOSTED>
+            <TRNAMT>-12.50</TRNAMT>
+            <FITID>A-1</FITID>
+            <PAYEE><NAME>Caf&#233; &amp; Bar</NAME></PAYEE>
+          </STMTTRN>
+          <STMTTRN>
+            <TRNTYPE>PAYMENT</TRNTYPE>
+            <DTPOSTED>20250715</DTPOSTED>
+            <TRNAMT>+200.00</TRNAMT>
+            <FITID>A-2</FITID>
+            <MEMO>Thank you</MEMO>
+          </STMTTRN>
+        </BANKTRANLIST>
+      </CCSTMTRS>
+    </CCSTMTTRNRS>
+  </CREDITCARDMSGSRSV1>
+</OFX>"#;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 7, day).unwrap()
+    }
+
+    #[test]
+    fn test_parse_sgml() {
+        let statements = parse_statements(SGML).unwrap();
+        assert_eq!(statements.len(), 1);
+        let statement = &statements[0];
+        assert_eq!(statement.account_number, "12345678");
+        assert_eq!(statement.currency.as_deref(), Some("USD"));
+        assert_eq!(
+            statement.transactions,
+            vec![
+                OfxTransaction {
+                    fitid: "2025070101".to_string(),
+                    date_posted: date(1),
+                    amount: Decimal::new(-4217, 2),
+                    transaction_type: Some("DEBIT".to_string()),
+                    name: Some("AT&T WIRELESS".to_string()),
+                    memo: Some("Monthly bill".to_string()),
+                    reference: None,
+                },
+                // Not closed, and with a decimal comma
+                OfxTransaction {
+                    fitid: "2025070201".to_string(),
+                    date_posted: date(2),
+                    amount: Decimal::new(-10000, 2),
+                    transaction_type: Some("CHECK".to_string()),
+                    name: None,
+                    memo: None,
+                    reference: Some("1042".to_string()),
+                },
+            ]
+        );
+        assert_eq!(
+            statement.issues,
+            vec!["Transaction 2025070301: invalid amount 'abc'"]
+        );
+    }
+
+    #[test]
+    fn test_parse_xml() {
+        let statements = parse_statements(XML).unwrap();
+        assert_eq!(statements.len(), 1);
+        let preview = statement_drafts(statements[0].clone(), 7, 8, &Currency::eur());
+        assert!(preview.issues.is_empty());
+
+        let purchase = &preview.drafts[0];
+        assert_eq!(purchase.description, "Café & Bar");
+        assert_eq!(purchase.amount.amount_minor(), -1250);
+        assert_eq!(purchase.external_id, "ofx:4111********1111:A-1");
+        assert_eq!((purchase.account_id, purchase.counter_account_id), (7, 8));
+        // Without a name the memo becomes the description
+        let payment = &preview.drafts[1];
+        assert_eq!(payment.description, "Thank you");
+        assert_eq!(payment.notes, None);
+        assert_eq!(payment.amount.amount_minor(), 20000);
+    }
+
+    #[test]
+    fn test_not_ofx() {
+        assert!(parse_statements("Date,Amount\n2025-07-01,12.50\n").is_err());
+    }
+}
@@ -1,11 +1,38 @@
 pub mod db;
 pub mod errors;
+pub mod import;
 pub mod models;
 pub mod services;
 
+pub use rust_decimal::Decimal;
+
 pub use crate::models::account::{Account, AccountNode, AccountType};
-pub use crate::models::money::{Currency, Money};
-pub use crate::models::transaction::{EntryType, Transaction, TransactionEntry};
+pub use crate::models::audit::{AuditAction, AuditEntityType, AuditEntry};
+pub use crate::models::balance_assertion::{BalanceAssertion, FailedAssertion};
+pub use crate::models::import::{
+    CsvImportProfile, DecimalSeparator, DraftTransaction, ImportPreview, ImportSummary,
+    StatementBalanceCheck,
+};
+pub use crate::models::money::{Currency, CurrencyInfo, CurrencyKind, Money};
+pub use crate::models::period::{ClosedPeriod, FiscalYearClose};
+pub use crate::models::price::{Price, PriceFileFormat, PriceImportSummary};
+pub use crate::models::reconciliation::{
+    Reconciliation, ReconciliationEntry, ReconciliationSummary,
+};
+pub use crate::models::register::{RegisterCounterpart, RegisterEntry};
+pub use crate::models::report::{
+    BalanceSheet, BalanceSheetLine, BalanceSheetSection, CashFlowCategory, CashFlowLine,
+    CashFlowStatement, ExpenseBreakdown, ExpenseCategory, IncomeStatement, IncomeStatementLine,
+    IncomeStatementSection, MissingRate, NetWorthPoint, NetWorthReport, NetWorthSeries,
+    ReportInterval, ReportPeriod, TrialBalance, TrialBalanceLine, TrialBalanceTotal, ValuedTotal,
+};
+pub use crate::models::transaction::{
+    EntryPrice, EntryStatus, EntryType, SnippetSegment, Transaction, TransactionCursor,
+    TransactionEntry, TransactionPage, TransactionSearchHit,
+};
 pub use crate::services::{
-    AccountService, ReportService, TransactionEntryInput, TransactionFilters, TransactionService,
+    AccountMatch, AccountService, AuditService, BalanceAssertionService, CurrencyService,
+    ImportService, NewTransaction, PeriodService, PriceService, ReconciliationService,
+    ReportService, TransactionEntryInput, TransactionFilters, TransactionService, TransactionSort,
+    UndoService,
 };
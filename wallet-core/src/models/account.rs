@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, Row};
 
+use crate::errors::{CurrencyError, WalletError};
 use crate::models::money::Currency;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type, specta::Type)]
@@ -14,6 +15,13 @@ pub enum AccountType {
     Expense,
 }
 
+impl AccountType {
+    /// Assets and expenses increase with debits, the other types with credits
+    pub fn is_debit_normal(&self) -> bool {
+        matches!(self, AccountType::Asset | AccountType::Expense)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
 pub struct Account {
     pub id: Option<i64>,
@@ -29,12 +37,23 @@ pub struct Account {
 
 impl FromRow<'_, sqlx::sqlite::SqliteRow> for Account {
     fn from_row(row: &sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        // Scale and symbol come from the joined currency registry, and are
+        // missing when the account's currency is not in it
+        let code: String = row.try_get("currency")?;
+        let scale: Option<u8> = row.try_get("currency_scale")?;
+        let symbol: Option<String> = row.try_get("currency_symbol")?;
+        let (Some(scale), Some(symbol)) = (scale, symbol) else {
+            return Err(sqlx::Error::Decode(sqlx::error::BoxDynError::from(
+                WalletError::from(CurrencyError::UnknownCurrency(code)),
+            )));
+        };
+
         Ok(Account {
             id: row.try_get("id")?,
             name: row.try_get("name")?,
             account_type: row.try_get("account_type")?,
             parent_id: row.try_get("parent_id")?,
-            currency: Currency::from_code(row.try_get("currency")?)
+            currency: Currency::new(&code, scale, &symbol)
                 .map_err(|e| sqlx::Error::Decode(sqlx::error::BoxDynError::from(e)))?,
             description: row.try_get("description")?,
             is_active: row.try_get("is_active")?,
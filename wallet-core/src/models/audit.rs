@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::errors::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, specta::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum AuditEntityType {
+    Account,
+    Transaction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, specta::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Deactivate,
+    Delete,
+}
+
+/// One row of the audit log: the state of an entity before and after a change,
+/// serialized as JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type, FromRow)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub entity_type: AuditEntityType,
+    pub entity_id: i64,
+    pub action: AuditAction,
+    pub actor: String,
+    pub before_json: Option<String>,
+    pub after_json: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    /// State of the entity before the change, `None` for a creation
+    pub fn before<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        Ok(self
+            .before_json
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()?)
+    }
+
+    /// State of the entity after the change, `None` for a deletion
+    pub fn after<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        Ok(self
+            .after_json
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()?)
+    }
+}
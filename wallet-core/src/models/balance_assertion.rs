@@ -0,0 +1,47 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, Row};
+
+use crate::models::money::{Currency, Money};
+
+/// Expected balance of an account at the start of a date, before any
+/// transaction of that date
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct BalanceAssertion {
+    pub id: Option<i64>,
+    pub account_id: i64,
+    pub assertion_date: NaiveDate,
+    pub expected: Money,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FromRow<'_, sqlx::sqlite::SqliteRow> for BalanceAssertion {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        let currency = Currency::new(
+            row.try_get("currency")?,
+            row.try_get("currency_scale")?,
+            row.try_get("currency_symbol")?,
+        )
+        .map_err(|e| sqlx::Error::Decode(sqlx::error::BoxDynError::from(e)))?;
+        Ok(BalanceAssertion {
+            id: row.try_get("id")?,
+            account_id: row.try_get("account_id")?,
+            assertion_date: row.try_get("assertion_date")?,
+            expected: Money::from_minor_units(row.try_get("amount_minor")?, currency),
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+/// Assertion that does not hold against the ledger, or whose balance could
+/// not be computed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct FailedAssertion {
+    pub assertion: BalanceAssertion,
+    /// `None` when the balance could not be computed
+    pub actual: Option<Money>,
+    /// Actual minus expected balance
+    pub discrepancy: Option<Money>,
+    /// Why the balance could not be computed
+    pub error: Option<String>,
+}
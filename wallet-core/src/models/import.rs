@@ -0,0 +1,123 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, Row};
+
+use crate::models::money::Money;
+
+/// Character separating the integer and fractional part of amounts. The other
+/// one of `.` and `,`, spaces and apostrophes are read as digit grouping.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, sqlx::Type, specta::Type,
+)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum DecimalSeparator {
+    /// `1,234.56`
+    #[default]
+    Point,
+    /// `1.234,56`
+    Comma,
+}
+
+/// Saved mapping from the columns of a bank's CSV export to transactions on
+/// one account. Column indices are zero-based.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct CsvImportProfile {
+    pub id: Option<i64>,
+    pub name: String,
+    /// Account the statement belongs to
+    pub account_id: i64,
+    /// Account the other side of each row is booked to, unless changed in the preview
+    pub counter_account_id: i64,
+    pub delimiter: char,
+    /// Header and other lines to skip at the start of the file
+    pub skip_rows: u32,
+    pub date_column: u32,
+    /// chrono format of the dates, e.g. `%d.%m.%Y`
+    pub date_format: String,
+    pub description_column: u32,
+    pub reference_column: Option<u32>,
+    /// Signed amount, positive for money coming in unless `negate_amounts`
+    pub amount_column: Option<u32>,
+    /// Unsigned money going out and coming in, used instead of `amount_column`
+    pub withdrawal_column: Option<u32>,
+    pub deposit_column: Option<u32>,
+    /// Flip the sign of `amount_column`, for exports where spending is positive
+    pub negate_amounts: bool,
+    pub decimal_separator: DecimalSeparator,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FromRow<'_, sqlx::sqlite::SqliteRow> for CsvImportProfile {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        let delimiter: String = row.try_get("delimiter")?;
+        Ok(CsvImportProfile {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            account_id: row.try_get("account_id")?,
+            counter_account_id: row.try_get("counter_account_id")?,
+            delimiter: delimiter.chars().next().unwrap_or(','),
+            skip_rows: row.try_get("skip_rows")?,
+            date_column: row.try_get("date_column")?,
+            date_format: row.try_get("date_format")?,
+            description_column: row.try_get("description_column")?,
+            reference_column: row.try_get("reference_column")?,
+            amount_column: row.try_get("amount_column")?,
+            withdrawal_column: row.try_get("withdrawal_column")?,
+            deposit_column: row.try_get("deposit_column")?,
+            negate_amounts: row.try_get("negate_amounts")?,
+            decimal_separator: row.try_get("decimal_separator")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+/// Statement line parsed into a transaction that is not in the ledger yet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct DraftTransaction {
+    pub transaction_date: NaiveDate,
+    pub description: String,
+    pub reference: Option<String>,
+    pub notes: Option<String>,
+    /// Account the statement belongs to
+    pub account_id: i64,
+    pub counter_account_id: i64,
+    /// Amount in the account currency, positive for money coming in
+    pub amount: Money,
+    /// Identifies the statement line, so importing it twice is detected
+    pub external_id: String,
+    /// Transaction already imported from the same statement line
+    pub duplicate_of: Option<i64>,
+}
+
+/// Balance stated in a bank statement next to the ledger balance at the same
+/// moment. Both are from the account holder's view, like draft amounts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct StatementBalanceCheck {
+    /// ISO 20022 balance type: `OPBD` for opening, `CLBD` for closing
+    pub balance_type: String,
+    /// Opening balances hold at the start of this date, closing ones at its end
+    pub date: NaiveDate,
+    pub statement_balance: Money,
+    /// Ledger balance once the drafts not imported yet are committed
+    pub ledger_balance: Money,
+    /// Statement minus ledger balance, zero when they agree
+    pub difference: Money,
+}
+
+/// Parsed statement to review before committing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ImportPreview {
+    pub drafts: Vec<DraftTransaction>,
+    /// Statement lines that could not be read, each with where it is and why
+    pub issues: Vec<String>,
+    /// Statement balances checked against the ledger, for formats stating them
+    pub balance_checks: Vec<StatementBalanceCheck>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ImportSummary {
+    /// Ids of the transactions created
+    pub transaction_ids: Vec<i64>,
+    /// Drafts skipped because they were imported before
+    pub duplicates: u32,
+}
@@ -1,3 +1,11 @@
 pub mod account;
+pub mod audit;
+pub mod balance_assertion;
+pub mod import;
 pub mod money;
+pub mod period;
+pub mod price;
+pub mod reconciliation;
+pub mod register;
+pub mod report;
 pub mod transaction;
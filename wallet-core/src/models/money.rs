@@ -1,6 +1,8 @@
-use crate::errors::{CurrencyError, Result};
+use crate::errors::{CurrencyError, MoneyError, Result, WalletError};
 use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, Row};
+use std::iter::Sum;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
 pub struct Currency {
@@ -11,9 +13,19 @@ pub struct Currency {
 
 impl Currency {
     pub fn new(code: &str, minor_unit_scale: u8, symbol: &str) -> Result<Self> {
-        if code.len() != 3 {
+        // ISO 4217 codes have 3 letters, but commodities such as stock tickers
+        // or crypto tokens can be shorter or longer
+        if code.is_empty()
+            || code.len() > 10
+            || !code
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '.' || c == '-')
+        {
             return Err(CurrencyError::InvalidCurrencyCode(code.to_string()).into());
         };
+        if minor_unit_scale > 18 {
+            return Err(CurrencyError::InvalidMinorUnitScale(minor_unit_scale).into());
+        }
         Ok(Self {
             code: code.to_string(),
             minor_unit_scale,
@@ -33,6 +45,9 @@ impl Currency {
         &self.symbol
     }
 
+    #[deprecated(
+        note = "only knows EUR and BTC; look codes up with `CurrencyService::get_currency`"
+    )]
     pub fn from_code(code: &str) -> Result<Self> {
         match code.to_uppercase().as_str() {
             "EUR" => Ok(Self::eur()),
@@ -49,6 +64,40 @@ impl Currency {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type, specta::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum CurrencyKind {
+    Fiat,
+    Crypto,
+    Security,
+    Commodity,
+}
+
+/// Entry of the currency registry: ISO 4217 currencies and user-defined commodities
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct CurrencyInfo {
+    pub currency: Currency,
+    pub name: String,
+    pub kind: CurrencyKind,
+    pub is_builtin: bool,
+}
+
+impl FromRow<'_, sqlx::sqlite::SqliteRow> for CurrencyInfo {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(CurrencyInfo {
+            currency: Currency::new(
+                row.try_get("code")?,
+                row.try_get("minor_unit_scale")?,
+                row.try_get("symbol")?,
+            )
+            .map_err(|e| sqlx::Error::Decode(sqlx::error::BoxDynError::from(e)))?,
+            name: row.try_get("name")?,
+            kind: row.try_get("kind")?,
+            is_builtin: row.try_get("is_builtin")?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
 pub struct Money {
     amount_minor: i64,
@@ -56,16 +105,17 @@ pub struct Money {
 }
 
 impl Money {
-    pub fn new(amount: Decimal, currency: Currency) -> Self {
+    /// Create an amount from a decimal, rounded to the currency's minor unit
+    pub fn new(amount: Decimal, currency: Currency) -> Result<Self> {
         let scale_factor = 10_i64.pow(currency.minor_unit_scale() as u32);
-        let amount_minor = (amount * Decimal::from(scale_factor))
-            .round()
-            .to_i64()
-            .expect("Overflow here would indicate too much money ;-)");
-        Self {
+        let amount_minor = amount
+            .checked_mul(Decimal::from(scale_factor))
+            .and_then(|minor| minor.round().to_i64())
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self {
             amount_minor,
             currency,
-        }
+        })
     }
 
     pub fn amount_minor(&self) -> i64 {
@@ -88,7 +138,7 @@ impl Money {
         }
     }
 
-    pub fn eur(amount: Decimal) -> Self {
+    pub fn eur(amount: Decimal) -> Result<Self> {
         Self::new(amount, Currency::eur())
     }
 
@@ -98,4 +148,217 @@ impl Money {
             currency,
         }
     }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount_minor == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.amount_minor < 0
+    }
+
+    pub fn checked_add(&self, other: &Money) -> Result<Money> {
+        self.ensure_same_currency(other)?;
+        let amount_minor = self
+            .amount_minor
+            .checked_add(other.amount_minor)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self::from_minor_units(amount_minor, self.currency.clone()))
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> Result<Money> {
+        self.ensure_same_currency(other)?;
+        let amount_minor = self
+            .amount_minor
+            .checked_sub(other.amount_minor)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self::from_minor_units(amount_minor, self.currency.clone()))
+    }
+
+    pub fn checked_neg(&self) -> Result<Money> {
+        let amount_minor = self
+            .amount_minor
+            .checked_neg()
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self::from_minor_units(amount_minor, self.currency.clone()))
+    }
+
+    /// Multiply by a decimal factor, rounding to the currency's minor unit
+    pub fn checked_mul(&self, factor: Decimal) -> Result<Money> {
+        let product = self
+            .to_decimal()
+            .checked_mul(factor)
+            .ok_or(MoneyError::Overflow)?;
+        Self::new(product, self.currency.clone())
+    }
+
+    /// Convert into another currency at the given rate (units of the target
+    /// currency per unit of this amount's currency)
+    pub fn convert(&self, rate: Decimal, currency: Currency) -> Result<Money> {
+        let converted = self
+            .to_decimal()
+            .checked_mul(rate)
+            .ok_or(MoneyError::Overflow)?;
+        Self::new(converted, currency)
+    }
+
+    /// Sum amounts in a known currency, so that an empty list is zero rather
+    /// than an error
+    pub fn sum_in<'a>(
+        currency: Currency,
+        amounts: impl IntoIterator<Item = &'a Money>,
+    ) -> Result<Money> {
+        amounts
+            .into_iter()
+            .try_fold(Money::zero(currency), |total, amount| {
+                total.checked_add(amount)
+            })
+    }
+
+    /// Split the amount following the given ratios. Minor units that cannot be
+    /// split evenly go to the parts with the largest remainders, so the parts
+    /// always add up exactly to the original amount.
+    pub fn allocate(&self, ratios: &[u32]) -> Result<Vec<Money>> {
+        let total_ratio: u64 = ratios.iter().map(|&ratio| ratio as u64).sum();
+        if total_ratio == 0 {
+            return Err(MoneyError::InvalidAllocation(
+                "at least one ratio must be positive".to_string(),
+            )
+            .into());
+        }
+
+        // Work on the absolute amount so remainders are distributed the same
+        // way for negative amounts
+        let amount = (self.amount_minor as i128).abs();
+        let mut shares: Vec<(i128, i128)> = ratios
+            .iter()
+            .map(|&ratio| {
+                let exact = amount * ratio as i128;
+                (exact / total_ratio as i128, exact % total_ratio as i128)
+            })
+            .collect();
+
+        let allocated: i128 = shares.iter().map(|(share, _)| share).sum();
+        let mut order: Vec<usize> = (0..shares.len()).collect();
+        order.sort_by(|&a, &b| shares[b].1.cmp(&shares[a].1));
+        for &index in order.iter().take((amount - allocated) as usize) {
+            shares[index].0 += 1;
+        }
+
+        let sign = self.amount_minor.signum() as i128;
+        Ok(shares
+            .into_iter()
+            .map(|(share, _)| Self::from_minor_units((sign * share) as i64, self.currency.clone()))
+            .collect())
+    }
+
+    fn ensure_same_currency(&self, other: &Money) -> Result<()> {
+        if self.currency.code() != other.currency.code() {
+            return Err(MoneyError::CurrencyMismatch {
+                expected: self.currency.code().to_string(),
+                found: other.currency.code().to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Summing amounts fails on currency mismatch or overflow. An empty iterator
+/// has no currency to express zero in, so it is an error as well; use
+/// `Money::sum_in` when the list may be empty.
+impl Sum<Money> for Result<Money> {
+    fn sum<I: Iterator<Item = Money>>(mut iter: I) -> Self {
+        let first = iter
+            .next()
+            .ok_or_else(|| WalletError::from(MoneyError::EmptySum))?;
+        iter.try_fold(first, |total, amount| total.checked_add(&amount))
+    }
+}
+
+impl<'a> Sum<&'a Money> for Result<Money> {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eur(amount_minor: i64) -> Money {
+        Money::from_minor_units(amount_minor, Currency::eur())
+    }
+
+    #[test]
+    fn test_new_rounds_and_checks_overflow() {
+        let money = Money::new(Decimal::new(12345, 3), Currency::eur()).unwrap();
+        assert_eq!(money.amount_minor(), 1234); // 12.345 rounds half to even
+
+        assert!(Money::new(Decimal::MAX, Currency::btc()).is_err());
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        assert_eq!(eur(1000).checked_add(&eur(250)).unwrap(), eur(1250));
+        assert_eq!(eur(1000).checked_sub(&eur(1250)).unwrap(), eur(-250));
+        assert_eq!(eur(1000).checked_neg().unwrap(), eur(-1000));
+        assert_eq!(
+            eur(1000).checked_mul(Decimal::new(15, 1)).unwrap(),
+            eur(1500)
+        );
+
+        assert!(eur(i64::MAX).checked_add(&eur(1)).is_err());
+        assert!(eur(i64::MIN).checked_neg().is_err());
+
+        let btc = Money::from_minor_units(1, Currency::btc());
+        let err = eur(1000).checked_add(&btc).unwrap_err();
+        assert!(err.to_string().contains("Currency mismatch"));
+    }
+
+    #[test]
+    fn test_sum() {
+        let total: Result<Money> = vec![eur(100), eur(200), eur(300)].into_iter().sum();
+        assert_eq!(total.unwrap(), eur(600));
+
+        let amounts = [eur(100), Money::from_minor_units(1, Currency::btc())];
+        let total: Result<Money> = amounts.iter().sum();
+        assert!(total.is_err());
+
+        let total: Result<Money> = Vec::<Money>::new().into_iter().sum();
+        assert!(matches!(
+            total,
+            Err(WalletError::MoneyError(MoneyError::EmptySum))
+        ));
+
+        assert_eq!(Money::sum_in(Currency::eur(), &[]).unwrap(), eur(0));
+        assert_eq!(
+            Money::sum_in(Currency::eur(), &[eur(100), eur(250)]).unwrap(),
+            eur(350)
+        );
+        assert!(Money::sum_in(Currency::btc(), &[eur(100)]).is_err());
+    }
+
+    #[test]
+    fn test_allocate() {
+        let parts = eur(100).allocate(&[1, 1, 1]).unwrap();
+        assert_eq!(parts, vec![eur(34), eur(33), eur(33)]);
+
+        // Equal remainders: the leftover unit goes to the first part
+        let parts = eur(5).allocate(&[3, 7]).unwrap();
+        assert_eq!(parts, vec![eur(2), eur(3)]);
+
+        let parts = eur(10).allocate(&[1, 2]).unwrap();
+        assert_eq!(parts, vec![eur(3), eur(7)]);
+
+        let parts = eur(-100).allocate(&[1, 0, 2]).unwrap();
+        assert_eq!(parts, vec![eur(-33), eur(0), eur(-67)]);
+
+        let parts = eur(i64::MAX).allocate(&[u32::MAX, u32::MAX]).unwrap();
+        let total: Result<Money> = parts.iter().sum();
+        assert_eq!(total.unwrap(), eur(i64::MAX));
+
+        assert!(eur(100).allocate(&[]).is_err());
+        assert!(eur(100).allocate(&[0, 0]).is_err());
+    }
 }
@@ -0,0 +1,22 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Accounting period closed up to and including `lock_date`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type, FromRow)]
+pub struct ClosedPeriod {
+    pub id: i64,
+    pub lock_date: NaiveDate,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Year-end close: the transaction moving Income and Expense balances into
+/// retained earnings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type, FromRow)]
+pub struct FiscalYearClose {
+    pub id: i64,
+    pub fiscal_year_end: NaiveDate,
+    pub transaction_id: i64,
+    pub retained_earnings_account_id: i64,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,50 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, Row};
+
+/// Value of one unit of `base_currency` expressed in `quote_currency` on a given date
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct Price {
+    pub id: Option<i64>,
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub price_date: NaiveDate,
+    pub rate: Decimal,
+    pub source: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FromRow<'_, sqlx::sqlite::SqliteRow> for Price {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        let rate: String = row.try_get("rate")?;
+        Ok(Price {
+            id: row.try_get("id")?,
+            base_currency: row.try_get("base_currency")?,
+            quote_currency: row.try_get("quote_currency")?,
+            price_date: row.try_get("price_date")?,
+            rate: Decimal::from_str(&rate)
+                .map_err(|e| sqlx::Error::Decode(sqlx::error::BoxDynError::from(e)))?,
+            source: row.try_get("source")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+/// Supported price file formats
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+pub enum PriceFileFormat {
+    /// ECB euro foreign exchange reference rates (eurofxref-hist.csv)
+    EcbCsv,
+    /// Ledger `P` directives (also accepts beancount `price` directives)
+    Ledger,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct PriceImportSummary {
+    pub imported: u32,
+    /// Prices involving currencies missing from the registry
+    pub skipped: u32,
+}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, Row};
+
+use crate::models::money::{Currency, Money};
+use crate::models::transaction::{EntryStatus, EntryType};
+
+/// Finished reconciliation of an account against a bank statement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct Reconciliation {
+    pub id: i64,
+    pub account_id: i64,
+    pub statement_date: NaiveDate,
+    pub statement_balance: Money,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FromRow<'_, sqlx::sqlite::SqliteRow> for Reconciliation {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        let currency = Currency::new(
+            row.try_get("currency")?,
+            row.try_get("currency_scale")?,
+            row.try_get("currency_symbol")?,
+        )
+        .map_err(|e| sqlx::Error::Decode(sqlx::error::BoxDynError::from(e)))?;
+        Ok(Reconciliation {
+            id: row.try_get("id")?,
+            account_id: row.try_get("account_id")?,
+            statement_date: row.try_get("statement_date")?,
+            statement_balance: Money::from_minor_units(
+                row.try_get("statement_balance_minor")?,
+                currency,
+            ),
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+/// Entry of the account being reconciled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ReconciliationEntry {
+    pub entry_id: i64,
+    pub transaction_id: i64,
+    pub transaction_date: NaiveDate,
+    /// Entry description, or the transaction description when the entry has none
+    pub description: String,
+    pub entry_type: EntryType,
+    /// Effect of the entry on the account balance: negative when it decreases it
+    pub amount: Money,
+    pub status: EntryStatus,
+}
+
+/// State of a reconciliation in progress
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ReconciliationSummary {
+    pub account_id: i64,
+    pub statement_date: NaiveDate,
+    pub statement_balance: Money,
+    /// Balance of the cleared and reconciled entries up to the statement date
+    pub cleared_balance: Money,
+    /// Statement balance minus cleared balance; the reconciliation can be
+    /// finished once it is zero
+    pub difference: Money,
+    /// Pending entries up to the statement date
+    pub uncleared_entries: Vec<ReconciliationEntry>,
+}
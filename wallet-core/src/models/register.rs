@@ -0,0 +1,71 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, Row};
+
+use crate::errors::{CurrencyError, WalletError};
+use crate::models::money::{Currency, Money};
+use crate::models::transaction::{EntryStatus, EntryType};
+
+/// Other account of a transaction shown in a register
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct RegisterCounterpart {
+    pub account_id: i64,
+    pub account_name: String,
+}
+
+/// Entry of an account register with the account balance right after it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct RegisterEntry {
+    pub entry_id: i64,
+    pub transaction_id: i64,
+    pub transaction_date: NaiveDate,
+    /// Transaction description
+    pub description: String,
+    pub entry_description: Option<String>,
+    pub reference: Option<String>,
+    /// Other accounts of the transaction
+    pub counterparts: Vec<RegisterCounterpart>,
+    pub entry_type: EntryType,
+    pub amount: Money,
+    pub status: EntryStatus,
+    /// Running balance of the account in the entry currency, following the
+    /// account's normal balance
+    pub balance: Money,
+}
+
+impl FromRow<'_, sqlx::sqlite::SqliteRow> for RegisterEntry {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        // Scale and symbol come from a LEFT JOIN on the currency registry and are
+        // missing when the entry currency is not in it
+        let code: String = row.try_get("currency")?;
+        let scale: Option<u8> = row.try_get("currency_scale")?;
+        let symbol: Option<String> = row.try_get("currency_symbol")?;
+        let (Some(scale), Some(symbol)) = (scale, symbol) else {
+            return Err(sqlx::Error::Decode(sqlx::error::BoxDynError::from(
+                WalletError::from(CurrencyError::UnknownCurrency(code)),
+            )));
+        };
+        let currency = Currency::new(&code, scale, &symbol)
+            .map_err(|e| sqlx::Error::Decode(sqlx::error::BoxDynError::from(e)))?;
+        let counterparts: String = row.try_get("counterparts")?;
+        let entry_type: String = row.try_get("entry_type")?;
+        Ok(RegisterEntry {
+            entry_id: row.try_get("entry_id")?,
+            transaction_id: row.try_get("transaction_id")?,
+            transaction_date: row.try_get("transaction_date")?,
+            description: row.try_get("description")?,
+            entry_description: row.try_get("entry_description")?,
+            reference: row.try_get("reference")?,
+            counterparts: serde_json::from_str(&counterparts)
+                .map_err(|e| sqlx::Error::Decode(sqlx::error::BoxDynError::from(e)))?,
+            entry_type: if entry_type == "debit" {
+                EntryType::Debit
+            } else {
+                EntryType::Credit
+            },
+            amount: Money::from_minor_units(row.try_get("amount_minor")?, currency.clone()),
+            status: row.try_get("status")?,
+            balance: Money::from_minor_units(row.try_get("balance_minor")?, currency),
+        })
+    }
+}
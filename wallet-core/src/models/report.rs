@@ -0,0 +1,251 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::account::AccountType;
+use crate::models::money::{Currency, Money};
+
+/// Total kept per currency and also valued in the reporting currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ValuedTotal {
+    /// One amount per currency, in the accounts' own currencies
+    pub by_currency: Vec<Money>,
+    /// Sum of the amounts that could be converted into the reporting currency
+    pub converted: Money,
+}
+
+/// Account whose balance could not be valued for lack of an exchange rate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct MissingRate {
+    pub account_id: i64,
+    pub account_name: String,
+    pub currency: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct NetWorthReport {
+    pub as_of: NaiveDate,
+    pub reporting_currency: Currency,
+    pub total_assets: ValuedTotal,
+    pub total_liabilities: ValuedTotal,
+    pub net_worth: ValuedTotal,
+    pub missing_rates: Vec<MissingRate>,
+}
+
+/// Debit and credit totals of one account in one currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct TrialBalanceLine {
+    pub account_id: i64,
+    pub account_name: String,
+    pub account_type: AccountType,
+    pub level: i32,
+    pub path: String,
+    pub total_debits: Money,
+    pub total_credits: Money,
+    /// Debits minus credits: positive for a debit balance, negative for a credit balance
+    pub balance: Money,
+}
+
+/// Totals of all accounts in one currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct TrialBalanceTotal {
+    pub total_debits: Money,
+    pub total_credits: Money,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct TrialBalance {
+    pub as_of: NaiveDate,
+    /// Every account of the tree, including inactive ones, in tree order; one line
+    /// per currency for accounts holding several currencies
+    pub lines: Vec<TrialBalanceLine>,
+    /// Totals per currency the entries balance in, counting entries with a
+    /// price at their converted amount
+    pub totals: Vec<TrialBalanceTotal>,
+    /// Whether total debits equal total credits in every currency of `totals`
+    pub is_balanced: bool,
+}
+
+/// Account of a balance sheet with its balance including sub-accounts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct BalanceSheetLine {
+    /// `None` for computed lines such as current earnings
+    pub account_id: Option<i64>,
+    pub name: String,
+    pub level: i32,
+    pub path: String,
+    /// One amount per report date, in the reporting currency
+    pub amounts: Vec<Money>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct BalanceSheetSection {
+    pub account_type: AccountType,
+    /// Accounts in tree order; each line is the subtotal of its sub-accounts
+    pub lines: Vec<BalanceSheetLine>,
+    /// One total per report date
+    pub totals: Vec<Money>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct BalanceSheet {
+    /// Report dates, one column each; balances are taken at the end of the day
+    pub dates: Vec<NaiveDate>,
+    pub reporting_currency: Currency,
+    pub assets: BalanceSheetSection,
+    pub liabilities: BalanceSheetSection,
+    /// Equity accounts plus current earnings (Income minus Expenses not yet
+    /// closed into retained earnings) and exchange rate differences
+    pub equity: BalanceSheetSection,
+    /// Liabilities plus equity, equal to total assets
+    pub total_liabilities_and_equity: Vec<Money>,
+    pub missing_rates: Vec<MissingRate>,
+}
+
+/// Length of the period columns of a report
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+pub enum ReportInterval {
+    Day,
+    /// Weeks run from Monday to Sunday
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+/// Column of a report, both dates included
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ReportPeriod {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+/// Income or Expense account of an income statement with its activity including
+/// sub-accounts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct IncomeStatementLine {
+    pub account_id: i64,
+    pub name: String,
+    pub level: i32,
+    pub path: String,
+    /// One amount per period, in the reporting currency
+    pub amounts: Vec<Money>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct IncomeStatementSection {
+    pub account_type: AccountType,
+    /// Accounts in tree order; each line is the subtotal of its sub-accounts
+    pub lines: Vec<IncomeStatementLine>,
+    /// One total per period
+    pub totals: Vec<Money>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct IncomeStatement {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Period columns covering `from` to `to`, a single one without interval
+    pub periods: Vec<ReportPeriod>,
+    pub reporting_currency: Currency,
+    pub income: IncomeStatementSection,
+    pub expenses: IncomeStatementSection,
+    /// Income minus expenses, one amount per period
+    pub net_income: Vec<Money>,
+    pub missing_rates: Vec<MissingRate>,
+}
+
+/// Kind of counter-account a cash movement is booked against
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+pub enum CashFlowCategory {
+    Income,
+    Expense,
+    /// Borrowing and repayments
+    Liability,
+    Equity,
+    /// Movements to or from asset accounts that are not cash
+    Transfer,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct CashFlowLine {
+    pub category: CashFlowCategory,
+    /// Cash received, one amount per period
+    pub inflows: Vec<Money>,
+    /// Cash paid, as positive amounts, one per period
+    pub outflows: Vec<Money>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct CashFlowStatement {
+    /// Cash accounts the report was asked for; their sub-accounts count as cash too
+    pub cash_account_ids: Vec<i64>,
+    pub periods: Vec<ReportPeriod>,
+    pub reporting_currency: Currency,
+    /// Cash at the start of each period
+    pub opening_cash: Vec<Money>,
+    /// One line per category, in a fixed order
+    pub lines: Vec<CashFlowLine>,
+    pub total_inflows: Vec<Money>,
+    pub total_outflows: Vec<Money>,
+    /// Change in the value of cash held in other currencies than the reporting
+    /// one: of the opening cash and of every movement, up to the rates of the
+    /// last day of the period
+    pub exchange_rate_effect: Vec<Money>,
+    /// Cash at the end of each period: opening cash, plus inflows, minus
+    /// outflows, plus the exchange rate effect
+    pub closing_cash: Vec<Money>,
+    pub missing_rates: Vec<MissingRate>,
+}
+
+/// Net worth at the end of one day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct NetWorthPoint {
+    pub date: NaiveDate,
+    pub total_assets: Money,
+    pub total_liabilities: Money,
+    pub net_worth: Money,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct NetWorthSeries {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub interval: ReportInterval,
+    pub reporting_currency: Currency,
+    /// One point at the end of every period of the interval, the last one on `to`
+    pub points: Vec<NetWorthPoint>,
+    pub missing_rates: Vec<MissingRate>,
+}
+
+/// Expense account with the spending of its sub-accounts rolled up into it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ExpenseCategory {
+    pub account_id: i64,
+    pub name: String,
+    pub level: i32,
+    pub path: String,
+    /// Spending in the reporting currency
+    pub amount: Money,
+    /// Fraction of the breakdown total, between 0 and 1 unless there are refunds
+    pub share: Decimal,
+    /// Transactions booked on the account or its rolled-up sub-accounts
+    pub transaction_count: u32,
+    /// Whether the category can be drilled into
+    pub has_children: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ExpenseBreakdown {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Category drilled into, all Expense accounts when `None`
+    pub parent_id: Option<i64>,
+    pub depth: u32,
+    pub reporting_currency: Currency,
+    pub total: Money,
+    pub transaction_count: u32,
+    /// Largest spending first
+    pub categories: Vec<ExpenseCategory>,
+    pub missing_rates: Vec<MissingRate>,
+}
@@ -1,5 +1,7 @@
-use crate::models::money::Money;
+use crate::errors::{Result, WalletError};
+use crate::models::money::{Currency, Money};
 use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
@@ -8,6 +10,51 @@ pub enum EntryType {
     Debit,
 }
 
+/// Progress of an entry through bank reconciliation
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, sqlx::Type, specta::Type,
+)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum EntryStatus {
+    /// Not yet seen on a bank statement
+    #[default]
+    Pending,
+    /// Ticked off against a statement being reconciled
+    Cleared,
+    /// Part of a finished reconciliation, locked against edits
+    Reconciled,
+}
+
+/// Price of an entry expressed in a second currency, as in `EUR 92.10 @ 1.0857 USD`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct EntryPrice {
+    /// Units of the price currency for one unit of the entry currency
+    pub rate: Decimal,
+    /// Entry amount converted into the price currency
+    pub amount: Money,
+}
+
+impl EntryPrice {
+    pub fn new(amount: &Money, rate: Decimal, currency: Currency) -> Result<Self> {
+        if rate <= Decimal::ZERO {
+            return Err(WalletError::ValidationError(
+                "Exchange rate must be positive".to_string(),
+            ));
+        }
+        if amount.currency().code() == currency.code() {
+            return Err(WalletError::ValidationError(format!(
+                "Price currency must differ from the entry currency {}",
+                currency.code()
+            )));
+        }
+
+        Ok(Self {
+            rate,
+            amount: amount.convert(rate, currency)?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
 pub struct TransactionEntry {
     pub id: Option<i64>,
@@ -16,6 +63,12 @@ pub struct TransactionEntry {
     pub amount: Money,
     pub entry_type: EntryType,
     pub description: Option<String>,
+    pub price: Option<EntryPrice>,
+    #[serde(default)]
+    pub status: EntryStatus,
+    /// Reconciliation that locked the entry
+    #[serde(default)]
+    pub reconciliation_id: Option<i64>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -26,7 +79,43 @@ pub struct Transaction {
     pub reference: Option<String>,
     pub transaction_date: NaiveDate,
     pub created_at: DateTime<Utc>,
-    pub tags: Option<String>,
+    pub tags: Vec<String>,
     pub notes: Option<String>,
+    /// Identifier of the transaction in the system it was imported from
+    pub external_id: Option<String>,
+    /// Original transaction this one reverses, if it is a reversal
+    pub reverses_transaction_id: Option<i64>,
     pub entries: Vec<TransactionEntry>,
 }
+
+/// Position in a listing sorted by date: the date and id of the last
+/// transaction already seen
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct TransactionCursor {
+    pub transaction_date: NaiveDate,
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct TransactionPage {
+    pub transactions: Vec<Transaction>,
+    /// Cursor to fetch the next page with, `None` on the last page
+    pub next_cursor: Option<TransactionCursor>,
+}
+
+/// Piece of a search snippet, highlighted where it matched the query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct SnippetSegment {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+/// Transaction found by a full-text search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct TransactionSearchHit {
+    pub transaction: Transaction,
+    /// BM25 rank, lower is a better match
+    pub rank: f64,
+    /// Best matching part of the searched text, cut around the matches
+    pub snippet: Vec<SnippetSegment>,
+}
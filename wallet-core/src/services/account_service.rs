@@ -4,19 +4,23 @@ use chrono::Utc;
 
 use crate::AccountNode;
 use crate::db::connection::Database;
-use crate::errors::{Result, WalletError};
+use crate::db::currencies::CurrencyRepository;
+use crate::errors::{CurrencyError, Result, WalletError};
+use crate::models::register::RegisterEntry;
 use crate::{Account, Currency, Money};
 use crate::{AccountType, db::accounts::AccountRepository};
 use chrono::NaiveDate;
 
 pub struct AccountService {
     repository: AccountRepository,
+    currencies: CurrencyRepository,
 }
 
 impl AccountService {
     pub fn new(db: Arc<Database>) -> Self {
         Self {
-            repository: AccountRepository::new(db),
+            repository: AccountRepository::new(db.clone()),
+            currencies: CurrencyRepository::new(db),
         }
     }
 
@@ -48,6 +52,16 @@ impl AccountService {
             ));
         }
 
+        // Validate the currency is known to the registry
+        if self
+            .currencies
+            .get_by_code(currency.code())
+            .await?
+            .is_none()
+        {
+            return Err(CurrencyError::UnknownCurrency(currency.code().to_string()).into());
+        }
+
         // Create account
         let account = Account {
             id: None,
@@ -64,8 +78,6 @@ impl AccountService {
         self.repository.create(&account).await
     }
     pub async fn calculate_balance(&self, account_id: i64) -> Result<Money> {
-        use crate::{Currency, Money};
-
         // Get the account to determine its type
         let account = self.repository.get_by_id(account_id).await?;
 
@@ -75,35 +87,12 @@ impl AccountService {
             .get_account_transaction_sums(account_id)
             .await?;
 
-        // If no transactions, return zero balance with default currency
-        let (debit_sum, credit_sum, currency_code) = match transaction_sums {
-            Some((debits, credits, currency)) => (debits, credits, currency),
-            None => {
-                // Default to EUR for zero balance
-                let currency = Currency::new("EUR", 2, "€")?;
-                return Ok(Money::from_minor_units(0, currency));
-            }
-        };
-
-        // Calculate balance based on account type (normal balance)
-        let balance_minor = match account.account_type {
-            // Assets & Expenses: Debit increases balance (Debit - Credit)
-            crate::AccountType::Asset | crate::AccountType::Expense => debit_sum - credit_sum,
-            // Liabilities, Equity & Income: Credit increases balance (Credit - Debit)
-            crate::AccountType::Liability
-            | crate::AccountType::Equity
-            | crate::AccountType::Income => credit_sum - debit_sum,
-        };
-
-        // Reconstruct Money object
-        let currency = Currency::new(&currency_code, 2, "€")?;
-        Ok(Money::from_minor_units(balance_minor, currency))
+        self.balance_from_sums(&account, transaction_sums).await
     }
 
-    /// Calculate balance including all descendant accounts (hierarchical)
-    pub async fn calculate_balance_with_children(&self, account_id: i64) -> Result<Money> {
-        use crate::{Currency, Money};
-
+    /// Calculate balance including all descendant accounts (hierarchical),
+    /// one amount per currency held in the hierarchy, the account's own first
+    pub async fn calculate_balance_with_children(&self, account_id: i64) -> Result<Vec<Money>> {
         // Get the account to determine its type
         let account = self.repository.get_by_id(account_id).await?;
 
@@ -119,30 +108,27 @@ impl AccountService {
             .get_multiple_accounts_transaction_sums(&account_ids)
             .await?;
 
-        // If no transactions, return zero balance with default currency
-        let (debit_sum, credit_sum, currency_code) = match transaction_sums {
-            Some((debits, credits, currency)) => (debits, credits, currency),
-            None => {
-                // Default to EUR for zero balance
-                let currency = Currency::new("EUR", 2, "€")?;
-                return Ok(Money::from_minor_units(0, currency));
-            }
-        };
+        self.balances_from_sums(&account, transaction_sums).await
+    }
 
-        // Calculate balance based on account type (normal balance)
-        // Note: All accounts in hierarchy should have same account type for this to make sense
-        let balance_minor = match account.account_type {
-            // Assets & Expenses: Debit increases balance (Debit - Credit)
-            crate::AccountType::Asset | crate::AccountType::Expense => debit_sum - credit_sum,
-            // Liabilities, Equity & Income: Credit increases balance (Credit - Debit)
-            crate::AccountType::Liability
-            | crate::AccountType::Equity
-            | crate::AccountType::Income => credit_sum - debit_sum,
-        };
+    /// Per-currency balances including all descendant accounts at the start of
+    /// `as_of_date`
+    pub async fn calculate_balance_with_children_as_of(
+        &self,
+        account_id: i64,
+        as_of_date: NaiveDate,
+    ) -> Result<Vec<Money>> {
+        let account = self.repository.get_by_id(account_id).await?;
+        let account_ids = self
+            .repository
+            .get_descendant_account_ids(account_id)
+            .await?;
+        let transaction_sums = self
+            .repository
+            .get_multiple_accounts_transaction_sums_before_date(&account_ids, as_of_date)
+            .await?;
 
-        // Reconstruct Money object
-        let currency = Currency::new(&currency_code, 2, "€")?;
-        Ok(Money::from_minor_units(balance_minor, currency))
+        self.balances_from_sums(&account, transaction_sums).await
     }
 
     pub async fn calculate_account_balance(
@@ -150,8 +136,6 @@ impl AccountService {
         account_id: i64,
         as_of_date: Option<NaiveDate>,
     ) -> Result<Money> {
-        use crate::{Currency, Money};
-
         // Get the account to determine its type
         let account = self.repository.get_by_id(account_id).await?;
 
@@ -169,29 +153,69 @@ impl AccountService {
             }
         };
 
-        // If no transactions, return zero balance with default currency
-        let (debit_sum, credit_sum, currency_code) = match transaction_sums {
-            Some((debits, credits, currency)) => (debits, credits, currency),
-            None => {
-                // Default to EUR for zero balance
-                let currency = Currency::new("EUR", 2, "€")?;
-                return Ok(Money::from_minor_units(0, currency));
-            }
-        };
+        self.balance_from_sums(&account, transaction_sums).await
+    }
 
-        // Calculate balance based on account type (normal balance) - same logic as calculate_balance
-        let balance_minor = match account.account_type {
-            // Assets & Expenses: Debit increases balance (Debit - Credit)
-            crate::AccountType::Asset | crate::AccountType::Expense => debit_sum - credit_sum,
-            // Liabilities, Equity & Income: Credit increases balance (Credit - Debit)
-            crate::AccountType::Liability
-            | crate::AccountType::Equity
-            | crate::AccountType::Income => credit_sum - debit_sum,
-        };
+    /// Turn raw debit/credit sums into the balance of a single account
+    async fn balance_from_sums(
+        &self,
+        account: &Account,
+        transaction_sums: Vec<(i64, i64, String)>,
+    ) -> Result<Money> {
+        let balances = self.balances_from_sums(account, transaction_sums).await?;
+        if let [balance] = balances.as_slice() {
+            return Ok(balance.clone());
+        }
 
-        // Reconstruct Money object
-        let currency = Currency::new(&currency_code, 2, "€")?;
-        Ok(Money::from_minor_units(balance_minor, currency))
+        // One amount cannot hold several currencies, and picking one would
+        // silently drop the others
+        let codes: Vec<&str> = balances.iter().map(|b| b.currency().code()).collect();
+        Err(WalletError::ValidationError(format!(
+            "{} holds amounts in several currencies ({}), so it has no single balance",
+            account.name,
+            codes.join(", ")
+        )))
+    }
+
+    /// Turn raw debit/credit sums into one balance per currency following the
+    /// account's normal balance, the account currency first
+    async fn balances_from_sums(
+        &self,
+        account: &Account,
+        transaction_sums: Vec<(i64, i64, String)>,
+    ) -> Result<Vec<Money>> {
+        // If no transactions, return zero balance in the account's currency
+        if transaction_sums.is_empty() {
+            return Ok(vec![Money::zero(account.currency.clone())]);
+        }
+
+        let mut balances = Vec::with_capacity(transaction_sums.len());
+        for (debit_sum, credit_sum, currency_code) in transaction_sums {
+            // Reconstruct Money objects, looking the currency up in the registry
+            let currency = if currency_code == account.currency.code() {
+                account.currency.clone()
+            } else {
+                self.currencies
+                    .get_by_code(&currency_code)
+                    .await?
+                    .ok_or(CurrencyError::UnknownCurrency(currency_code))?
+                    .currency
+            };
+            let debits = Money::from_minor_units(debit_sum, currency.clone());
+            let credits = Money::from_minor_units(credit_sum, currency);
+
+            // Calculate balance based on account type (normal balance)
+            balances.push(match account.account_type {
+                // Assets & Expenses: Debit increases balance (Debit - Credit)
+                AccountType::Asset | AccountType::Expense => debits.checked_sub(&credits)?,
+                // Liabilities, Equity & Income: Credit increases balance (Credit - Debit)
+                AccountType::Liability | AccountType::Equity | AccountType::Income => {
+                    credits.checked_sub(&debits)?
+                }
+            });
+        }
+        balances.sort_by_key(|balance| balance.currency().code() != account.currency.code());
+        Ok(balances)
     }
 
     pub async fn get_account_balances(&self, account_ids: &[i64]) -> Result<Vec<(i64, Money)>> {
@@ -246,6 +270,25 @@ impl AccountService {
             .await
     }
 
+    /// Entries of an account with the running balance after each, most recent
+    /// first, one page at a time
+    pub async fn get_register(
+        &self,
+        account_id: i64,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Vec<RegisterEntry>> {
+        let account = self.repository.get_by_id(account_id).await?;
+        self.repository
+            .get_register(
+                account_id,
+                account.account_type.is_debit_normal(),
+                limit,
+                offset,
+            )
+            .await
+    }
+
     pub async fn get_account(&self, id: i64) -> Result<Account> {
         self.repository.get_by_id(id).await
     }
@@ -270,14 +313,12 @@ impl AccountService {
         }
 
         // Prevent circular references
-        if let Some(account_id) = account.id {
-            if let Some(parent_id) = account.parent_id {
-                if account_id == parent_id {
-                    return Err(WalletError::ValidationError(
-                        "Account cannot be its own parent".to_string(),
-                    ));
-                }
-            }
+        if let (Some(account_id), Some(parent_id)) = (account.id, account.parent_id)
+            && account_id == parent_id
+        {
+            return Err(WalletError::ValidationError(
+                "Account cannot be its own parent".to_string(),
+            ));
         }
 
         self.repository.update(account).await
@@ -332,7 +373,7 @@ impl AccountService {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::services::transaction_service::TransactionEntryInput;
+    use crate::services::transaction_service::{NewTransaction, TransactionEntryInput};
     use crate::{AccountType, Currency, EntryType, Money, TransactionService};
     use std::sync::Arc;
 
@@ -414,21 +455,23 @@ mod tests {
                 amount: amount.clone(),
                 entry_type: EntryType::Credit, // Income increases with credit
                 description: None,
+                price: None,
             },
             TransactionEntryInput {
                 account_id: asset_id,
                 amount: amount.clone(),
                 entry_type: EntryType::Debit, // Asset increases with debit
                 description: None,
+                price: None,
             },
         ];
 
         transaction_service
-            .create_transaction(
+            .create_transaction(NewTransaction::new(
                 "Salary payment".to_string(),
                 chrono::NaiveDate::from_ymd_opt(2025, 7, 6).unwrap(),
                 entries,
-            )
+            ))
             .await
             .unwrap();
 
@@ -472,21 +515,23 @@ mod tests {
                 amount: initial_amount.clone(),
                 entry_type: EntryType::Credit,
                 description: None,
+                price: None,
             },
             TransactionEntryInput {
                 account_id: asset_id,
                 amount: initial_amount,
                 entry_type: EntryType::Debit,
                 description: None,
+                price: None,
             },
         ];
 
         transaction_service
-            .create_transaction(
+            .create_transaction(NewTransaction::new(
                 "Initial income".to_string(),
                 chrono::NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(),
                 income_entries,
-            )
+            ))
             .await
             .unwrap();
 
@@ -497,21 +542,23 @@ mod tests {
                 amount: expense_amount.clone(),
                 entry_type: EntryType::Credit, // Asset decreases with credit
                 description: None,
+                price: None,
             },
             TransactionEntryInput {
                 account_id: expense_id,
                 amount: expense_amount,
                 entry_type: EntryType::Debit, // Expense increases with debit
                 description: None,
+                price: None,
             },
         ];
 
         transaction_service
-            .create_transaction(
+            .create_transaction(NewTransaction::new(
                 "Grocery shopping".to_string(),
                 chrono::NaiveDate::from_ymd_opt(2025, 7, 6).unwrap(),
                 expense_entries,
-            )
+            ))
             .await
             .unwrap();
 
@@ -558,21 +605,23 @@ mod tests {
                 amount: amount.clone(),
                 entry_type: EntryType::Credit,
                 description: None,
+                price: None,
             },
             TransactionEntryInput {
                 account_id: child_id,
                 amount,
                 entry_type: EntryType::Debit,
                 description: None,
+                price: None,
             },
         ];
 
         transaction_service
-            .create_transaction(
+            .create_transaction(NewTransaction::new(
                 "Money to child account".to_string(),
                 chrono::NaiveDate::from_ymd_opt(2025, 7, 6).unwrap(),
                 entries,
-            )
+            ))
             .await
             .unwrap();
 
@@ -588,14 +637,15 @@ mod tests {
             .calculate_balance_with_children(parent_id)
             .await
             .unwrap();
-        assert_eq!(hierarchical_balance.amount_minor(), 50000); // Parent + child = 500 EUR
+        assert_eq!(hierarchical_balance.len(), 1);
+        assert_eq!(hierarchical_balance[0].amount_minor(), 50000); // Parent + child = 500 EUR
 
         // Child hierarchical balance should equal its own balance
         let child_hierarchical = account_service
             .calculate_balance_with_children(child_id)
             .await
             .unwrap();
-        assert_eq!(child_hierarchical.amount_minor(), 50000); // Same as direct balance
+        assert_eq!(child_hierarchical, vec![child_balance]); // Same as direct balance
     }
 
     #[sqlx::test]
@@ -695,4 +745,246 @@ mod tests {
         let updated_account = account_service.get_account(account_id).await.unwrap();
         assert!(!updated_account.is_active);
     }
+
+    #[sqlx::test]
+    async fn test_create_account_uses_registry_currency(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let account_service = AccountService::new(db.clone());
+        let currency_service = crate::CurrencyService::new(db.clone());
+
+        let usd = currency_service.get_currency("USD").await.unwrap();
+        let account = account_service
+            .create_account("US Bank".to_string(), AccountType::Asset, Some(1), usd)
+            .await
+            .unwrap();
+        assert_eq!(account.currency.symbol(), "$");
+
+        // Zero balances are expressed in the account's own currency
+        let balance = account_service
+            .calculate_balance(account.id.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(balance.currency().code(), "USD");
+
+        // Currencies missing from the registry are rejected
+        let unknown = Currency::new("XYZ", 2, "X").unwrap();
+        let result = account_service
+            .create_account("Unknown".to_string(), AccountType::Asset, Some(1), unknown)
+            .await;
+        assert!(result.is_err());
+
+        // An account whose currency left the registry is reported, not hidden
+        let id = sqlx::query(
+            "INSERT INTO accounts (name, account_type, parent_id, currency) VALUES ('Old', 'asset', 1, 'XYZ')",
+        )
+        .execute(&db.pool)
+        .await
+        .unwrap()
+        .last_insert_rowid();
+        let error = account_service.get_account(id).await.unwrap_err();
+        assert!(error.to_string().contains("XYZ"));
+        assert!(account_service.get_accounts().await.is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_calculate_balance_several_currencies(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let account_service = AccountService::new(db.clone());
+        let transaction_service = TransactionService::new(db.clone());
+        let usd = crate::CurrencyService::new(db)
+            .get_currency("USD")
+            .await
+            .unwrap();
+
+        let wallet = create_test_account(&account_service, "Wallet", AccountType::Asset, None)
+            .await
+            .id
+            .unwrap();
+        let dollars = account_service
+            .create_account(
+                "Dollars".to_string(),
+                AccountType::Asset,
+                Some(wallet),
+                usd.clone(),
+            )
+            .await
+            .unwrap()
+            .id
+            .unwrap();
+        let income = create_test_account(&account_service, "Gifts", AccountType::Income, None)
+            .await
+            .id
+            .unwrap();
+        let dollar_income = account_service
+            .create_account(
+                "Dollar Gifts".to_string(),
+                AccountType::Income,
+                Some(4),
+                usd.clone(),
+            )
+            .await
+            .unwrap()
+            .id
+            .unwrap();
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 7, 1).unwrap();
+        transaction_service
+            .create_simple_transaction(
+                "Euros".to_string(),
+                date,
+                Money::from_minor_units(500, Currency::eur()),
+                income,
+                wallet,
+            )
+            .await
+            .unwrap();
+        transaction_service
+            .create_simple_transaction(
+                "Dollars".to_string(),
+                date,
+                Money::from_minor_units(1000, usd),
+                dollar_income,
+                dollars,
+            )
+            .await
+            .unwrap();
+
+        let balance = account_service.calculate_balance(dollars).await.unwrap();
+        assert_eq!(balance.amount_minor(), 1000);
+        assert_eq!(balance.currency().code(), "USD");
+        // Euros and dollars cannot be added up into one balance, so a parent
+        // holds one per currency, its own first
+        let amounts = |balances: Vec<Money>| -> Vec<(i64, String)> {
+            balances
+                .iter()
+                .map(|b| (b.amount_minor(), b.currency().code().to_string()))
+                .collect()
+        };
+        let wallet_balances = account_service
+            .calculate_balance_with_children(wallet)
+            .await
+            .unwrap();
+        assert_eq!(
+            amounts(wallet_balances),
+            [(500, "EUR".to_string()), (1000, "USD".to_string())]
+        );
+        let income_balances = account_service
+            .calculate_balance_with_children(4)
+            .await
+            .unwrap();
+        assert_eq!(
+            amounts(income_balances),
+            [(500, "EUR".to_string()), (1000, "USD".to_string())]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_register(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let account_service = AccountService::new(db.clone());
+        let transaction_service = TransactionService::new(db);
+
+        let bank = create_test_account(&account_service, "Bank", AccountType::Asset, None)
+            .await
+            .id
+            .unwrap();
+        let card = create_test_account(&account_service, "Card", AccountType::Liability, None)
+            .await
+            .id
+            .unwrap();
+        let salary = create_test_account(&account_service, "Salary", AccountType::Income, None)
+            .await
+            .id
+            .unwrap();
+        let rent = create_test_account(&account_service, "Rent", AccountType::Expense, None)
+            .await
+            .id
+            .unwrap();
+
+        // Created out of date order on purpose
+        for (from, to, amount_minor, day, description) in [
+            (salary, bank, 100000, 1, "Salary"),
+            (card, rent, 5000, 3, "Deposit"),
+            (bank, card, 5000, 5, "Card payment"),
+            (bank, rent, 30000, 2, "Rent"),
+        ] {
+            let amount = Money::from_minor_units(amount_minor, Currency::eur());
+            let entries = vec![
+                TransactionEntryInput {
+                    account_id: from,
+                    amount: amount.clone(),
+                    entry_type: EntryType::Credit,
+                    description: None,
+                    price: None,
+                },
+                TransactionEntryInput {
+                    account_id: to,
+                    amount,
+                    entry_type: EntryType::Debit,
+                    description: None,
+                    price: None,
+                },
+            ];
+            transaction_service
+                .create_transaction(NewTransaction::new(
+                    description.to_string(),
+                    NaiveDate::from_ymd_opt(2025, 7, day).unwrap(),
+                    entries,
+                ))
+                .await
+                .unwrap();
+        }
+
+        let summary = |register: Vec<RegisterEntry>| -> Vec<(String, i64, i64)> {
+            register
+                .into_iter()
+                .map(|entry| {
+                    (
+                        entry.description,
+                        entry.amount.amount_minor(),
+                        entry.balance.amount_minor(),
+                    )
+                })
+                .collect()
+        };
+
+        let page = account_service
+            .get_register(bank, Some(2), None)
+            .await
+            .unwrap();
+        assert_eq!(page[0].counterparts.len(), 1);
+        assert_eq!(page[0].counterparts[0].account_id, card);
+        assert_eq!(page[0].entry_type, EntryType::Credit);
+        assert_eq!(
+            summary(page),
+            vec![
+                ("Card payment".to_string(), 5000, 65000),
+                ("Rent".to_string(), 30000, 70000),
+            ]
+        );
+        let page = account_service
+            .get_register(bank, Some(2), Some(2))
+            .await
+            .unwrap();
+        assert_eq!(summary(page), vec![("Salary".to_string(), 100000, 100000)]);
+
+        // Liabilities grow with credits
+        let register = account_service
+            .get_register(card, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            summary(register),
+            vec![
+                ("Card payment".to_string(), 5000, 0),
+                ("Deposit".to_string(), 5000, 5000),
+            ]
+        );
+
+        assert!(
+            account_service
+                .get_register(9999, None, None)
+                .await
+                .is_err()
+        );
+    }
 }
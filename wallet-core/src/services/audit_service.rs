@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use crate::db::audit::AuditRepository;
+use crate::db::connection::Database;
+use crate::errors::Result;
+use crate::{AuditEntityType, AuditEntry};
+
+pub struct AuditService {
+    repository: AuditRepository,
+}
+
+impl AuditService {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            repository: AuditRepository::new(db),
+        }
+    }
+
+    /// Every change made to one account or transaction, oldest first
+    pub async fn get_history(
+        &self,
+        entity_type: AuditEntityType,
+        entity_id: i64,
+    ) -> Result<Vec<AuditEntry>> {
+        self.repository.get_history(entity_type, entity_id).await
+    }
+
+    /// Most recent changes across the ledger, newest first
+    pub async fn get_recent_changes(&self, limit: u32) -> Result<Vec<AuditEntry>> {
+        self.repository.get_recent(limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::{ASSETS, EXPENSES, create_account, date, eur};
+    use crate::{
+        Account, AccountService, AccountType, AuditAction, Currency, Transaction,
+        TransactionService,
+    };
+
+    #[sqlx::test]
+    async fn test_account_history(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let account_service = AccountService::new(db.clone());
+        let audit_service = AuditService::new(db);
+
+        let mut account = account_service
+            .create_account(
+                "Bank".to_string(),
+                AccountType::Asset,
+                Some(ASSETS),
+                Currency::eur(),
+            )
+            .await
+            .unwrap();
+        let id = account.id.unwrap();
+        account.name = "Main bank".to_string();
+        account_service.update_account(&account).await.unwrap();
+        account_service.deactivate_account(id).await.unwrap();
+
+        let history = audit_service
+            .get_history(AuditEntityType::Account, id)
+            .await
+            .unwrap();
+        let actions: Vec<AuditAction> = history.iter().map(|entry| entry.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                AuditAction::Create,
+                AuditAction::Update,
+                AuditAction::Deactivate
+            ]
+        );
+
+        assert_eq!(history[0].before::<Account>().unwrap(), None);
+        let before = history[1].before::<Account>().unwrap().unwrap();
+        let after = history[1].after::<Account>().unwrap().unwrap();
+        assert_eq!(before.name, "Bank");
+        assert_eq!(after.name, "Main bank");
+        let deactivated = history[2].after::<Account>().unwrap().unwrap();
+        assert!(!deactivated.is_active);
+        assert!(!history[0].actor.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_transaction_history(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let transaction_service = TransactionService::new(db.clone());
+        let audit_service = AuditService::new(db.clone());
+
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let groceries = create_account(&db, "Groceries", AccountType::Expense, EXPENSES).await;
+
+        let created = transaction_service
+            .create_simple_transaction(
+                "Supermarket".to_string(),
+                date(2025, 7, 1),
+                eur(5000),
+                bank,
+                groceries,
+            )
+            .await
+            .unwrap();
+        let id = created.id.unwrap();
+        transaction_service.delete_transaction(id).await.unwrap();
+
+        // A failed change leaves no trace
+        assert!(transaction_service.delete_transaction(id).await.is_err());
+
+        let history = audit_service
+            .get_history(AuditEntityType::Transaction, id)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].action, AuditAction::Delete);
+        assert_eq!(history[1].after::<Transaction>().unwrap(), None);
+        let deleted = history[1].before::<Transaction>().unwrap().unwrap();
+        assert_eq!(deleted, created);
+
+        // The log itself cannot be rewritten
+        let update = sqlx::query("UPDATE audit_log SET actor = 'someone else'")
+            .execute(&db.pool)
+            .await;
+        assert!(update.is_err());
+        let delete = sqlx::query("DELETE FROM audit_log").execute(&db.pool).await;
+        assert!(delete.is_err());
+
+        let recent = audit_service.get_recent_changes(1).await.unwrap();
+        assert_eq!(recent[0].action, AuditAction::Delete);
+    }
+}
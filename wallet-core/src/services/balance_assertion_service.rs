@@ -0,0 +1,304 @@
+use chrono::{NaiveDate, Utc};
+use std::sync::Arc;
+
+use crate::db::balance_assertions::BalanceAssertionRepository;
+use crate::db::connection::Database;
+use crate::errors::{Result, WalletError};
+use crate::models::balance_assertion::{BalanceAssertion, FailedAssertion};
+use crate::{AccountService, Money};
+
+pub struct BalanceAssertionService {
+    repository: BalanceAssertionRepository,
+    account_service: AccountService,
+}
+
+impl BalanceAssertionService {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            repository: BalanceAssertionRepository::new(db.clone()),
+            account_service: AccountService::new(db),
+        }
+    }
+
+    /// Record that an account had `expected` as balance at the start of `date`
+    pub async fn create_assertion(
+        &self,
+        account_id: i64,
+        date: NaiveDate,
+        expected: Money,
+    ) -> Result<BalanceAssertion> {
+        let account = self.account_service.get_account(account_id).await?;
+        if expected.currency().code() != account.currency.code() {
+            return Err(WalletError::ValidationError(format!(
+                "Asserted balance must be in the account currency {}",
+                account.currency.code()
+            )));
+        }
+
+        let assertion = BalanceAssertion {
+            id: None,
+            account_id,
+            assertion_date: date,
+            expected,
+            created_at: Utc::now(),
+        };
+        self.repository.create(&assertion).await
+    }
+
+    pub async fn get_assertions(&self, account_id: Option<i64>) -> Result<Vec<BalanceAssertion>> {
+        self.repository.get_all(account_id).await
+    }
+
+    pub async fn delete_assertion(&self, id: i64) -> Result<()> {
+        self.repository.delete(id).await
+    }
+
+    /// Check every assertion against the ledger and return the ones that fail.
+    /// An assertion on a parent account covers its sub-accounts, in the asserted
+    /// currency only, like a beancount balance directive. One whose
+    /// balance cannot be computed fails with the reason instead of stopping
+    /// the check
+    pub async fn check_assertions(&self) -> Result<Vec<FailedAssertion>> {
+        let mut failures = Vec::new();
+        for assertion in self.repository.get_all(None).await? {
+            match self.discrepancy(&assertion).await {
+                Ok(None) => {}
+                Ok(Some((actual, discrepancy))) => failures.push(FailedAssertion {
+                    assertion,
+                    actual: Some(actual),
+                    discrepancy: Some(discrepancy),
+                    error: None,
+                }),
+                Err(e) => failures.push(FailedAssertion {
+                    assertion,
+                    actual: None,
+                    discrepancy: None,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+        Ok(failures)
+    }
+
+    /// Ledger balance and its difference to the expected one, `None` when the
+    /// assertion holds
+    async fn discrepancy(&self, assertion: &BalanceAssertion) -> Result<Option<(Money, Money)>> {
+        let currency = assertion.expected.currency();
+        let actual = self
+            .account_service
+            .calculate_balance_with_children_as_of(assertion.account_id, assertion.assertion_date)
+            .await?
+            .into_iter()
+            .find(|balance| balance.currency().code() == currency.code())
+            .unwrap_or_else(|| Money::zero(currency.clone()));
+        let discrepancy = actual.checked_sub(&assertion.expected)?;
+        Ok((!discrepancy.is_zero()).then_some((actual, discrepancy)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::{
+        ASSETS, EQUITY, create_account, create_account_in, date, eur,
+    };
+    use crate::{AccountType, Currency, CurrencyService, TransactionService};
+
+    #[sqlx::test]
+    async fn test_check_assertions(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let assertion_service = BalanceAssertionService::new(db.clone());
+
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let opening = create_account(&db, "Opening Balances", AccountType::Equity, EQUITY).await;
+        TransactionService::new(db)
+            .create_simple_transaction(
+                "Opening balance".to_string(),
+                date(2025, 7, 10),
+                eur(100000),
+                opening,
+                bank,
+            )
+            .await
+            .unwrap();
+
+        // Assertions hold at the start of the day: the deposit of the 10th only
+        // counts from the 11th
+        assertion_service
+            .create_assertion(bank, date(2025, 7, 10), eur(0))
+            .await
+            .unwrap();
+        assertion_service
+            .create_assertion(bank, date(2025, 7, 11), eur(100000))
+            .await
+            .unwrap();
+        let wrong = assertion_service
+            .create_assertion(bank, date(2025, 7, 20), eur(120000))
+            .await
+            .unwrap();
+
+        let failures = assertion_service.check_assertions().await.unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].assertion, wrong);
+        assert_eq!(failures[0].actual.as_ref().unwrap().amount_minor(), 100000);
+        assert_eq!(
+            failures[0].discrepancy.as_ref().unwrap().amount_minor(),
+            -20000
+        );
+
+        assertion_service
+            .delete_assertion(wrong.id.unwrap())
+            .await
+            .unwrap();
+        assert!(
+            assertion_service
+                .check_assertions()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            assertion_service
+                .get_assertions(Some(bank))
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Assertions are in the account currency
+        assert!(
+            assertion_service
+                .create_assertion(
+                    bank,
+                    date(2025, 7, 21),
+                    Money::from_minor_units(100, Currency::btc())
+                )
+                .await
+                .is_err()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_assertion_on_parent_account(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let checking = create_account(&db, "Checking", AccountType::Asset, bank).await;
+        let savings = create_account(&db, "Savings", AccountType::Asset, bank).await;
+        let opening = create_account(&db, "Opening Balances", AccountType::Equity, EQUITY).await;
+        let transaction_service = TransactionService::new(db.clone());
+        let assertion_service = BalanceAssertionService::new(db);
+
+        for (to, amount) in [(checking, 30000), (savings, 70000)] {
+            transaction_service
+                .create_simple_transaction(
+                    "Opening balance".to_string(),
+                    date(2025, 7, 1),
+                    eur(amount),
+                    opening,
+                    to,
+                )
+                .await
+                .unwrap();
+        }
+
+        // The parent balance is rolled up from its children
+        assertion_service
+            .create_assertion(bank, date(2025, 7, 2), eur(100000))
+            .await
+            .unwrap();
+        assert!(
+            assertion_service
+                .check_assertions()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let wrong = assertion_service
+            .create_assertion(bank, date(2025, 7, 3), eur(30000))
+            .await
+            .unwrap();
+        let failures = assertion_service.check_assertions().await.unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].assertion, wrong);
+        assert_eq!(failures[0].actual.as_ref().unwrap().amount_minor(), 100000);
+    }
+
+    #[sqlx::test]
+    async fn test_assertion_without_computable_balance(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let usd = CurrencyService::new(db.clone())
+            .get_currency("USD")
+            .await
+            .unwrap();
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let us_bank =
+            create_account_in(&db, "US Bank", AccountType::Asset, bank, usd.clone()).await;
+        let opening = create_account(&db, "Opening Balances", AccountType::Equity, EQUITY).await;
+        let opening_usd = create_account_in(
+            &db,
+            "Opening Balances USD",
+            AccountType::Equity,
+            EQUITY,
+            usd.clone(),
+        )
+        .await;
+        let transaction_service = TransactionService::new(db.clone());
+        let assertion_service = BalanceAssertionService::new(db.clone());
+
+        transaction_service
+            .create_simple_transaction(
+                "Opening balance".to_string(),
+                date(2025, 7, 1),
+                eur(100000),
+                opening,
+                bank,
+            )
+            .await
+            .unwrap();
+        transaction_service
+            .create_simple_transaction(
+                "Opening balance".to_string(),
+                date(2025, 7, 1),
+                Money::from_minor_units(5000, usd),
+                opening_usd,
+                us_bank,
+            )
+            .await
+            .unwrap();
+
+        // Dollars below the bank are outside an assertion in euros
+        let unknown = assertion_service
+            .create_assertion(bank, date(2025, 7, 2), eur(100000))
+            .await
+            .unwrap();
+        assert!(
+            assertion_service
+                .check_assertions()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // A currency missing from the registry leaves the bank without a
+        // balance; the check still covers the other assertions
+        sqlx::query("UPDATE transaction_entries SET currency = 'XYZ' WHERE account_id = ?")
+            .bind(us_bank)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        let wrong = assertion_service
+            .create_assertion(opening, date(2025, 7, 2), eur(0))
+            .await
+            .unwrap();
+        let failures = assertion_service.check_assertions().await.unwrap();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].assertion, unknown);
+        assert_eq!(failures[0].actual, None);
+        assert_eq!(failures[0].discrepancy, None);
+        assert!(failures[0].error.as_ref().unwrap().contains("XYZ"));
+        assert_eq!(failures[1].assertion, wrong);
+        assert_eq!(failures[1].error, None);
+    }
+}
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use crate::db::connection::Database;
+use crate::db::currencies::CurrencyRepository;
+use crate::errors::{CurrencyError, Result, WalletError};
+use crate::{Currency, CurrencyInfo, CurrencyKind};
+
+pub struct CurrencyService {
+    repository: CurrencyRepository,
+}
+
+impl CurrencyService {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            repository: CurrencyRepository::new(db),
+        }
+    }
+
+    /// Look up a currency or commodity in the registry by its code
+    pub async fn get_currency(&self, code: &str) -> Result<Currency> {
+        Ok(self.get_currency_info(code).await?.currency)
+    }
+
+    pub async fn get_currency_info(&self, code: &str) -> Result<CurrencyInfo> {
+        let code = code.trim().to_uppercase();
+        self.repository
+            .get_by_code(&code)
+            .await?
+            .ok_or_else(|| CurrencyError::UnknownCurrency(code).into())
+    }
+
+    pub async fn list_currencies(&self) -> Result<Vec<CurrencyInfo>> {
+        self.repository.get_all().await
+    }
+
+    /// Register a user-defined commodity (crypto token, stock ticker...)
+    pub async fn create_commodity(
+        &self,
+        code: &str,
+        name: String,
+        minor_unit_scale: u8,
+        symbol: &str,
+        kind: CurrencyKind,
+    ) -> Result<CurrencyInfo> {
+        // Fiat currencies are all part of the built-in ISO 4217 table
+        if kind == CurrencyKind::Fiat {
+            return Err(WalletError::ValidationError(
+                "Fiat currencies cannot be user-defined".to_string(),
+            ));
+        }
+
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(WalletError::ValidationError(
+                "Commodity name cannot be empty".to_string(),
+            ));
+        }
+
+        let code = code.trim().to_uppercase();
+        if self.repository.get_by_code(&code).await?.is_some() {
+            return Err(WalletError::ValidationError(format!(
+                "Currency {code} already exists"
+            )));
+        }
+
+        // Default the symbol to the code, as is usual for tickers
+        let symbol = match symbol.trim() {
+            "" => code.as_str(),
+            symbol => symbol,
+        };
+
+        let info = CurrencyInfo {
+            currency: Currency::new(&code, minor_unit_scale, symbol)?,
+            name: name.to_string(),
+            kind,
+            is_builtin: false,
+        };
+
+        self.repository.create(&info).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_iso_currencies_are_seeded(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let service = CurrencyService::new(db);
+
+        let usd = service.get_currency("usd").await.unwrap();
+        assert_eq!(usd.code(), "USD");
+        assert_eq!(usd.symbol(), "$");
+        assert_eq!(usd.minor_unit_scale(), 2);
+
+        let jpy = service.get_currency("JPY").await.unwrap();
+        assert_eq!(jpy.minor_unit_scale(), 0);
+
+        let kwd = service.get_currency("KWD").await.unwrap();
+        assert_eq!(kwd.minor_unit_scale(), 3);
+
+        let info = service.get_currency_info("EUR").await.unwrap();
+        assert_eq!(info.name, "Euro");
+        assert_eq!(info.kind, CurrencyKind::Fiat);
+        assert!(info.is_builtin);
+
+        assert!(service.get_currency("XYZ").await.is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_create_commodity(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let service = CurrencyService::new(db);
+
+        let created = service
+            .create_commodity(
+                "aapl",
+                "Apple Inc.".to_string(),
+                4,
+                "",
+                CurrencyKind::Security,
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.currency.code(), "AAPL");
+        assert_eq!(created.currency.symbol(), "AAPL");
+        assert_eq!(created.currency.minor_unit_scale(), 4);
+        assert!(!created.is_builtin);
+
+        let loaded = service.get_currency("AAPL").await.unwrap();
+        assert_eq!(loaded, created.currency);
+
+        // Duplicates and user-defined fiat currencies are rejected
+        assert!(
+            service
+                .create_commodity("AAPL", "Apple".to_string(), 4, "", CurrencyKind::Security)
+                .await
+                .is_err()
+        );
+        assert!(
+            service
+                .create_commodity("ABC", "Fake".to_string(), 2, "", CurrencyKind::Fiat)
+                .await
+                .is_err()
+        );
+    }
+}
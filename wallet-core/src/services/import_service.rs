@@ -0,0 +1,721 @@
+use chrono::format::{Item, StrftimeItems};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::db::connection::Database;
+use crate::db::import_profiles::ImportProfileRepository;
+use crate::db::transactions::TransactionRepository;
+use crate::errors::{Result, WalletError};
+use crate::import::camt::{self, CamtBalance};
+use crate::import::{self, csv, ofx};
+use crate::models::import::{
+    CsvImportProfile, DraftTransaction, ImportPreview, ImportSummary, StatementBalanceCheck,
+};
+use crate::services::transaction_service::{NewTransaction, TransactionEntryInput};
+use crate::{Account, AccountService, AccountType, EntryType, Money, TransactionService};
+
+/// Imports bank statements: files are parsed into drafts for review, then the
+/// reviewed drafts are committed as transactions
+pub struct ImportService {
+    profiles: ImportProfileRepository,
+    transactions: TransactionRepository,
+    transaction_service: TransactionService,
+    account_service: AccountService,
+}
+
+impl ImportService {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            profiles: ImportProfileRepository::new(db.clone()),
+            transactions: TransactionRepository::new(db.clone()),
+            transaction_service: TransactionService::new(db.clone()),
+            account_service: AccountService::new(db),
+        }
+    }
+
+    pub async fn create_csv_profile(&self, profile: CsvImportProfile) -> Result<CsvImportProfile> {
+        self.validate_csv_profile(&profile).await?;
+        self.profiles.create(&profile).await
+    }
+
+    pub async fn update_csv_profile(&self, profile: CsvImportProfile) -> Result<CsvImportProfile> {
+        self.validate_csv_profile(&profile).await?;
+        self.profiles.update(&profile).await
+    }
+
+    pub async fn get_csv_profiles(&self) -> Result<Vec<CsvImportProfile>> {
+        self.profiles.get_all().await
+    }
+
+    pub async fn delete_csv_profile(&self, id: i64) -> Result<()> {
+        self.profiles.delete(id).await
+    }
+
+    /// Parse a CSV statement with a saved profile, marking the lines that
+    /// were imported before
+    pub async fn preview_csv(&self, profile_id: i64, content: &str) -> Result<ImportPreview> {
+        let profile = self.profiles.get_by_id(profile_id).await?;
+        let account = self.account_service.get_account(profile.account_id).await?;
+        let mut preview = csv::parse_statement(content, &profile, &account.currency)?;
+        self.mark_duplicates(&mut preview.drafts).await?;
+        Ok(preview)
+    }
+
+    /// Preview a CSV statement file from disk
+    pub async fn preview_csv_file(
+        &self,
+        profile_id: i64,
+        path: impl AsRef<Path>,
+    ) -> Result<ImportPreview> {
+        let content = import::decode_text(tokio::fs::read(path).await?);
+        self.preview_csv(profile_id, &content).await
+    }
+
+    /// Parse an OFX or QFX statement of an asset or liability account, booking
+    /// every line against the counter-account
+    pub async fn preview_ofx(
+        &self,
+        account_id: i64,
+        counter_account_id: i64,
+        content: &str,
+    ) -> Result<ImportPreview> {
+        let account = self
+            .statement_account(account_id, counter_account_id)
+            .await?;
+
+        let mut statements = ofx::parse_statements(content)?;
+        if statements.len() > 1 {
+            let numbers: Vec<String> = statements
+                .iter()
+                .map(|statement| statement.account_number.clone())
+                .collect();
+            return Err(WalletError::ValidationError(format!(
+                "The file holds statements of several accounts ({}), which cannot go into one account",
+                numbers.join(", ")
+            )));
+        }
+        let statement = statements.pop().ok_or_else(|| {
+            WalletError::ValidationError("The file holds no statement".to_string())
+        })?;
+        if let Some(currency) = &statement.currency
+            && currency != account.currency.code()
+        {
+            return Err(WalletError::ValidationError(format!(
+                "The statement is in {currency} but {} is in {}",
+                account.name,
+                account.currency.code()
+            )));
+        }
+
+        let mut preview =
+            ofx::statement_drafts(statement, account_id, counter_account_id, &account.currency);
+        self.mark_duplicates(&mut preview.drafts).await?;
+        Ok(preview)
+    }
+
+    /// Preview an OFX or QFX statement file from disk
+    pub async fn preview_ofx_file(
+        &self,
+        account_id: i64,
+        counter_account_id: i64,
+        path: impl AsRef<Path>,
+    ) -> Result<ImportPreview> {
+        let content = import::decode_text(tokio::fs::read(path).await?);
+        self.preview_ofx(account_id, counter_account_id, &content)
+            .await
+    }
+
+    /// Parse a camt.053 statement or camt.052 report of an asset or liability
+    /// account, booking every entry against the counter-account, and check
+    /// its opening and closing balances against the ledger
+    pub async fn preview_camt(
+        &self,
+        account_id: i64,
+        counter_account_id: i64,
+        content: &str,
+    ) -> Result<ImportPreview> {
+        let account = self
+            .statement_account(account_id, counter_account_id)
+            .await?;
+
+        // Banks put consecutive days in one file as separate statements
+        let mut statements = camt::parse_statements(content)?.into_iter();
+        let mut statement = statements.next().ok_or_else(|| {
+            WalletError::ValidationError("The file holds no statement".to_string())
+        })?;
+        for other in statements {
+            if other.account != statement.account {
+                return Err(WalletError::ValidationError(format!(
+                    "The file holds statements of several accounts ({}, {}), which cannot go into one account",
+                    statement.account, other.account
+                )));
+            }
+            statement.balances.extend(other.balances);
+            statement.entries.extend(other.entries);
+            statement.issues.extend(other.issues);
+        }
+        if let Some(currency) = &statement.currency
+            && currency != account.currency.code()
+        {
+            return Err(WalletError::ValidationError(format!(
+                "The statement is in {currency} but {} is in {}",
+                account.name,
+                account.currency.code()
+            )));
+        }
+
+        let balances = std::mem::take(&mut statement.balances);
+        let mut preview =
+            camt::statement_drafts(statement, account_id, counter_account_id, &account.currency);
+        self.mark_duplicates(&mut preview.drafts).await?;
+        self.check_balances(&account, &balances, &mut preview)
+            .await?;
+        Ok(preview)
+    }
+
+    /// Preview a camt.053 or camt.052 file from disk
+    pub async fn preview_camt_file(
+        &self,
+        account_id: i64,
+        counter_account_id: i64,
+        path: impl AsRef<Path>,
+    ) -> Result<ImportPreview> {
+        let content = import::decode_text(tokio::fs::read(path).await?);
+        self.preview_camt(account_id, counter_account_id, &content)
+            .await
+    }
+
+    /// Create a transaction for each reviewed draft, between its account and
+    /// counter-account. Drafts imported before are skipped, so committing a
+    /// statement again, also after a failure part way through, books no line
+    /// twice.
+    pub async fn commit(&self, drafts: Vec<DraftTransaction>) -> Result<ImportSummary> {
+        let external_ids: Vec<String> = drafts
+            .iter()
+            .map(|draft| draft.external_id.clone())
+            .collect();
+        let mut existing = self
+            .transactions
+            .get_transaction_ids_by_external_ids(&external_ids)
+            .await?;
+
+        let mut summary = ImportSummary::default();
+        for draft in drafts {
+            if existing.contains_key(&draft.external_id) {
+                summary.duplicates += 1;
+                continue;
+            }
+
+            // Money coming in is a debit to the statement account
+            let (amount, account_side, counter_side) = if draft.amount.is_negative() {
+                (
+                    draft.amount.checked_neg()?,
+                    EntryType::Credit,
+                    EntryType::Debit,
+                )
+            } else {
+                (draft.amount, EntryType::Debit, EntryType::Credit)
+            };
+            let entries = vec![
+                TransactionEntryInput {
+                    account_id: draft.account_id,
+                    amount: amount.clone(),
+                    entry_type: account_side,
+                    description: None,
+                    price: None,
+                },
+                TransactionEntryInput {
+                    account_id: draft.counter_account_id,
+                    amount,
+                    entry_type: counter_side,
+                    description: None,
+                    price: None,
+                },
+            ];
+
+            let mut new_transaction =
+                NewTransaction::new(draft.description, draft.transaction_date, entries);
+            new_transaction.reference = draft.reference;
+            new_transaction.notes = draft.notes;
+            new_transaction.external_id = Some(draft.external_id.clone());
+            let created = self
+                .transaction_service
+                .create_transaction(new_transaction)
+                .await?;
+            let id = created.id.unwrap_or_default();
+            existing.insert(draft.external_id, id);
+            summary.transaction_ids.push(id);
+        }
+
+        Ok(summary)
+    }
+
+    /// Account a statement is imported into, checking it and the counter-account
+    async fn statement_account(&self, account_id: i64, counter_account_id: i64) -> Result<Account> {
+        let account = self.account_service.get_account(account_id).await?;
+        if !matches!(
+            account.account_type,
+            AccountType::Asset | AccountType::Liability
+        ) {
+            return Err(WalletError::ValidationError(format!(
+                "Statements can only be imported into asset or liability accounts, not {}",
+                account.name
+            )));
+        }
+        if account_id == counter_account_id {
+            return Err(WalletError::ValidationError(
+                "Counter-account must differ from the statement account".to_string(),
+            ));
+        }
+        self.account_service.get_account(counter_account_id).await?;
+        Ok(account)
+    }
+
+    /// Compare the opening and closing booked balances of a statement with the
+    /// ledger balance at the same moment, as it will be once the drafts not
+    /// imported yet are committed. Balances that cannot be checked are
+    /// reported as issues.
+    async fn check_balances(
+        &self,
+        account: &Account,
+        balances: &[CamtBalance],
+        preview: &mut ImportPreview,
+    ) -> Result<()> {
+        let account_id = account.id.unwrap_or_default();
+        for balance in balances {
+            // Opening balances hold at the start of the day, closing ones and
+            // the previous closing balance opening a statement at its end
+            let until = match balance.code.as_str() {
+                "OPBD" => balance.date,
+                "CLBD" | "PRCD" => balance.date.succ_opt().unwrap_or(balance.date),
+                other => {
+                    preview.issues.push(format!(
+                        "Balance {other} of {}: only booked opening and closing balances are checked",
+                        balance.date
+                    ));
+                    continue;
+                }
+            };
+            if let Some(currency) = &balance.currency
+                && currency != account.currency.code()
+            {
+                preview.issues.push(format!(
+                    "Balance {} of {}: in {currency} but {} is in {}",
+                    balance.code,
+                    balance.date,
+                    account.name,
+                    account.currency.code()
+                ));
+                continue;
+            }
+
+            let ledger = self
+                .account_service
+                .calculate_account_balance(account_id, Some(until))
+                .await?;
+            // From the account holder's view, like the statement
+            let mut ledger_balance = if account.account_type.is_debit_normal() {
+                ledger
+            } else {
+                ledger.checked_neg()?
+            };
+            for draft in &preview.drafts {
+                if draft.duplicate_of.is_none() && draft.transaction_date < until {
+                    ledger_balance = ledger_balance.checked_add(&draft.amount)?;
+                }
+            }
+
+            let statement_balance = Money::new(balance.amount, account.currency.clone())?;
+            preview.balance_checks.push(StatementBalanceCheck {
+                balance_type: balance.code.clone(),
+                date: balance.date,
+                difference: statement_balance.checked_sub(&ledger_balance)?,
+                statement_balance,
+                ledger_balance,
+            });
+        }
+        Ok(())
+    }
+
+    async fn mark_duplicates(&self, drafts: &mut [DraftTransaction]) -> Result<()> {
+        let external_ids: Vec<String> = drafts
+            .iter()
+            .map(|draft| draft.external_id.clone())
+            .collect();
+        let existing = self
+            .transactions
+            .get_transaction_ids_by_external_ids(&external_ids)
+            .await?;
+        for draft in drafts {
+            draft.duplicate_of = existing.get(&draft.external_id).copied();
+        }
+        Ok(())
+    }
+
+    async fn validate_csv_profile(&self, profile: &CsvImportProfile) -> Result<()> {
+        if profile.name.trim().is_empty() {
+            return Err(WalletError::ValidationError(
+                "Profile name cannot be empty".to_string(),
+            ));
+        }
+        if matches!(profile.delimiter, '"' | '\n' | '\r') {
+            return Err(WalletError::ValidationError(format!(
+                "Invalid delimiter {:?}",
+                profile.delimiter
+            )));
+        }
+        let split_columns = profile.withdrawal_column.is_some() || profile.deposit_column.is_some();
+        if profile.amount_column.is_some() == split_columns {
+            return Err(WalletError::ValidationError(
+                "Map either an amount column or withdrawal and deposit columns".to_string(),
+            ));
+        }
+        if StrftimeItems::new(&profile.date_format).any(|item| item == Item::Error) {
+            return Err(WalletError::ValidationError(format!(
+                "Invalid date format '{}'",
+                profile.date_format
+            )));
+        }
+        if profile.account_id == profile.counter_account_id {
+            return Err(WalletError::ValidationError(
+                "Counter-account must differ from the statement account".to_string(),
+            ));
+        }
+        self.account_service.get_account(profile.account_id).await?;
+        self.account_service
+            .get_account(profile.counter_account_id)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Currency;
+    use crate::models::import::DecimalSeparator;
+    use crate::services::test_support::{ASSETS, EXPENSES, LIABILITIES, create_account};
+    use chrono::Utc;
+
+    async fn setup_profile(db: &Arc<Database>) -> CsvImportProfile {
+        let bank = create_account(db, "Bank", AccountType::Asset, ASSETS).await;
+        let suspense = create_account(db, "Uncategorized", AccountType::Expense, EXPENSES).await;
+
+        CsvImportProfile {
+            id: None,
+            name: "Girokonto".to_string(),
+            account_id: bank,
+            counter_account_id: suspense,
+            delimiter: ';',
+            skip_rows: 1,
+            date_column: 0,
+            date_format: "%d.%m.%Y".to_string(),
+            description_column: 1,
+            reference_column: Some(3),
+            amount_column: Some(2),
+            withdrawal_column: None,
+            deposit_column: None,
+            negate_amounts: false,
+            decimal_separator: DecimalSeparator::Comma,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_csv_profiles(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let import_service = ImportService::new(db.clone());
+        let profile = setup_profile(&db).await;
+
+        let created = import_service
+            .create_csv_profile(profile.clone())
+            .await
+            .unwrap();
+        assert_eq!(created.delimiter, ';');
+        assert_eq!(created.decimal_separator, DecimalSeparator::Comma);
+        let renamed = import_service
+            .update_csv_profile(CsvImportProfile {
+                name: "Checking".to_string(),
+                ..created.clone()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            import_service.get_csv_profiles().await.unwrap(),
+            vec![renamed]
+        );
+
+        let invalid = [
+            CsvImportProfile {
+                withdrawal_column: Some(4),
+                ..profile.clone()
+            },
+            CsvImportProfile {
+                date_format: "%d.%Q".to_string(),
+                ..profile.clone()
+            },
+            CsvImportProfile {
+                counter_account_id: profile.account_id,
+                ..profile.clone()
+            },
+        ];
+        for profile in invalid {
+            assert!(import_service.create_csv_profile(profile).await.is_err());
+        }
+
+        import_service
+            .delete_csv_profile(created.id.unwrap())
+            .await
+            .unwrap();
+        assert!(import_service.get_csv_profiles().await.unwrap().is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_csv_import(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let import_service = ImportService::new(db.clone());
+        let account_service = AccountService::new(db.clone());
+        let profile = import_service
+            .create_csv_profile(setup_profile(&db).await)
+            .await
+            .unwrap();
+        let content = "Datum;Text;Betrag;Referenz\n\
+            01.07.2025;Bakery;-3,50;\n\
+            02.07.2025;\"Salary\nJuly\";2.500,00;SAL-7\n\
+            03.07.2025;Broken;abc;\n";
+
+        let preview = import_service
+            .preview_csv(profile.id.unwrap(), content)
+            .await
+            .unwrap();
+        assert_eq!(preview.drafts.len(), 2);
+        assert_eq!(preview.drafts[1].description, "Salary July");
+        assert_eq!(preview.drafts[1].reference.as_deref(), Some("SAL-7"));
+        assert_eq!(preview.issues, vec!["Line 5: invalid amount 'abc'"]);
+        assert!(preview.drafts.iter().all(|d| d.duplicate_of.is_none()));
+
+        let summary = import_service.commit(preview.drafts).await.unwrap();
+        assert_eq!(summary.transaction_ids.len(), 2);
+        assert_eq!(summary.duplicates, 0);
+        let balance = account_service
+            .calculate_account_balance(profile.account_id, None)
+            .await
+            .unwrap();
+        assert_eq!(balance.amount_minor(), 249650);
+
+        // Importing the statement again finds every line already booked
+        let preview = import_service
+            .preview_csv(profile.id.unwrap(), content)
+            .await
+            .unwrap();
+        assert_eq!(
+            preview
+                .drafts
+                .iter()
+                .map(|draft| draft.duplicate_of)
+                .collect::<Vec<_>>(),
+            summary
+                .transaction_ids
+                .iter()
+                .copied()
+                .map(Some)
+                .collect::<Vec<_>>()
+        );
+        let again = import_service.commit(preview.drafts).await.unwrap();
+        assert!(again.transaction_ids.is_empty());
+        assert_eq!(again.duplicates, 2);
+    }
+
+    #[sqlx::test]
+    async fn test_ofx_import(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let import_service = ImportService::new(db.clone());
+        let account_service = AccountService::new(db.clone());
+        let card = create_account(&db, "Credit card", AccountType::Liability, LIABILITIES).await;
+        let profile = setup_profile(&db).await;
+        let expenses = profile.counter_account_id;
+        let content = "OFXHEADER:100\nDATA:OFXSGML\n\n<OFX><CREDITCARDMSGSRSV1><CCSTMTTRNRS><CCSTMTRS>\n\
+            <CURDEF>EUR<CCACCTFROM><ACCTID>9876</CCACCTFROM><BANKTRANLIST>\n\
+            <STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20250702<TRNAMT>-30.00<FITID>F1<NAME>Books</STMTTRN>\n\
+            <STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20250703<TRNAMT>-20.00<FITID>F2<NAME>Lunch</STMTTRN>\n\
+            </BANKTRANLIST></CCSTMTRS></CCSTMTTRNRS></CREDITCARDMSGSRSV1></OFX>\n";
+
+        let preview = import_service
+            .preview_ofx(card, expenses, content)
+            .await
+            .unwrap();
+        assert_eq!(preview.drafts.len(), 2);
+        assert_eq!(preview.drafts[0].external_id, "ofx:9876:F1");
+        let summary = import_service.commit(preview.drafts).await.unwrap();
+        assert_eq!(summary.transaction_ids.len(), 2);
+        // Purchases on the card increase what is owed
+        let owed = account_service
+            .calculate_account_balance(card, None)
+            .await
+            .unwrap();
+        assert_eq!(owed.amount_minor(), 5000);
+
+        // The same download again is recognised by the FITIDs
+        let preview = import_service
+            .preview_ofx(card, expenses, content)
+            .await
+            .unwrap();
+        assert!(preview.drafts.iter().all(|d| d.duplicate_of.is_some()));
+        assert_eq!(
+            import_service
+                .commit(preview.drafts)
+                .await
+                .unwrap()
+                .duplicates,
+            2
+        );
+
+        // Only into asset or liability accounts, and in their currency
+        assert!(
+            import_service
+                .preview_ofx(expenses, card, content)
+                .await
+                .is_err()
+        );
+        let dollars = content.replace("<CURDEF>EUR", "<CURDEF>USD");
+        assert!(
+            import_service
+                .preview_ofx(card, expenses, &dollars)
+                .await
+                .is_err()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_camt_import(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let import_service = ImportService::new(db.clone());
+        let profile = setup_profile(&db).await;
+        let (bank, expenses) = (profile.account_id, profile.counter_account_id);
+        let date = |day| chrono::NaiveDate::from_ymd_opt(2025, 7, day).unwrap();
+
+        // Balance brought forward from before the statement
+        let opening = DraftTransaction {
+            transaction_date: date(1) - chrono::Days::new(1),
+            description: "Opening balance".to_string(),
+            reference: None,
+            notes: None,
+            account_id: bank,
+            counter_account_id: expenses,
+            amount: Money::new(rust_decimal::Decimal::new(50000, 2), Currency::eur()).unwrap(),
+            external_id: "opening".to_string(),
+            duplicate_of: None,
+        };
+        import_service.commit(vec![opening]).await.unwrap();
+
+        let balance = |code: &str, day: u32, amount: &str| {
+            format!(
+                "<Bal><Tp><CdOrPrtry><Cd>{code}</Cd></CdOrPrtry></Tp>\
+                <Amt Ccy=\"EUR\">{amount}</Amt><CdtDbtInd>CRDT</CdtDbtInd>\
+                <Dt><Dt>2025-07-0{day}</Dt></Dt></Bal>"
+            )
+        };
+        let entry = |reference: &str, day: u32, amount: &str, indicator: &str| {
+            format!(
+                "<Ntry><Amt Ccy=\"EUR\">{amount}</Amt><CdtDbtInd>{indicator}</CdtDbtInd>\
+                <Sts>BOOK</Sts><BookgDt><Dt>2025-07-0{day}</Dt></BookgDt>\
+                <AcctSvcrRef>{reference}</AcctSvcrRef></Ntry>"
+            )
+        };
+        let statement = |closing: &str| {
+            format!(
+                "<Document><BkToCstmrStmt><Stmt>\
+                <Acct><Id><IBAN>DE89370400440532013000</IBAN></Id><Ccy>EUR</Ccy></Acct>{}{}{}{}\
+                </Stmt></BkToCstmrStmt></Document>",
+                balance("OPBD", 1, "500.00"),
+                balance("CLBD", 2, closing),
+                entry("R1", 1, "1000.00", "CRDT"),
+                entry("R2", 2, "250.00", "DBIT"),
+            )
+        };
+        let content = statement("1250.00");
+
+        let preview = import_service
+            .preview_camt(bank, expenses, &content)
+            .await
+            .unwrap();
+        assert_eq!(preview.drafts.len(), 2);
+        let differences: Vec<(&str, i64, i64)> = preview
+            .balance_checks
+            .iter()
+            .map(|check| {
+                (
+                    check.balance_type.as_str(),
+                    check.ledger_balance.amount_minor(),
+                    check.difference.amount_minor(),
+                )
+            })
+            .collect();
+        assert_eq!(differences, vec![("OPBD", 50000, 0), ("CLBD", 125000, 0)]);
+        let summary = import_service.commit(preview.drafts).await.unwrap();
+        assert_eq!(summary.transaction_ids.len(), 2);
+
+        // Once imported, the entries count through the ledger instead
+        let preview = import_service
+            .preview_camt(bank, expenses, &content)
+            .await
+            .unwrap();
+        assert!(preview.drafts.iter().all(|d| d.duplicate_of.is_some()));
+        assert!(
+            preview
+                .balance_checks
+                .iter()
+                .all(|check| check.difference.is_zero())
+        );
+
+        // A closing balance the entries do not lead to is reported
+        let preview = import_service
+            .preview_camt(bank, expenses, &statement("1260.00"))
+            .await
+            .unwrap();
+        assert_eq!(preview.balance_checks[1].difference.amount_minor(), 1000);
+
+        // The previous closing balance opens a statement at the end of its
+        // day, interim balances are not checked and neither are balances in
+        // another currency
+        let content = content
+            .replace(
+                &balance("OPBD", 1, "500.00"),
+                &balance("PRCD", 1, "500.00").replace("2025-07-01", "2025-06-30"),
+            )
+            .replace(
+                &balance("CLBD", 2, "1250.00"),
+                &format!(
+                    "{}{}",
+                    balance("ITBD", 2, "1250.00"),
+                    balance("CLBD", 2, "1250.00").replace("EUR", "USD")
+                ),
+            );
+        let preview = import_service
+            .preview_camt(bank, expenses, &content)
+            .await
+            .unwrap();
+        assert_eq!(preview.balance_checks.len(), 1);
+        assert_eq!(preview.balance_checks[0].balance_type, "PRCD");
+        assert_eq!(
+            preview.balance_checks[0].ledger_balance.amount_minor(),
+            50000
+        );
+        assert!(preview.balance_checks[0].difference.is_zero());
+        assert_eq!(
+            preview.issues,
+            vec![
+                "Balance ITBD of 2025-07-02: only booked opening and closing balances are checked",
+                "Balance CLBD of 2025-07-02: in USD but Bank is in EUR",
+            ]
+        );
+
+        let dollars = content.replace("<Ccy>EUR</Ccy>", "<Ccy>USD</Ccy>");
+        assert!(
+            import_service
+                .preview_camt(bank, expenses, &dollars)
+                .await
+                .is_err()
+        );
+    }
+}
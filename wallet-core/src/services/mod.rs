@@ -1,7 +1,28 @@
 pub mod account_service;
+pub mod audit_service;
+pub mod balance_assertion_service;
+pub mod currency_service;
+pub mod import_service;
+pub mod period_service;
+pub mod price_service;
+pub mod reconciliation_service;
 pub mod report_service;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod transaction_service;
+pub mod undo_service;
 
 pub use account_service::AccountService;
+pub use audit_service::AuditService;
+pub use balance_assertion_service::BalanceAssertionService;
+pub use currency_service::CurrencyService;
+pub use import_service::ImportService;
+pub use period_service::PeriodService;
+pub use price_service::PriceService;
+pub use reconciliation_service::ReconciliationService;
 pub use report_service::ReportService;
-pub use transaction_service::{TransactionEntryInput, TransactionFilters, TransactionService};
+pub use transaction_service::{
+    AccountMatch, NewTransaction, TransactionEntryInput, TransactionFilters, TransactionService,
+    TransactionSort,
+};
+pub use undo_service::UndoService;
@@ -0,0 +1,557 @@
+use chrono::{NaiveDate, Utc};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use crate::db::accounts::AccountRepository;
+use crate::db::connection::Database;
+use crate::db::periods::PeriodRepository;
+use crate::errors::{Result, WalletError};
+use crate::models::period::{ClosedPeriod, FiscalYearClose};
+use crate::{
+    Account, AccountService, AccountType, EntryPrice, EntryType, Money, NewTransaction,
+    PriceService, TransactionEntryInput, TransactionService,
+};
+
+/// Name of the Equity account receiving the result of closed fiscal years
+pub const RETAINED_EARNINGS: &str = "Retained earnings";
+
+/// Closing of accounting periods and fiscal years
+pub struct PeriodService {
+    repository: PeriodRepository,
+    account_service: AccountService,
+    account_repository: AccountRepository,
+    price_service: PriceService,
+}
+
+impl PeriodService {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            repository: PeriodRepository::new(db.clone()),
+            account_service: AccountService::new(db.clone()),
+            account_repository: AccountRepository::new(db.clone()),
+            price_service: PriceService::new(db),
+        }
+    }
+
+    /// Transactions dated on or before this date cannot be created, edited or deleted
+    pub async fn get_lock_date(&self) -> Result<Option<NaiveDate>> {
+        Ok(self
+            .repository
+            .get_latest()
+            .await?
+            .map(|period| period.lock_date))
+    }
+
+    /// Close the books up to and including `lock_date`
+    pub async fn close_period(&self, lock_date: NaiveDate) -> Result<ClosedPeriod> {
+        self.ensure_after_lock_date(lock_date).await?;
+        self.repository.create(lock_date).await
+    }
+
+    /// Reopen the latest closed period, removing its closing entries if it was
+    /// closed as a fiscal year. Returns the lock date now in force.
+    pub async fn reopen_period(&self) -> Result<Option<NaiveDate>> {
+        let latest = self
+            .repository
+            .get_latest()
+            .await?
+            .ok_or_else(|| WalletError::ValidationError("No period is closed".to_string()))?;
+        self.repository.delete(&latest).await?;
+        self.get_lock_date().await
+    }
+
+    /// Post closing entries moving every Income and Expense balance at the end of
+    /// the fiscal year into the "Retained earnings" Equity account, then close
+    /// the period up to the end of the year. Balances in other currencies than
+    /// the retained earnings account are converted at the rate known at the end
+    /// of the year; the close is refused when a rate is missing.
+    pub async fn close_fiscal_year(&self, fiscal_year_end: NaiveDate) -> Result<FiscalYearClose> {
+        self.ensure_after_lock_date(fiscal_year_end).await?;
+
+        let before_date = fiscal_year_end
+            .succ_opt()
+            .ok_or_else(|| WalletError::ValidationError("Invalid date".to_string()))?;
+
+        // Net debit balance of each Income and Expense account
+        let mut balances = Vec::new();
+        for account_type in [AccountType::Income, AccountType::Expense] {
+            let sums = self
+                .account_repository
+                .get_account_sums_by_type(&account_type, Some(before_date))
+                .await?;
+            for sums in sums {
+                let net_debit =
+                    Money::from_minor_units(sums.total_debits, sums.currency.clone()).checked_sub(
+                        &Money::from_minor_units(sums.total_credits, sums.currency.clone()),
+                    )?;
+                if !net_debit.is_zero() {
+                    balances.push((sums.account_id, net_debit));
+                }
+            }
+        }
+        if balances.is_empty() {
+            return Err(WalletError::ValidationError(format!(
+                "No income or expense balance to close at {fiscal_year_end}"
+            )));
+        }
+
+        let retained_earnings = self.retained_earnings_account().await?;
+        let currency = retained_earnings.currency.clone();
+
+        // Zero out each account, converting foreign balances into the retained
+        // earnings currency so the closing transaction balances in it
+        let mut entries = Vec::new();
+        let mut net_debit_total = Money::zero(currency.clone());
+        let mut missing_rates = BTreeSet::new();
+        for (account_id, net_debit) in balances {
+            let mut entry = Self::closing_entry(account_id, &net_debit, false)?;
+            if net_debit.currency().code() != currency.code() {
+                let rate = self
+                    .price_service
+                    .get_rate(
+                        net_debit.currency().code(),
+                        currency.code(),
+                        fiscal_year_end,
+                    )
+                    .await?;
+                let Some(rate) = rate else {
+                    missing_rates.insert(net_debit.currency().code().to_string());
+                    continue;
+                };
+                entry.price = Some(EntryPrice::new(&entry.amount, rate, currency.clone())?);
+            }
+            let converted = entry.balance_amount();
+            net_debit_total = if net_debit.is_negative() {
+                net_debit_total.checked_sub(converted)?
+            } else {
+                net_debit_total.checked_add(converted)?
+            };
+            entries.push(entry);
+        }
+        if !missing_rates.is_empty() {
+            return Err(WalletError::ValidationError(format!(
+                "No exchange rate into {} on or before {fiscal_year_end} for {}",
+                currency.code(),
+                missing_rates.into_iter().collect::<Vec<_>>().join(", ")
+            )));
+        }
+
+        let closing_transaction = |retained_earnings_id| {
+            if !net_debit_total.is_zero() {
+                // A net loss (debit balance) reduces retained earnings
+                entries.push(Self::closing_entry(
+                    retained_earnings_id,
+                    &net_debit_total,
+                    true,
+                )?);
+            }
+            TransactionService::validate_transaction_balance(&entries)?;
+            Ok(NewTransaction::new(
+                format!("Closing entries for fiscal year ending {fiscal_year_end}"),
+                fiscal_year_end,
+                entries,
+            ))
+        };
+        self.repository
+            .close_fiscal_year(fiscal_year_end, &retained_earnings, closing_transaction)
+            .await
+    }
+
+    pub async fn get_fiscal_year_closes(&self) -> Result<Vec<FiscalYearClose>> {
+        self.repository.get_fiscal_year_closes().await
+    }
+
+    /// Entry for the absolute value of `amount`: a debit when `amount` is positive
+    /// and `debit_if_positive` is set, or when it is negative and it is not
+    fn closing_entry(
+        account_id: i64,
+        amount: &Money,
+        debit_if_positive: bool,
+    ) -> Result<TransactionEntryInput> {
+        let debit = amount.is_negative() != debit_if_positive;
+        let amount = if amount.is_negative() {
+            amount.checked_neg()?
+        } else {
+            amount.clone()
+        };
+        Ok(TransactionEntryInput {
+            account_id,
+            amount,
+            entry_type: if debit {
+                EntryType::Debit
+            } else {
+                EntryType::Credit
+            },
+            description: None,
+            price: None,
+        })
+    }
+
+    /// The "Retained earnings" account, or the one to create under the Equity
+    /// root account, in its currency, when there is none yet
+    async fn retained_earnings_account(&self) -> Result<Account> {
+        let accounts = self.account_service.get_accounts().await?;
+        if let Some(account) = accounts
+            .iter()
+            .find(|a| a.account_type == AccountType::Equity && a.name == RETAINED_EARNINGS)
+        {
+            return Ok(account.clone());
+        }
+
+        let equity_root = accounts
+            .iter()
+            .find(|a| a.parent_id.is_none() && a.account_type == AccountType::Equity)
+            .ok_or_else(|| {
+                WalletError::ValidationError("Equity root account not found".to_string())
+            })?;
+        Ok(Account {
+            id: None,
+            name: RETAINED_EARNINGS.to_string(),
+            account_type: AccountType::Equity,
+            parent_id: equity_root.id,
+            currency: equity_root.currency.clone(),
+            description: None,
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+    }
+
+    async fn ensure_after_lock_date(&self, date: NaiveDate) -> Result<()> {
+        match self.get_lock_date().await? {
+            Some(lock_date) if date <= lock_date => Err(WalletError::ValidationError(format!(
+                "Period is already closed up to {lock_date}"
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::{ASSETS, EXPENSES, INCOME, create_account, date, eur};
+    use crate::{CurrencyService, UndoService};
+    use rust_decimal::Decimal;
+
+    #[sqlx::test]
+    async fn test_closed_period_refuses_changes(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let groceries = create_account(&db, "Groceries", AccountType::Expense, EXPENSES).await;
+        let transaction_service = TransactionService::new(db.clone());
+        let period_service = PeriodService::new(db);
+
+        let purchase = transaction_service
+            .create_simple_transaction(
+                "Supermarket".to_string(),
+                date(2025, 3, 31),
+                eur(5000),
+                bank,
+                groceries,
+            )
+            .await
+            .unwrap();
+        period_service
+            .close_period(date(2025, 3, 31))
+            .await
+            .unwrap();
+        assert_eq!(
+            period_service.get_lock_date().await.unwrap(),
+            Some(date(2025, 3, 31))
+        );
+
+        // Nothing can change on or before the lock date...
+        let create = transaction_service
+            .create_simple_transaction(
+                "Late receipt".to_string(),
+                date(2025, 3, 15),
+                eur(1000),
+                bank,
+                groceries,
+            )
+            .await;
+        assert!(create.is_err());
+        let id = purchase.id.unwrap();
+        assert!(transaction_service.delete_transaction(id).await.is_err());
+        let reversal = transaction_service.reverse_transaction(id).await.unwrap();
+
+        // ...and transactions cannot be moved into the closed period
+        let april = transaction_service
+            .create_simple_transaction(
+                "Bakery".to_string(),
+                date(2025, 4, 1),
+                eur(300),
+                bank,
+                groceries,
+            )
+            .await
+            .unwrap();
+        let entries = april
+            .entries
+            .iter()
+            .cloned()
+            .map(TransactionEntryInput::from)
+            .collect();
+        assert!(
+            transaction_service
+                .update_transaction(
+                    april.id.unwrap(),
+                    NewTransaction::new("Bakery".to_string(), date(2025, 3, 30), entries),
+                )
+                .await
+                .is_err()
+        );
+
+        // Locks only move forward, and can be lifted again
+        assert!(
+            period_service
+                .close_period(date(2025, 2, 28))
+                .await
+                .is_err()
+        );
+        assert_eq!(period_service.reopen_period().await.unwrap(), None);
+        transaction_service
+            .delete_transaction(reversal.id.unwrap())
+            .await
+            .unwrap();
+        transaction_service.delete_transaction(id).await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_close_fiscal_year(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let salary = create_account(&db, "Salary", AccountType::Income, INCOME).await;
+        let groceries = create_account(&db, "Groceries", AccountType::Expense, EXPENSES).await;
+        let account_service = AccountService::new(db.clone());
+        let transaction_service = TransactionService::new(db.clone());
+        let period_service = PeriodService::new(db.clone());
+
+        transaction_service
+            .create_simple_transaction(
+                "June salary".to_string(),
+                date(2024, 6, 30),
+                eur(300000),
+                salary,
+                bank,
+            )
+            .await
+            .unwrap();
+        transaction_service
+            .create_simple_transaction(
+                "Groceries".to_string(),
+                date(2024, 7, 10),
+                eur(100000),
+                bank,
+                groceries,
+            )
+            .await
+            .unwrap();
+
+        let undo_service = UndoService::new(db.clone());
+        let undo_counts = undo_service.get_undo_redo_counts().await.unwrap();
+        let close = period_service
+            .close_fiscal_year(date(2024, 12, 31))
+            .await
+            .unwrap();
+        assert_eq!(close.fiscal_year_end, date(2024, 12, 31));
+        // The closing transaction is not put on the undo stack
+        assert_eq!(
+            undo_service.get_undo_redo_counts().await.unwrap(),
+            undo_counts
+        );
+
+        // The new year starts with clean P&L accounts
+        let new_year = Some(date(2025, 1, 1));
+        for account_id in [salary, groceries] {
+            let balance = account_service
+                .calculate_account_balance(account_id, new_year)
+                .await
+                .unwrap();
+            assert!(balance.is_zero());
+        }
+        let retained_earnings = account_service
+            .calculate_account_balance(close.retained_earnings_account_id, new_year)
+            .await
+            .unwrap();
+        assert_eq!(retained_earnings.amount_minor(), 200000);
+        let bank_balance = account_service
+            .calculate_account_balance(bank, new_year)
+            .await
+            .unwrap();
+        assert_eq!(bank_balance.amount_minor(), 200000);
+
+        // The year is locked and cannot be closed twice
+        assert_eq!(
+            period_service.get_lock_date().await.unwrap(),
+            Some(date(2024, 12, 31))
+        );
+        assert!(
+            period_service
+                .close_fiscal_year(date(2024, 12, 31))
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            period_service.get_fiscal_year_closes().await.unwrap(),
+            vec![close]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_reopen_fiscal_year_close(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let salary = create_account(&db, "Salary", AccountType::Income, INCOME).await;
+        let account_service = AccountService::new(db.clone());
+        let transaction_service = TransactionService::new(db.clone());
+        let period_service = PeriodService::new(db);
+
+        transaction_service
+            .create_simple_transaction(
+                "June salary".to_string(),
+                date(2024, 6, 30),
+                eur(300000),
+                salary,
+                bank,
+            )
+            .await
+            .unwrap();
+        let first = period_service
+            .close_fiscal_year(date(2024, 12, 31))
+            .await
+            .unwrap();
+
+        // Reopening removes the closing entries along with the lock
+        assert_eq!(period_service.reopen_period().await.unwrap(), None);
+        assert!(
+            period_service
+                .get_fiscal_year_closes()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            transaction_service
+                .get_transaction(first.transaction_id)
+                .await
+                .is_err()
+        );
+        let new_year = Some(date(2025, 1, 1));
+        let balance = account_service
+            .calculate_account_balance(salary, new_year)
+            .await
+            .unwrap();
+        assert_eq!(balance.amount_minor(), 300000);
+
+        // A late salary correction, then the year is closed again
+        transaction_service
+            .create_simple_transaction(
+                "Bonus".to_string(),
+                date(2024, 12, 20),
+                eur(50000),
+                salary,
+                bank,
+            )
+            .await
+            .unwrap();
+        let second = period_service
+            .close_fiscal_year(date(2024, 12, 31))
+            .await
+            .unwrap();
+        assert_eq!(
+            second.retained_earnings_account_id,
+            first.retained_earnings_account_id
+        );
+        let retained_earnings = account_service
+            .calculate_account_balance(second.retained_earnings_account_id, new_year)
+            .await
+            .unwrap();
+        assert_eq!(retained_earnings.amount_minor(), 350000);
+        assert_eq!(
+            period_service.get_fiscal_year_closes().await.unwrap(),
+            vec![second]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_close_fiscal_year_in_several_currencies(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let account_service = AccountService::new(db.clone());
+        let transaction_service = TransactionService::new(db.clone());
+        let price_service = PriceService::new(db.clone());
+        let period_service = PeriodService::new(db.clone());
+        let usd = CurrencyService::new(db.clone())
+            .get_currency("USD")
+            .await
+            .unwrap();
+
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let groceries = create_account(&db, "Groceries", AccountType::Expense, EXPENSES).await;
+        let mut usd_accounts = Vec::new();
+        for (name, account_type, parent_id) in [
+            ("US bank", AccountType::Asset, ASSETS),
+            ("Consulting", AccountType::Income, INCOME),
+        ] {
+            let account = account_service
+                .create_account(name.to_string(), account_type, Some(parent_id), usd.clone())
+                .await
+                .unwrap();
+            usd_accounts.push(account.id.unwrap());
+        }
+        let (us_bank, consulting) = (usd_accounts[0], usd_accounts[1]);
+
+        transaction_service
+            .create_simple_transaction(
+                "Consulting fee".to_string(),
+                date(2024, 5, 15),
+                Money::from_minor_units(100000, usd.clone()),
+                consulting,
+                us_bank,
+            )
+            .await
+            .unwrap();
+        transaction_service
+            .create_simple_transaction(
+                "Groceries".to_string(),
+                date(2024, 7, 10),
+                eur(20000),
+                bank,
+                groceries,
+            )
+            .await
+            .unwrap();
+
+        // Without a USD rate the result cannot be stated in euros
+        let error = period_service
+            .close_fiscal_year(date(2024, 12, 31))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("USD"));
+        assert_eq!(period_service.get_lock_date().await.unwrap(), None);
+
+        price_service
+            .record_price("USD", "EUR", date(2024, 12, 31), Decimal::new(9, 1), "test")
+            .await
+            .unwrap();
+        let close = period_service
+            .close_fiscal_year(date(2024, 12, 31))
+            .await
+            .unwrap();
+
+        // The USD income is closed in USD and lands in euros in retained earnings
+        let new_year = Some(date(2025, 1, 1));
+        let consulting_balance = account_service
+            .calculate_account_balance(consulting, new_year)
+            .await
+            .unwrap();
+        assert!(consulting_balance.is_zero());
+        let retained_earnings = account_service
+            .calculate_account_balance(close.retained_earnings_account_id, new_year)
+            .await
+            .unwrap();
+        assert_eq!(retained_earnings.currency().code(), "EUR");
+        assert_eq!(retained_earnings.amount_minor(), 90000 - 20000);
+    }
+}
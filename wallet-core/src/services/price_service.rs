@@ -0,0 +1,434 @@
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::db::connection::Database;
+use crate::db::currencies::CurrencyRepository;
+use crate::db::prices::PriceRepository;
+use crate::errors::{CurrencyError, Result, WalletError};
+use crate::models::price::{Price, PriceFileFormat, PriceImportSummary};
+use crate::{Currency, Money};
+
+pub struct PriceService {
+    repository: PriceRepository,
+    currencies: CurrencyRepository,
+}
+
+impl PriceService {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            repository: PriceRepository::new(db.clone()),
+            currencies: CurrencyRepository::new(db),
+        }
+    }
+
+    /// Record the value of one unit of `base_currency` in `quote_currency` on a date
+    pub async fn record_price(
+        &self,
+        base_currency: &str,
+        quote_currency: &str,
+        price_date: NaiveDate,
+        rate: Decimal,
+        source: &str,
+    ) -> Result<Price> {
+        let price = new_price(base_currency, quote_currency, price_date, rate, source)?;
+
+        for code in [&price.base_currency, &price.quote_currency] {
+            if self.currencies.get_by_code(code).await?.is_none() {
+                return Err(CurrencyError::UnknownCurrency(code.clone()).into());
+            }
+        }
+
+        self.repository.upsert(&price).await
+    }
+
+    /// Rate to convert one unit of `base_currency` into `quote_currency`, using the
+    /// most recent price dated on or before `date`. Prices recorded for the inverse
+    /// pair are used as well. Returns `None` when no price is known.
+    pub async fn get_rate(
+        &self,
+        base_currency: &str,
+        quote_currency: &str,
+        date: NaiveDate,
+    ) -> Result<Option<Decimal>> {
+        // Codes are stored the way `record_price` normalizes them
+        let base_currency = base_currency.trim().to_uppercase();
+        let quote_currency = quote_currency.trim().to_uppercase();
+        if base_currency == quote_currency {
+            return Ok(Some(Decimal::ONE));
+        }
+
+        let direct = self
+            .repository
+            .get_latest_on_or_before(&base_currency, &quote_currency, date)
+            .await?;
+        let inverse = self
+            .repository
+            .get_latest_on_or_before(&quote_currency, &base_currency, date)
+            .await?;
+
+        // Prefer the most recent price, whichever direction it was recorded in
+        let rate = match (direct, inverse) {
+            (Some(direct), Some(inverse)) if inverse.price_date > direct.price_date => {
+                Decimal::ONE.checked_div(inverse.rate)
+            }
+            (Some(direct), _) => Some(direct.rate),
+            (None, Some(inverse)) => Decimal::ONE.checked_div(inverse.rate),
+            (None, None) => None,
+        };
+        Ok(rate)
+    }
+
+    /// Value an amount in another currency at the rate known on `date`
+    pub async fn convert(
+        &self,
+        amount: &Money,
+        currency: &Currency,
+        date: NaiveDate,
+    ) -> Result<Option<Money>> {
+        let rate = self
+            .get_rate(amount.currency().code(), currency.code(), date)
+            .await?;
+        rate.map(|rate| amount.convert(rate, currency.clone()))
+            .transpose()
+    }
+
+    pub async fn get_price_history(
+        &self,
+        base_currency: &str,
+        quote_currency: &str,
+    ) -> Result<Vec<Price>> {
+        self.repository
+            .get_history(base_currency, quote_currency)
+            .await
+    }
+
+    /// Import a price file from disk
+    pub async fn import_file(
+        &self,
+        path: impl AsRef<Path>,
+        format: PriceFileFormat,
+    ) -> Result<PriceImportSummary> {
+        let content = tokio::fs::read_to_string(path).await?;
+        let prices = match format {
+            PriceFileFormat::EcbCsv => parse_ecb_csv(&content)?,
+            PriceFileFormat::Ledger => parse_price_directives(&content)?,
+        };
+        self.import_prices(prices).await
+    }
+
+    /// Store parsed prices, skipping the ones involving unknown currencies
+    pub async fn import_prices(&self, prices: Vec<Price>) -> Result<PriceImportSummary> {
+        let known: HashSet<String> = self
+            .currencies
+            .get_all()
+            .await?
+            .into_iter()
+            .map(|info| info.currency.code().to_string())
+            .collect();
+
+        let (prices, unknown): (Vec<Price>, Vec<Price>) = prices
+            .into_iter()
+            .partition(|p| known.contains(&p.base_currency) && known.contains(&p.quote_currency));
+
+        self.repository.upsert_many(&prices).await?;
+
+        Ok(PriceImportSummary {
+            imported: prices.len() as u32,
+            skipped: unknown.len() as u32,
+        })
+    }
+}
+
+fn new_price(
+    base_currency: &str,
+    quote_currency: &str,
+    price_date: NaiveDate,
+    rate: Decimal,
+    source: &str,
+) -> Result<Price> {
+    let base_currency = base_currency.trim().to_uppercase();
+    let quote_currency = quote_currency.trim().to_uppercase();
+
+    if base_currency == quote_currency {
+        return Err(WalletError::ValidationError(format!(
+            "Cannot price {base_currency} in itself"
+        )));
+    }
+    if rate <= Decimal::ZERO {
+        return Err(WalletError::ValidationError(
+            "Price rate must be positive".to_string(),
+        ));
+    }
+
+    Ok(Price {
+        id: None,
+        base_currency,
+        quote_currency,
+        price_date,
+        rate,
+        source: source.to_string(),
+        created_at: Utc::now(),
+    })
+}
+
+fn parse_decimal(value: &str, line_number: usize) -> Result<Decimal> {
+    Decimal::from_str(value).map_err(|_| {
+        WalletError::ValidationError(format!("Line {line_number}: invalid rate '{value}'"))
+    })
+}
+
+fn parse_date(value: &str, line_number: usize) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y/%m/%d"))
+        .map_err(|_| {
+            WalletError::ValidationError(format!("Line {line_number}: invalid date '{value}'"))
+        })
+}
+
+/// Parse the ECB historical reference rates CSV: a `Date` column followed by
+/// one column per currency, each giving the value of one euro. Missing rates
+/// are written as `N/A`.
+pub fn parse_ecb_csv(content: &str) -> Result<Vec<Price>> {
+    let mut lines = content.lines().enumerate();
+
+    let header: Vec<&str> = match lines.next() {
+        Some((_, header)) => header.split(',').map(str::trim).collect(),
+        None => return Ok(Vec::new()),
+    };
+    if header.first() != Some(&"Date") {
+        return Err(WalletError::ValidationError(
+            "Not an ECB rates file: the first column must be 'Date'".to_string(),
+        ));
+    }
+
+    let mut prices = Vec::new();
+    for (index, line) in lines {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split(',').map(str::trim);
+        let date = parse_date(fields.next().unwrap_or_default(), line_number)?;
+
+        for (currency, value) in header.iter().skip(1).zip(fields) {
+            if currency.is_empty() || value.is_empty() || value == "N/A" {
+                continue;
+            }
+            let rate = parse_decimal(value, line_number)?;
+            prices.push(new_price("EUR", currency, date, rate, "ecb")?);
+        }
+    }
+
+    Ok(prices)
+}
+
+/// Parse ledger price directives (`P 2024-01-05 USD 0.9157 EUR`). An optional
+/// time after the date is ignored, and the amount can also be written with
+/// the currency first (`P 2024/01/05 AAPL EUR 150.00`). Beancount `price`
+/// directives (`2024-01-05 price USD 0.9157 EUR`) are accepted too. Other
+/// lines are ignored.
+pub fn parse_price_directives(content: &str) -> Result<Vec<Price>> {
+    let mut prices = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        let (date, rest) = match tokens.as_slice() {
+            ["P", date, rest @ ..] => (*date, rest),
+            [date, "price", rest @ ..] => (*date, rest),
+            _ => continue,
+        };
+        let date = parse_date(date, line_number)?;
+
+        // Skip the optional time of day
+        let rest = match rest {
+            [time, rest @ ..] if time.contains(':') => rest,
+            rest => rest,
+        };
+
+        let (commodity, rate, currency) = match rest {
+            [commodity, first, second, ..] => {
+                if let Ok(rate) = Decimal::from_str(first) {
+                    (*commodity, rate, *second)
+                } else {
+                    (*commodity, parse_decimal(second, line_number)?, *first)
+                }
+            }
+            _ => {
+                return Err(WalletError::ValidationError(format!(
+                    "Line {line_number}: incomplete price directive"
+                )));
+            }
+        };
+
+        prices.push(new_price(commodity, currency, date, rate, "ledger")?);
+    }
+
+    Ok(prices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::date;
+
+    #[test]
+    fn test_parse_ecb_csv() {
+        let content = "Date,USD,JPY,CYP,\n\
+                       2024-01-05,1.0921,158.48,N/A,\n\
+                       2024-01-04,1.0953,157.95,N/A,\n";
+
+        let prices = parse_ecb_csv(content).unwrap();
+        assert_eq!(prices.len(), 4);
+        assert_eq!(prices[0].base_currency, "EUR");
+        assert_eq!(prices[0].quote_currency, "USD");
+        assert_eq!(prices[0].price_date, date(2024, 1, 5));
+        assert_eq!(prices[0].rate, Decimal::new(10921, 4));
+        assert_eq!(prices[3].quote_currency, "JPY");
+        assert_eq!(prices[3].source, "ecb");
+    }
+
+    #[test]
+    fn test_parse_price_directives() {
+        let content = "; exchange rates\n\
+                       P 2024-01-05 USD 0.9157 EUR\n\
+                       P 2024/01/06 12:00:00 AAPL EUR 150.25\n\
+                       2024-01-07 price BTC 40000 EUR\n\
+                       2024-01-07 open Assets:Bank\n";
+
+        let prices = parse_price_directives(content).unwrap();
+        assert_eq!(prices.len(), 3);
+        assert_eq!(prices[0].base_currency, "USD");
+        assert_eq!(prices[0].quote_currency, "EUR");
+        assert_eq!(prices[0].rate, Decimal::new(9157, 4));
+        assert_eq!(prices[1].base_currency, "AAPL");
+        assert_eq!(prices[1].price_date, date(2024, 1, 6));
+        assert_eq!(prices[1].rate, Decimal::new(15025, 2));
+        assert_eq!(prices[2].base_currency, "BTC");
+
+        assert!(parse_price_directives("P 2024-01-05 USD\n").is_err());
+        assert!(parse_price_directives("P 2024-13-05 USD 1 EUR\n").is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_get_rate_on_or_before(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let service = PriceService::new(db);
+
+        service
+            .record_price(
+                "EUR",
+                "USD",
+                date(2024, 1, 4),
+                Decimal::new(10953, 4),
+                "ecb",
+            )
+            .await
+            .unwrap();
+        service
+            .record_price(
+                "EUR",
+                "USD",
+                date(2024, 1, 5),
+                Decimal::new(10921, 4),
+                "ecb",
+            )
+            .await
+            .unwrap();
+
+        // Exact date, later date, and before any known price
+        let rate = service.get_rate("EUR", "USD", date(2024, 1, 5)).await;
+        assert_eq!(rate.unwrap(), Some(Decimal::new(10921, 4)));
+        let rate = service.get_rate("EUR", "USD", date(2024, 2, 1)).await;
+        assert_eq!(rate.unwrap(), Some(Decimal::new(10921, 4)));
+        let rate = service.get_rate("EUR", "USD", date(2024, 1, 3)).await;
+        assert_eq!(rate.unwrap(), None);
+
+        // Inverse pair
+        let rate = service.get_rate("USD", "EUR", date(2024, 1, 4)).await;
+        assert_eq!(rate.unwrap(), Some(Decimal::ONE / Decimal::new(10953, 4)));
+
+        // Codes are matched like `record_price` stores them
+        let rate = service.get_rate(" eur", "usd ", date(2024, 1, 5)).await;
+        assert_eq!(rate.unwrap(), Some(Decimal::new(10921, 4)));
+        let rate = service.get_rate("eur", "EUR", date(2024, 1, 5)).await;
+        assert_eq!(rate.unwrap(), Some(Decimal::ONE));
+
+        // Conversion into the quote currency
+        let usd = Currency::new("USD", 2, "$").unwrap();
+        let converted = service
+            .convert(
+                &Money::eur(Decimal::new(100, 0)).unwrap(),
+                &usd,
+                date(2024, 1, 5),
+            )
+            .await
+            .unwrap();
+        assert_eq!(converted.map(|m| m.amount_minor()), Some(10921));
+    }
+
+    #[sqlx::test]
+    async fn test_record_price_returns_recorded_row(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let service = PriceService::new(db);
+
+        let manual = service
+            .record_price(
+                "EUR",
+                "USD",
+                date(2024, 1, 5),
+                Decimal::new(11, 1),
+                "manual",
+            )
+            .await
+            .unwrap();
+        // Another source on the same date, recorded later, does not take its place
+        service
+            .record_price(
+                "EUR",
+                "USD",
+                date(2024, 1, 5),
+                Decimal::new(10921, 4),
+                "ecb",
+            )
+            .await
+            .unwrap();
+        let updated = service
+            .record_price(
+                "EUR",
+                "USD",
+                date(2024, 1, 5),
+                Decimal::new(12, 1),
+                "manual",
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.id, manual.id);
+        assert_eq!(updated.source, "manual");
+        assert_eq!(updated.rate, Decimal::new(12, 1));
+    }
+
+    #[sqlx::test]
+    async fn test_import_skips_unknown_currencies(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let service = PriceService::new(db);
+
+        let prices = parse_ecb_csv("Date,USD,CYP,\n2007-12-31,1.4721,0.585274,\n").unwrap();
+        let summary = service.import_prices(prices).await.unwrap();
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.skipped, 1);
+
+        // Importing again updates rather than duplicates
+        let prices = parse_ecb_csv("Date,USD,\n2007-12-31,1.4722,\n").unwrap();
+        service.import_prices(prices).await.unwrap();
+        let history = service.get_price_history("EUR", "USD").await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].rate, Decimal::new(14722, 4));
+    }
+}
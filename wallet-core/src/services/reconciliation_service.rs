@@ -0,0 +1,460 @@
+use chrono::NaiveDate;
+use std::sync::Arc;
+
+use crate::db::accounts::AccountRepository;
+use crate::db::connection::Database;
+use crate::db::reconciliations::ReconciliationRepository;
+use crate::errors::{Result, WalletError};
+use crate::models::reconciliation::{Reconciliation, ReconciliationSummary};
+use crate::{Account, EntryStatus, Money};
+
+/// Reconciliation of accounts against bank statements: entries are marked
+/// cleared as they are found on the statement, then locked once the cleared
+/// balance matches the statement closing balance
+pub struct ReconciliationService {
+    repository: ReconciliationRepository,
+    account_repository: AccountRepository,
+}
+
+impl ReconciliationService {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            repository: ReconciliationRepository::new(db.clone()),
+            account_repository: AccountRepository::new(db),
+        }
+    }
+
+    /// Mark an entry as cleared or back to pending. Reconciled entries cannot
+    /// change and only finishing a reconciliation reconciles entries.
+    pub async fn set_entry_status(&self, entry_id: i64, status: EntryStatus) -> Result<()> {
+        if status == EntryStatus::Reconciled {
+            return Err(WalletError::ValidationError(
+                "Entries are reconciled by finishing a reconciliation".to_string(),
+            ));
+        }
+
+        self.repository.set_entry_status(entry_id, status).await
+    }
+
+    /// Compare the cleared balance of an account with a statement closing balance
+    pub async fn get_summary(
+        &self,
+        account_id: i64,
+        statement_date: NaiveDate,
+        statement_balance: Money,
+    ) -> Result<ReconciliationSummary> {
+        let account = self.account_repository.get_by_id(account_id).await?;
+        Self::check_statement_currency(&account, &statement_balance)?;
+
+        let entries = self
+            .repository
+            .get_entries(
+                account_id,
+                &account.currency,
+                account.account_type.is_debit_normal(),
+                statement_date,
+            )
+            .await?;
+
+        let mut cleared_balance = Money::zero(account.currency.clone());
+        let mut uncleared_entries = Vec::new();
+        for entry in entries {
+            match entry.status {
+                EntryStatus::Pending => uncleared_entries.push(entry),
+                EntryStatus::Cleared | EntryStatus::Reconciled => {
+                    cleared_balance = cleared_balance.checked_add(&entry.amount)?;
+                }
+            }
+        }
+        let difference = statement_balance.checked_sub(&cleared_balance)?;
+
+        Ok(ReconciliationSummary {
+            account_id,
+            statement_date,
+            statement_balance,
+            cleared_balance,
+            difference,
+            uncleared_entries,
+        })
+    }
+
+    /// Lock the cleared entries up to the statement date, provided the cleared
+    /// balance matches the statement closing balance
+    pub async fn finish_reconciliation(
+        &self,
+        account_id: i64,
+        statement_date: NaiveDate,
+        statement_balance: Money,
+    ) -> Result<Reconciliation> {
+        let account = self.account_repository.get_by_id(account_id).await?;
+        Self::check_statement_currency(&account, &statement_balance)?;
+
+        self.repository
+            .create(
+                account_id,
+                account.account_type.is_debit_normal(),
+                statement_date,
+                &statement_balance,
+            )
+            .await
+    }
+
+    fn check_statement_currency(account: &Account, statement_balance: &Money) -> Result<()> {
+        if statement_balance.currency().code() != account.currency.code() {
+            return Err(WalletError::ValidationError(format!(
+                "Statement balance must be in the account currency {}",
+                account.currency.code()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Past reconciliations of an account, latest statement first
+    pub async fn get_reconciliations(&self, account_id: i64) -> Result<Vec<Reconciliation>> {
+        self.repository.get_by_account(account_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::{ASSETS, EQUITY, EXPENSES, create_account, date, eur};
+    use crate::services::transaction_service::{NewTransaction, TransactionEntryInput};
+    use crate::{
+        AccountService, AccountType, AuditEntityType, AuditService, Transaction,
+        TransactionService, UndoService,
+    };
+
+    #[sqlx::test]
+    async fn test_reconcile_account(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let transaction_service = TransactionService::new(db.clone());
+        let reconciliation_service = ReconciliationService::new(db.clone());
+
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let opening = create_account(&db, "Opening Balances", AccountType::Equity, EQUITY).await;
+        let groceries = create_account(&db, "Groceries", AccountType::Expense, EXPENSES).await;
+
+        let mut transactions = Vec::new();
+        for (description, day, amount, from, to) in [
+            ("Opening balance", 1, 100000, opening, bank),
+            ("Supermarket", 3, 5000, bank, groceries),
+            ("Bakery", 5, 3000, bank, groceries),
+        ] {
+            let transaction = transaction_service
+                .create_simple_transaction(
+                    description.to_string(),
+                    date(2025, 7, day),
+                    eur(amount),
+                    from,
+                    to,
+                )
+                .await
+                .unwrap();
+            transactions.push(transaction);
+        }
+        let bank_entry = |index: usize| {
+            transactions[index]
+                .entries
+                .iter()
+                .find(|entry| entry.account_id == bank)
+                .unwrap()
+                .id
+                .unwrap()
+        };
+
+        // Nothing cleared yet: the whole statement balance is unexplained
+        let summary = reconciliation_service
+            .get_summary(bank, date(2025, 7, 31), eur(95000))
+            .await
+            .unwrap();
+        assert_eq!(summary.cleared_balance.amount_minor(), 0);
+        assert_eq!(summary.difference.amount_minor(), 95000);
+        assert_eq!(summary.uncleared_entries.len(), 3);
+
+        // The bakery purchase is not on the statement
+        reconciliation_service
+            .set_entry_status(bank_entry(0), EntryStatus::Cleared)
+            .await
+            .unwrap();
+        reconciliation_service
+            .set_entry_status(bank_entry(1), EntryStatus::Cleared)
+            .await
+            .unwrap();
+        let summary = reconciliation_service
+            .get_summary(bank, date(2025, 7, 31), eur(95000))
+            .await
+            .unwrap();
+        assert_eq!(summary.cleared_balance.amount_minor(), 95000);
+        assert!(summary.difference.is_zero());
+        assert_eq!(summary.uncleared_entries.len(), 1);
+        assert_eq!(summary.uncleared_entries[0].description, "Bakery");
+        assert_eq!(summary.uncleared_entries[0].amount.amount_minor(), -3000);
+
+        // A statement that does not match cannot be finished
+        assert!(
+            reconciliation_service
+                .finish_reconciliation(bank, date(2025, 7, 31), eur(90000))
+                .await
+                .is_err()
+        );
+
+        let reconciliation = reconciliation_service
+            .finish_reconciliation(bank, date(2025, 7, 31), eur(95000))
+            .await
+            .unwrap();
+        assert_eq!(reconciliation.statement_balance.amount_minor(), 95000);
+        assert_eq!(
+            reconciliation_service
+                .get_reconciliations(bank)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Clearing and locking entries are audited edits of their transaction
+        let supermarket = transactions[1].id.unwrap();
+        let history = AuditService::new(db.clone())
+            .get_history(AuditEntityType::Transaction, supermarket)
+            .await
+            .unwrap();
+        let statuses: Vec<EntryStatus> = history
+            .iter()
+            .map(|entry| {
+                let transaction: Transaction = entry.after().unwrap().unwrap();
+                transaction
+                    .entries
+                    .iter()
+                    .find(|entry| entry.account_id == bank)
+                    .unwrap()
+                    .status
+            })
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                EntryStatus::Pending,
+                EntryStatus::Cleared,
+                EntryStatus::Reconciled
+            ]
+        );
+        // The bakery purchase was not touched by the reconciliation
+        assert_eq!(
+            AuditService::new(db.clone())
+                .get_history(AuditEntityType::Transaction, transactions[2].id.unwrap())
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Reconciled entries are locked
+        let loaded = transaction_service
+            .get_transaction(supermarket)
+            .await
+            .unwrap();
+        assert!(
+            loaded
+                .entries
+                .iter()
+                .any(|entry| entry.status == EntryStatus::Reconciled)
+        );
+        assert!(
+            transaction_service
+                .delete_transaction(supermarket)
+                .await
+                .is_err()
+        );
+        assert!(
+            reconciliation_service
+                .set_entry_status(bank_entry(1), EntryStatus::Pending)
+                .await
+                .is_err()
+        );
+
+        // The unreconciled purchase can still be edited
+        transaction_service
+            .delete_transaction(transactions[2].id.unwrap())
+            .await
+            .unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_cleared_entry_survives_edit_and_undo(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let groceries = create_account(&db, "Groceries", AccountType::Expense, EXPENSES).await;
+        let transaction_service = TransactionService::new(db.clone());
+        let reconciliation_service = ReconciliationService::new(db.clone());
+        let undo_service = UndoService::new(db);
+
+        let transaction = transaction_service
+            .create_simple_transaction(
+                "Supermarket".to_string(),
+                date(2025, 7, 3),
+                eur(5000),
+                bank,
+                groceries,
+            )
+            .await
+            .unwrap();
+        let id = transaction.id.unwrap();
+        let bank_entry = transaction
+            .entries
+            .iter()
+            .find(|entry| entry.account_id == bank)
+            .unwrap()
+            .id
+            .unwrap();
+        reconciliation_service
+            .set_entry_status(bank_entry, EntryStatus::Cleared)
+            .await
+            .unwrap();
+        let entry_states = |transaction: &Transaction| {
+            transaction
+                .entries
+                .iter()
+                .map(|entry| (entry.id.unwrap(), entry.account_id, entry.status))
+                .collect::<Vec<_>>()
+        };
+        let cleared = entry_states(&transaction_service.get_transaction(id).await.unwrap());
+
+        // Renaming keeps the entries and their status
+        let entries: Vec<TransactionEntryInput> = transaction
+            .entries
+            .iter()
+            .cloned()
+            .map(TransactionEntryInput::from)
+            .collect();
+        let renamed = transaction_service
+            .update_transaction(
+                id,
+                NewTransaction::new(
+                    "Farmers market".to_string(),
+                    date(2025, 7, 3),
+                    entries.clone(),
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(entry_states(&renamed), cleared);
+
+        // Clearing is not undoable: undoing and redoing the rename restore the
+        // entries with their ids and leave their status alone
+        assert_eq!(undo_service.get_undo_redo_counts().await.unwrap(), (4, 0));
+        undo_service.undo(1).await.unwrap();
+        let restored = transaction_service.get_transaction(id).await.unwrap();
+        assert_eq!(restored.description, "Supermarket");
+        assert_eq!(entry_states(&restored), cleared);
+        undo_service.redo(1).await.unwrap();
+        let restored = transaction_service.get_transaction(id).await.unwrap();
+        assert_eq!(restored.description, "Farmers market");
+        assert_eq!(entry_states(&restored), cleared);
+
+        // Changing the amount of the cleared entry sends it back to pending
+        let mut entries = entries;
+        for entry in &mut entries {
+            entry.amount = eur(4500);
+        }
+        let corrected = transaction_service
+            .update_transaction(
+                id,
+                NewTransaction::new("Farmers market".to_string(), date(2025, 7, 3), entries),
+            )
+            .await
+            .unwrap();
+        assert!(
+            corrected
+                .entries
+                .iter()
+                .all(|entry| entry.status == EntryStatus::Pending)
+        );
+
+        // Undoing the correction books the old amount again, still pending
+        undo_service.undo(1).await.unwrap();
+        let restored = transaction_service.get_transaction(id).await.unwrap();
+        assert_eq!(restored.entries[0].amount, eur(5000));
+        assert!(
+            entry_states(&restored)
+                .iter()
+                .all(|(_, _, status)| *status == EntryStatus::Pending)
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_undo_skips_reconciled_changes(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let groceries = create_account(&db, "Groceries", AccountType::Expense, EXPENSES).await;
+        let transaction_service = TransactionService::new(db.clone());
+        let reconciliation_service = ReconciliationService::new(db.clone());
+        let undo_service = UndoService::new(db.clone());
+        let cinema = create_account(&db, "Cinema", AccountType::Expense, EXPENSES).await;
+
+        let supermarket = transaction_service
+            .create_simple_transaction(
+                "Supermarket".to_string(),
+                date(2025, 7, 3),
+                eur(5000),
+                bank,
+                groceries,
+            )
+            .await
+            .unwrap();
+        let entries: Vec<TransactionEntryInput> = supermarket
+            .entries
+            .iter()
+            .cloned()
+            .map(TransactionEntryInput::from)
+            .collect();
+        transaction_service
+            .update_transaction(
+                supermarket.id.unwrap(),
+                NewTransaction::new("Farmers market".to_string(), date(2025, 7, 3), entries),
+            )
+            .await
+            .unwrap();
+        assert_eq!(undo_service.get_undo_redo_counts().await.unwrap(), (5, 0));
+
+        // Undo something, then tick entries off: what is left to redo stays
+        undo_service.undo(1).await.unwrap();
+        let bank_entry = supermarket
+            .entries
+            .iter()
+            .find(|entry| entry.account_id == bank)
+            .unwrap()
+            .id
+            .unwrap();
+        reconciliation_service
+            .set_entry_status(bank_entry, EntryStatus::Cleared)
+            .await
+            .unwrap();
+        assert_eq!(undo_service.get_undo_redo_counts().await.unwrap(), (4, 1));
+        undo_service.redo(1).await.unwrap();
+
+        reconciliation_service
+            .finish_reconciliation(bank, date(2025, 7, 31), eur(-5000))
+            .await
+            .unwrap();
+
+        // The rename and creation of the reconciled transaction can no longer
+        // be undone: they are dropped and the account creation before them
+        // is undone instead
+        let undone = undo_service.undo(1).await.unwrap();
+        assert_eq!(undone[0].entity_type, AuditEntityType::Account);
+        assert_eq!(undone[0].entity_id, cinema);
+        let transaction = transaction_service
+            .get_transaction(supermarket.id.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(transaction.description, "Farmers market");
+        assert_eq!(undo_service.get_undo_redo_counts().await.unwrap(), (2, 1));
+
+        // The accounts of the reconciled transaction cannot be removed either,
+        // so asking to undo their creation changes nothing
+        assert!(undo_service.undo(2).await.is_err());
+        assert_eq!(undo_service.get_undo_redo_counts().await.unwrap(), (2, 1));
+        assert!(AccountService::new(db).get_account(cinema).await.is_err());
+    }
+}
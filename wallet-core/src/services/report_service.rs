@@ -1,174 +1,1322 @@
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
+use crate::db::accounts::{AccountRepository, AccountSums};
 use crate::db::connection::Database;
-use crate::errors::Result;
-use crate::{AccountService, AccountType, Currency, Money, TransactionFilters, TransactionService};
+use crate::db::settings::{REPORTING_CURRENCY, SettingsRepository};
+use crate::errors::{MoneyError, Result, WalletError};
+use crate::models::report::{
+    BalanceSheet, BalanceSheetLine, BalanceSheetSection, CashFlowCategory, CashFlowLine,
+    CashFlowStatement, ExpenseBreakdown, ExpenseCategory, IncomeStatement, IncomeStatementLine,
+    IncomeStatementSection, MissingRate, NetWorthPoint, NetWorthReport, NetWorthSeries,
+    ReportInterval, ReportPeriod, TrialBalance, TrialBalanceLine, TrialBalanceTotal, ValuedTotal,
+};
+use crate::{
+    AccountNode, AccountType, Currency, CurrencyService, Decimal, Money, PriceService,
+    TransactionFilters, TransactionService,
+};
 
 pub struct ReportService {
-    account_service: AccountService,
     transaction_service: TransactionService,
+    currency_service: CurrencyService,
+    price_service: PriceService,
+    account_repository: AccountRepository,
+    settings: SettingsRepository,
 }
 
 impl ReportService {
     pub fn new(db: Arc<Database>) -> Self {
         Self {
-            account_service: AccountService::new(db.clone()),
-            transaction_service: TransactionService::new(db),
+            transaction_service: TransactionService::new(db.clone()),
+            currency_service: CurrencyService::new(db.clone()),
+            price_service: PriceService::new(db.clone()),
+            account_repository: AccountRepository::new(db.clone()),
+            settings: SettingsRepository::new(db),
         }
     }
 
-    /// Calculate net worth (Assets - Liabilities)
-    pub async fn get_net_worth(&self) -> Result<Money> {
-        let total_assets = self.get_total_assets().await?;
-        let total_liabilities = self.get_total_liabilities().await?;
+    /// Currency used to value reports mixing several currencies
+    pub async fn get_reporting_currency(&self) -> Result<Currency> {
+        let code = self
+            .settings
+            .get(REPORTING_CURRENCY)
+            .await?
+            .unwrap_or_else(|| "EUR".to_string());
+        self.currency_service.get_currency(&code).await
+    }
 
-        let currency = total_assets.currency().clone();
-        let net_worth = total_assets.amount_minor() - total_liabilities.amount_minor();
+    pub async fn set_reporting_currency(&self, code: &str) -> Result<Currency> {
+        let currency = self.currency_service.get_currency(code).await?;
+        self.settings
+            .set(REPORTING_CURRENCY, currency.code())
+            .await?;
+        Ok(currency)
+    }
 
-        Ok(Money::from_minor_units(net_worth, currency))
+    /// Calculate net worth (Assets - Liabilities) in the reporting currency
+    pub async fn get_net_worth(&self) -> Result<Money> {
+        let total_assets = self.get_current_total(AccountType::Asset).await?;
+        let total_liabilities = self.get_current_total(AccountType::Liability).await?;
+        total_assets.checked_sub(&total_liabilities)
     }
 
-    /// Get total assets (sum of all asset account balances)
+    /// Get total assets (sum of all asset account balances) in the reporting currency
     pub async fn get_total_assets(&self) -> Result<Money> {
-        self.get_total_by_account_type(AccountType::Asset).await
+        self.get_current_total(AccountType::Asset).await
     }
 
-    /// Get total liabilities (sum of all liability account balances)
+    /// Get total liabilities (sum of all liability account balances) in the reporting currency
     pub async fn get_total_liabilities(&self) -> Result<Money> {
-        self.get_total_by_account_type(AccountType::Liability).await
+        self.get_current_total(AccountType::Liability).await
     }
 
-    /// Get current month income
-    pub async fn get_monthly_income(&self, year: i32, month: u32) -> Result<Money> {
-        self.get_monthly_total_by_account_type(AccountType::Income, year, month)
-            .await
+    /// Today's total of an account type in the reporting currency, computed like
+    /// the totals of the net worth report
+    async fn get_current_total(&self, account_type: AccountType) -> Result<Money> {
+        let reporting_currency = self.get_reporting_currency().await?;
+        let total = self
+            .get_valued_total(
+                account_type,
+                &reporting_currency,
+                Local::now().date_naive(),
+                &mut Vec::new(),
+            )
+            .await?;
+        Ok(total.converted)
     }
 
-    /// Get current month expenses  
-    pub async fn get_monthly_expenses(&self, year: i32, month: u32) -> Result<Money> {
-        self.get_monthly_total_by_account_type(AccountType::Expense, year, month)
-            .await
+    /// Net worth, total assets and total liabilities at the end of a date (today by
+    /// default), per currency and valued in the reporting currency using the rates
+    /// known on that date
+    pub async fn get_net_worth_report(
+        &self,
+        reporting_currency: &Currency,
+        as_of: Option<NaiveDate>,
+    ) -> Result<NetWorthReport> {
+        let as_of = as_of.unwrap_or_else(|| Local::now().date_naive());
+
+        let mut missing_rates = Vec::new();
+        let total_assets = self
+            .get_valued_total(
+                AccountType::Asset,
+                reporting_currency,
+                as_of,
+                &mut missing_rates,
+            )
+            .await?;
+        let total_liabilities = self
+            .get_valued_total(
+                AccountType::Liability,
+                reporting_currency,
+                as_of,
+                &mut missing_rates,
+            )
+            .await?;
+
+        // Net worth per currency: assets minus liabilities
+        let mut net_by_currency: BTreeMap<String, Money> = BTreeMap::new();
+        for amount in &total_assets.by_currency {
+            net_by_currency.insert(amount.currency().code().to_string(), amount.clone());
+        }
+        for amount in &total_liabilities.by_currency {
+            let net = net_by_currency
+                .entry(amount.currency().code().to_string())
+                .or_insert_with(|| Money::zero(amount.currency().clone()));
+            *net = net.checked_sub(amount)?;
+        }
+
+        let net_worth = ValuedTotal {
+            by_currency: net_by_currency.into_values().collect(),
+            converted: total_assets
+                .converted
+                .checked_sub(&total_liabilities.converted)?,
+        };
+
+        Ok(NetWorthReport {
+            as_of,
+            reporting_currency: reporting_currency.clone(),
+            total_assets,
+            total_liabilities,
+            net_worth,
+            missing_rates,
+        })
     }
 
-    /// Get current month income (convenience method using current date)
-    pub async fn get_current_month_income(&self) -> Result<Money> {
-        let now = Local::now();
-        self.get_monthly_income(now.year(), now.month()).await
+    /// Total balance of all accounts of a type at the end of `as_of`, per currency
+    /// and converted into the reporting currency. Accounts whose currency has no
+    /// known rate are added to `missing_rates` and left out of the converted total.
+    async fn get_valued_total(
+        &self,
+        account_type: AccountType,
+        reporting_currency: &Currency,
+        as_of: NaiveDate,
+        missing_rates: &mut Vec<MissingRate>,
+    ) -> Result<ValuedTotal> {
+        let sums = self
+            .account_repository
+            .get_account_sums_by_type(&account_type, Some(day_after(as_of)?))
+            .await?;
+        self.value_sums(
+            &account_type,
+            sums,
+            reporting_currency,
+            as_of,
+            missing_rates,
+        )
+        .await
     }
 
-    /// Get current month expenses (convenience method using current date)
-    pub async fn get_current_month_expenses(&self) -> Result<Money> {
-        let now = Local::now();
-        self.get_monthly_expenses(now.year(), now.month()).await
+    /// Total of the account sums following the normal balance of their type, per
+    /// currency and converted into the reporting currency with the rates known
+    /// on `as_of`. Accounts whose currency has no known rate are added to
+    /// `missing_rates` and left out of the converted total.
+    async fn value_sums(
+        &self,
+        account_type: &AccountType,
+        sums: Vec<AccountSums>,
+        reporting_currency: &Currency,
+        as_of: NaiveDate,
+        missing_rates: &mut Vec<MissingRate>,
+    ) -> Result<ValuedTotal> {
+        // Balance of each account following its normal balance, grouped by currency
+        let mut totals: BTreeMap<String, (Money, Vec<MissingRate>)> = BTreeMap::new();
+        for sums in sums {
+            let balance = normal_balance(account_type, &sums)?;
+
+            let (total, accounts) = totals
+                .entry(sums.currency.code().to_string())
+                .or_insert_with(|| (Money::zero(sums.currency.clone()), Vec::new()));
+            *total = total.checked_add(&balance)?;
+            if !balance.is_zero() {
+                accounts.push(MissingRate {
+                    account_id: sums.account_id,
+                    account_name: sums.account_name,
+                    currency: sums.currency.code().to_string(),
+                });
+            }
+        }
+
+        let mut by_currency = Vec::new();
+        let mut converted = Money::zero(reporting_currency.clone());
+        for (total, accounts) in totals.into_values() {
+            if !total.is_zero() {
+                match self
+                    .price_service
+                    .convert(&total, reporting_currency, as_of)
+                    .await?
+                {
+                    Some(value) => converted = converted.checked_add(&value)?,
+                    None => missing_rates.extend(accounts),
+                }
+            }
+            by_currency.push(total);
+        }
+
+        Ok(ValuedTotal {
+            by_currency,
+            converted,
+        })
+    }
+
+    /// Total assets, total liabilities and net worth in the reporting currency at the
+    /// end of every period of `interval` from `from` to `to`. Balances are built in
+    /// a single pass over the daily entry sums, and rates come from the price
+    /// history of each currency loaded once up front, so long ranges stay cheap.
+    pub async fn net_worth_series(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        interval: ReportInterval,
+    ) -> Result<NetWorthSeries> {
+        let reporting_currency = self.get_reporting_currency().await?;
+        let periods = report_periods(from, to, Some(interval))?;
+        let daily_sums = self
+            .account_repository
+            .get_daily_net_worth_sums(day_after(to)?)
+            .await?;
+
+        let mut timelines: HashMap<String, RateTimeline> = HashMap::new();
+        for sums in &daily_sums {
+            let code = sums.currency.code();
+            if code != reporting_currency.code() && !timelines.contains_key(code) {
+                let timeline = self.rate_timeline(code, reporting_currency.code()).await?;
+                timelines.insert(code.to_string(), timeline);
+            }
+        }
+
+        // Running debit/credit sums of every account and currency
+        let mut balances: BTreeMap<(i64, String), (AccountType, AccountSums)> = BTreeMap::new();
+        let mut daily_sums = daily_sums.into_iter().peekable();
+        let mut points = Vec::new();
+        let mut missing_rates = Vec::new();
+
+        for period in &periods {
+            while let Some(sums) = daily_sums.next_if(|sums| sums.transaction_date <= period.end) {
+                let (_, balance) = balances
+                    .entry((sums.account_id, sums.currency.code().to_string()))
+                    .or_insert_with(|| {
+                        (
+                            sums.account_type.clone(),
+                            AccountSums {
+                                account_id: sums.account_id,
+                                account_name: sums.account_name.clone(),
+                                currency: sums.currency.clone(),
+                                total_debits: 0,
+                                total_credits: 0,
+                            },
+                        )
+                    });
+                balance.total_debits = balance
+                    .total_debits
+                    .checked_add(sums.total_debits)
+                    .ok_or(MoneyError::Overflow)?;
+                balance.total_credits = balance
+                    .total_credits
+                    .checked_add(sums.total_credits)
+                    .ok_or(MoneyError::Overflow)?;
+            }
+
+            let mut totals = [
+                Money::zero(reporting_currency.clone()),
+                Money::zero(reporting_currency.clone()),
+            ];
+            for (side, account_type) in [AccountType::Asset, AccountType::Liability]
+                .iter()
+                .enumerate()
+            {
+                // Balances grouped by currency, then valued like `get_valued_total`
+                let mut by_currency: BTreeMap<String, (Money, Vec<&AccountSums>)> = BTreeMap::new();
+                for (_, sums) in balances
+                    .values()
+                    .filter(|(balance_type, _)| balance_type == account_type)
+                {
+                    let balance = normal_balance(account_type, sums)?;
+                    let (total, accounts) = by_currency
+                        .entry(sums.currency.code().to_string())
+                        .or_insert_with(|| (Money::zero(sums.currency.clone()), Vec::new()));
+                    *total = total.checked_add(&balance)?;
+                    if !balance.is_zero() {
+                        accounts.push(sums);
+                    }
+                }
+
+                for (code, (total, accounts)) in by_currency {
+                    if total.is_zero() {
+                        continue;
+                    }
+                    let rate = match timelines.get_mut(&code) {
+                        Some(timeline) => timeline.rate_on(period.end),
+                        None => Some(Decimal::ONE),
+                    };
+                    match rate {
+                        Some(rate) => {
+                            let value = total.convert(rate, reporting_currency.clone())?;
+                            totals[side] = totals[side].checked_add(&value)?;
+                        }
+                        None => {
+                            for sums in accounts {
+                                add_missing_rate(
+                                    &mut missing_rates,
+                                    sums.account_id,
+                                    &sums.account_name,
+                                    &sums.currency,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+
+            let [total_assets, total_liabilities] = totals;
+            points.push(NetWorthPoint {
+                date: period.end,
+                net_worth: total_assets.checked_sub(&total_liabilities)?,
+                total_assets,
+                total_liabilities,
+            });
+        }
+
+        Ok(NetWorthSeries {
+            from,
+            to,
+            interval,
+            reporting_currency,
+            points,
+            missing_rates,
+        })
     }
 
-    /// Helper method to calculate total balance by account type
-    async fn get_total_by_account_type(&self, account_type: AccountType) -> Result<Money> {
-        let accounts = self.account_service.get_accounts().await?;
-        let currency = Currency::new("EUR", 2, "€")?;
+    /// Rates of `base_currency` in `quote_currency` over time, from the prices
+    /// recorded in either direction
+    async fn rate_timeline(
+        &self,
+        base_currency: &str,
+        quote_currency: &str,
+    ) -> Result<RateTimeline> {
+        let direct = self
+            .price_service
+            .get_price_history(base_currency, quote_currency)
+            .await?;
+        let inverse = self
+            .price_service
+            .get_price_history(quote_currency, base_currency)
+            .await?;
+
+        let mut rates: Vec<(NaiveDate, bool, Decimal)> = inverse
+            .into_iter()
+            .filter_map(|price| {
+                Decimal::ONE
+                    .checked_div(price.rate)
+                    .map(|rate| (price.price_date, false, rate))
+            })
+            .chain(
+                direct
+                    .into_iter()
+                    .map(|price| (price.price_date, true, price.rate)),
+            )
+            .collect();
+        // Like `PriceService::get_rate`, a direct price wins over an inverse one of
+        // the same date. The sort is stable, so the last price recorded on a date
+        // comes last.
+        rates.sort_by_key(|&(date, is_direct, _)| (date, is_direct));
+
+        Ok(RateTimeline {
+            rates: rates
+                .into_iter()
+                .map(|(date, _, rate)| (date, rate))
+                .collect(),
+            next: 0,
+            current: None,
+        })
+    }
+
+    /// Rate of `currency` in the reporting currency on `date`, from the timeline
+    /// of the currency, fetched the first time it is needed. Dates must not go
+    /// backwards between calls for one currency.
+    async fn rate_on(
+        &self,
+        timelines: &mut HashMap<String, RateTimeline>,
+        currency: &Currency,
+        reporting_currency: &Currency,
+        date: NaiveDate,
+    ) -> Result<Option<Decimal>> {
+        if currency.code() == reporting_currency.code() {
+            return Ok(Some(Decimal::ONE));
+        }
+        if !timelines.contains_key(currency.code()) {
+            let timeline = self
+                .rate_timeline(currency.code(), reporting_currency.code())
+                .await?;
+            timelines.insert(currency.code().to_string(), timeline);
+        }
+        Ok(timelines
+            .get_mut(currency.code())
+            .and_then(|timeline| timeline.rate_on(date)))
+    }
+
+    /// Total debits, total credits and net balance of every account at the end of
+    /// `as_of`, with the totals per currency entries balance in and a check that
+    /// debits equal credits in each
+    pub async fn trial_balance(&self, as_of: NaiveDate) -> Result<TrialBalance> {
+        let nodes = self
+            .account_repository
+            .get_account_tree_filtered(true)
+            .await?;
+        let before_date = Some(day_after(as_of)?);
+        let sums = self
+            .account_repository
+            .get_all_account_sums(before_date)
+            .await?;
+
+        let mut sums_by_account: BTreeMap<i64, Vec<AccountSums>> = BTreeMap::new();
+        for sums in sums {
+            sums_by_account
+                .entry(sums.account_id)
+                .or_default()
+                .push(sums);
+        }
 
-        let mut total = 0i64;
+        let mut lines = Vec::new();
+        for node in nodes {
+            let account_id = node.account.id.unwrap_or_default();
+            // Accounts without entries are listed with zero totals in their currency
+            let amounts = match sums_by_account.remove(&account_id) {
+                Some(sums) => sums
+                    .into_iter()
+                    .map(|sums| (sums.currency, sums.total_debits, sums.total_credits))
+                    .collect(),
+                None => vec![(node.account.currency.clone(), 0, 0)],
+            };
 
-        // Sum balances of root accounts of the specified type
-        for account in accounts
+            for (currency, debits, credits) in amounts {
+                let total_debits = Money::from_minor_units(debits, currency.clone());
+                let total_credits = Money::from_minor_units(credits, currency);
+                lines.push(TrialBalanceLine {
+                    account_id,
+                    account_name: node.account.name.clone(),
+                    account_type: node.account.account_type.clone(),
+                    level: node.level,
+                    path: node.path.clone(),
+                    balance: total_debits.checked_sub(&total_credits)?,
+                    total_debits,
+                    total_credits,
+                });
+            }
+        }
+
+        // Converted entries balance in their price currency, not in their own,
+        // so the totals count them like the check does
+        let totals: Vec<TrialBalanceTotal> = self
+            .account_repository
+            .get_balancing_totals(before_date)
+            .await?
+            .into_iter()
+            .map(|(currency, debits, credits)| TrialBalanceTotal {
+                total_debits: Money::from_minor_units(debits, currency.clone()),
+                total_credits: Money::from_minor_units(credits, currency),
+            })
+            .collect();
+        let is_balanced = totals
             .iter()
-            .filter(|a| a.parent_id.is_none() && a.account_type == account_type)
-        {
-            if let Some(account_id) = account.id {
+            .all(|total| total.total_debits == total.total_credits);
+
+        Ok(TrialBalance {
+            as_of,
+            lines,
+            totals,
+            is_balanced,
+        })
+    }
+
+    /// Balance sheet at the end of each date in `dates`, one column per date. Asset,
+    /// Liability and Equity accounts are laid out as a tree where every account
+    /// carries the subtotal of its sub-accounts, valued in the reporting currency
+    /// with the rates known on each date. Income and Expenses not yet closed into
+    /// retained earnings are shown as current earnings, and the effect of rate
+    /// changes as exchange rate differences: the value of every account at the
+    /// rates of the date minus its value at the rates of the days its entries
+    /// were booked. Assets then equal liabilities plus equity, unless balances
+    /// had to be left out for lack of a rate; those are listed in `missing_rates`.
+    pub async fn balance_sheet(
+        &self,
+        dates: &[NaiveDate],
+        reporting_currency: &Currency,
+    ) -> Result<BalanceSheet> {
+        if dates.is_empty() {
+            return Err(WalletError::ValidationError(
+                "At least one date is required".to_string(),
+            ));
+        }
+
+        let nodes = self
+            .account_repository
+            .get_account_tree_filtered(true)
+            .await?;
+        let positions: HashMap<i64, usize> = nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(position, node)| node.account.id.map(|id| (id, position)))
+            .collect();
+
+        let zero = vec![Money::zero(reporting_currency.clone()); dates.len()];
+        let mut amounts = vec![zero.clone(); nodes.len()];
+        let mut current_earnings = zero.clone();
+        let mut exchange_differences = zero.clone();
+        let mut missing_rates: Vec<MissingRate> = Vec::new();
+
+        // Entries in the currency they balance in, to value them at the rate of
+        // the day they were booked: transactions balance at those values too
+        let last_date = dates.iter().max().copied().unwrap_or(dates[0]);
+        let daily_sums = self
+            .account_repository
+            .get_daily_balancing_sums(day_after(last_date)?)
+            .await?;
+        let mut timelines: HashMap<String, RateTimeline> = HashMap::new();
+        for sums in &daily_sums {
+            let code = sums.currency.code();
+            if code != reporting_currency.code() && !timelines.contains_key(code) {
+                let timeline = self.rate_timeline(code, reporting_currency.code()).await?;
+                timelines.insert(code.to_string(), timeline);
+            }
+        }
+
+        for (column, &as_of) in dates.iter().enumerate() {
+            // Value of each account at the rates of `as_of` as a debit balance,
+            // and the accounts that could not be valued
+            let mut values: HashMap<i64, Money> = HashMap::new();
+            let mut unvalued: HashSet<i64> = HashSet::new();
+
+            let sums = self
+                .account_repository
+                .get_all_account_sums(Some(day_after(as_of)?))
+                .await?;
+            for sums in sums {
+                let Some(&position) = positions.get(&sums.account_id) else {
+                    continue;
+                };
+                let account_type = &nodes[position].account.account_type;
+                let balance = normal_balance(account_type, &sums)?;
+                if balance.is_zero() {
+                    continue;
+                }
+                let Some(value) = self
+                    .price_service
+                    .convert(&balance, reporting_currency, as_of)
+                    .await?
+                else {
+                    unvalued.insert(sums.account_id);
+                    add_missing_rate(
+                        &mut missing_rates,
+                        sums.account_id,
+                        &sums.account_name,
+                        &sums.currency,
+                    );
+                    continue;
+                };
+
+                let debit_value = if account_type.is_debit_normal() {
+                    value.clone()
+                } else {
+                    value.checked_neg()?
+                };
+                let total = values
+                    .entry(sums.account_id)
+                    .or_insert_with(|| Money::zero(reporting_currency.clone()));
+                *total = total.checked_add(&debit_value)?;
+
+                match account_type {
+                    AccountType::Income => {
+                        current_earnings[column] = current_earnings[column].checked_add(&value)?
+                    }
+                    AccountType::Expense => {
+                        current_earnings[column] = current_earnings[column].checked_sub(&value)?
+                    }
+                    _ => {
+                        add_to_parents(&nodes, &positions, &mut amounts, position, column, &value)?
+                    }
+                }
+            }
+
+            // Subtract the value of each account at the rates of the days its
+            // entries were booked
+            let mut timelines = timelines.clone();
+            for sums in daily_sums
+                .iter()
+                .take_while(|sums| sums.transaction_date <= as_of)
+            {
+                let rate = match timelines.get_mut(sums.currency.code()) {
+                    Some(timeline) => timeline.rate_on(sums.transaction_date),
+                    None => Some(Decimal::ONE),
+                };
+                let Some(rate) = rate else {
+                    unvalued.insert(sums.account_id);
+                    add_missing_rate(
+                        &mut missing_rates,
+                        sums.account_id,
+                        &sums.account_name,
+                        &sums.currency,
+                    );
+                    continue;
+                };
+                let net_debit =
+                    Money::from_minor_units(sums.total_debits, sums.currency.clone()).checked_sub(
+                        &Money::from_minor_units(sums.total_credits, sums.currency.clone()),
+                    )?;
+                let total = values
+                    .entry(sums.account_id)
+                    .or_insert_with(|| Money::zero(reporting_currency.clone()));
+                *total =
+                    total.checked_sub(&net_debit.convert(rate, reporting_currency.clone())?)?;
+            }
+            for (account_id, difference) in values {
+                if !unvalued.contains(&account_id) {
+                    exchange_differences[column] =
+                        exchange_differences[column].checked_add(&difference)?;
+                }
+            }
+        }
+
+        let section = |account_type: AccountType| -> Result<BalanceSheetSection> {
+            let mut lines = Vec::new();
+            let mut totals = zero.clone();
+            for (node, amounts) in nodes.iter().zip(&amounts) {
+                let account = &node.account;
+                if account.account_type != account_type
+                    || (!account.is_active && amounts.iter().all(Money::is_zero))
+                {
+                    continue;
+                }
+                if account.parent_id.is_none() {
+                    totals = add_columns(&totals, amounts)?;
+                }
+                lines.push(BalanceSheetLine {
+                    account_id: account.id,
+                    name: account.name.clone(),
+                    level: node.level,
+                    path: node.path.clone(),
+                    amounts: amounts.clone(),
+                });
+            }
+            Ok(BalanceSheetSection {
+                account_type,
+                lines,
+                totals,
+            })
+        };
+        let assets = section(AccountType::Asset)?;
+        let liabilities = section(AccountType::Liability)?;
+        let mut equity = section(AccountType::Equity)?;
+
+        let mut computed = vec![("Current earnings", current_earnings)];
+        if !exchange_differences.iter().all(Money::is_zero) {
+            computed.push(("Exchange rate differences", exchange_differences));
+        }
+        let root = equity.lines.iter().position(|line| line.level == 0);
+        for (name, amounts) in computed {
+            if let Some(root) = root {
+                equity.lines[root].amounts = add_columns(&equity.lines[root].amounts, &amounts)?;
+            }
+            equity.totals = add_columns(&equity.totals, &amounts)?;
+            let path = match root {
+                Some(root) => format!("{} > {}", equity.lines[root].path, name),
+                None => name.to_string(),
+            };
+            equity.lines.push(BalanceSheetLine {
+                account_id: None,
+                name: name.to_string(),
+                level: if root.is_some() { 1 } else { 0 },
+                path,
+                amounts,
+            });
+        }
+
+        let total_liabilities_and_equity = add_columns(&liabilities.totals, &equity.totals)?;
+
+        Ok(BalanceSheet {
+            dates: dates.to_vec(),
+            reporting_currency: reporting_currency.clone(),
+            assets,
+            liabilities,
+            equity,
+            total_liabilities_and_equity,
+            missing_rates,
+        })
+    }
+
+    /// Income and expenses from `from` to `to` (both included), one column per
+    /// period of `interval` or a single column without one. Accounts are laid out
+    /// as a tree where every account carries the subtotal of its sub-accounts,
+    /// valued in the reporting currency with the rates known at the end of each
+    /// period. Fiscal year closing transactions are left out.
+    pub async fn income_statement(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        interval: Option<ReportInterval>,
+        reporting_currency: &Currency,
+    ) -> Result<IncomeStatement> {
+        let periods = report_periods(from, to, interval)?;
+
+        let nodes = self
+            .account_repository
+            .get_account_tree_filtered(true)
+            .await?;
+        let positions: HashMap<i64, usize> = nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(position, node)| node.account.id.map(|id| (id, position)))
+            .collect();
+
+        let zero = vec![Money::zero(reporting_currency.clone()); periods.len()];
+        let mut amounts = vec![zero.clone(); nodes.len()];
+        let mut missing_rates: Vec<MissingRate> = Vec::new();
+
+        for (column, period) in periods.iter().enumerate() {
+            let sums = self
+                .account_repository
+                .get_activity_sums(period.start, day_after(period.end)?)
+                .await?;
+            for sums in sums {
+                let Some(&position) = positions.get(&sums.account_id) else {
+                    continue;
+                };
+                let account_type = &nodes[position].account.account_type;
+                if !matches!(account_type, AccountType::Income | AccountType::Expense) {
+                    continue;
+                }
+                let balance = normal_balance(account_type, &sums)?;
+                if balance.is_zero() {
+                    continue;
+                }
                 match self
-                    .account_service
-                    .calculate_balance_with_children(account_id)
-                    .await
+                    .price_service
+                    .convert(&balance, reporting_currency, period.end)
+                    .await?
                 {
-                    Ok(balance) => {
-                        total += balance.amount_minor();
+                    Some(value) => {
+                        add_to_parents(&nodes, &positions, &mut amounts, position, column, &value)?
                     }
-                    Err(e) => {
-                        // Log error but continue with other accounts
-                        eprintln!("Failed to calculate balance for account {account_id}: {e}");
+                    None => add_missing_rate(
+                        &mut missing_rates,
+                        sums.account_id,
+                        &sums.account_name,
+                        &sums.currency,
+                    ),
+                }
+            }
+        }
+
+        let section = |account_type: AccountType| -> Result<IncomeStatementSection> {
+            let mut lines = Vec::new();
+            let mut totals = zero.clone();
+            for (node, amounts) in nodes.iter().zip(&amounts) {
+                let account = &node.account;
+                if account.account_type != account_type
+                    || (!account.is_active && amounts.iter().all(Money::is_zero))
+                {
+                    continue;
+                }
+                if account.parent_id.is_none() {
+                    totals = add_columns(&totals, amounts)?;
+                }
+                lines.push(IncomeStatementLine {
+                    account_id: account.id.unwrap_or_default(),
+                    name: account.name.clone(),
+                    level: node.level,
+                    path: node.path.clone(),
+                    amounts: amounts.clone(),
+                });
+            }
+            Ok(IncomeStatementSection {
+                account_type,
+                lines,
+                totals,
+            })
+        };
+        let income = section(AccountType::Income)?;
+        let expenses = section(AccountType::Expense)?;
+        let net_income = sub_columns(&income.totals, &expenses.totals)?;
+
+        Ok(IncomeStatement {
+            from,
+            to,
+            periods,
+            reporting_currency: reporting_currency.clone(),
+            income,
+            expenses,
+            net_income,
+            missing_rates,
+        })
+    }
+
+    /// Cash flow of a set of cash accounts, their sub-accounts included, from `from`
+    /// to `to`, one column per period of `interval` or a single column without one.
+    /// Every movement is classified by the type of the other accounts of its
+    /// transaction, so transfers between cash accounts cancel out. Movements are
+    /// valued in the reporting currency with the rates known on their day,
+    /// opening cash with the rates of the day before the period starts and
+    /// closing cash with those of its last day. The exchange rate effect is the
+    /// change in value of the opening cash and of every movement between those
+    /// rates and the rates of the last day.
+    pub async fn cash_flow(
+        &self,
+        cash_account_ids: &[i64],
+        from: NaiveDate,
+        to: NaiveDate,
+        interval: Option<ReportInterval>,
+        reporting_currency: &Currency,
+    ) -> Result<CashFlowStatement> {
+        if cash_account_ids.is_empty() {
+            return Err(WalletError::ValidationError(
+                "At least one cash account is required".to_string(),
+            ));
+        }
+        let periods = report_periods(from, to, interval)?;
+
+        let nodes = self
+            .account_repository
+            .get_account_tree_filtered(true)
+            .await?;
+        let positions: HashMap<i64, usize> = nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(position, node)| node.account.id.map(|id| (id, position)))
+            .collect();
+        if let Some(account_id) = cash_account_ids
+            .iter()
+            .find(|account_id| !positions.contains_key(account_id))
+        {
+            return Err(WalletError::ValidationError(format!(
+                "Account {} not found",
+                account_id
+            )));
+        }
+
+        // An account is cash if it or one of its parents was designated as cash
+        let mut cash = HashSet::new();
+        for node in &nodes {
+            let mut next = Some(node);
+            while let Some(parent) = next {
+                if parent
+                    .account
+                    .id
+                    .is_some_and(|id| cash_account_ids.contains(&id))
+                {
+                    cash.extend(node.account.id);
+                    break;
+                }
+                next = parent
+                    .account
+                    .parent_id
+                    .and_then(|parent_id| positions.get(&parent_id))
+                    .map(|&position| &nodes[position]);
+            }
+        }
+        let cash_ids: Vec<i64> = cash.iter().copied().collect();
+
+        let zero = vec![Money::zero(reporting_currency.clone()); periods.len()];
+        let mut lines: Vec<CashFlowLine> = [
+            CashFlowCategory::Income,
+            CashFlowCategory::Expense,
+            CashFlowCategory::Liability,
+            CashFlowCategory::Equity,
+            CashFlowCategory::Transfer,
+        ]
+        .into_iter()
+        .map(|category| CashFlowLine {
+            category,
+            inflows: zero.clone(),
+            outflows: zero.clone(),
+        })
+        .collect();
+        let mut opening_cash = zero.clone();
+        let mut closing_cash = zero.clone();
+        let mut exchange_rate_effect = zero.clone();
+        let mut missing_rates = Vec::new();
+        // Counter-account and cash movements are read in date order, each with
+        // its own rates
+        let mut flow_timelines: HashMap<String, RateTimeline> = HashMap::new();
+        let mut cash_timelines: HashMap<String, RateTimeline> = HashMap::new();
+
+        for (column, period) in periods.iter().enumerate() {
+            let before_date = day_after(period.end)?;
+            let opening_date = period
+                .start
+                .pred_opt()
+                .ok_or_else(|| WalletError::ValidationError("Invalid date".to_string()))?;
+            opening_cash[column] = self
+                .cash_balance(
+                    &cash,
+                    period.start,
+                    opening_date,
+                    reporting_currency,
+                    &mut missing_rates,
+                )
+                .await?;
+            closing_cash[column] = self
+                .cash_balance(
+                    &cash,
+                    before_date,
+                    period.end,
+                    reporting_currency,
+                    &mut missing_rates,
+                )
+                .await?;
+
+            let sums = self
+                .account_repository
+                .get_counter_account_sums(&cash_ids, period.start, before_date)
+                .await?;
+            for sums in sums {
+                let category = match sums.account_type {
+                    AccountType::Income => CashFlowCategory::Income,
+                    AccountType::Expense => CashFlowCategory::Expense,
+                    AccountType::Liability => CashFlowCategory::Liability,
+                    AccountType::Equity => CashFlowCategory::Equity,
+                    AccountType::Asset => CashFlowCategory::Transfer,
+                };
+                let Some(line) = lines.iter_mut().find(|line| line.category == category) else {
+                    continue;
+                };
+                let Some(rate) = self
+                    .rate_on(
+                        &mut flow_timelines,
+                        &sums.currency,
+                        reporting_currency,
+                        sums.transaction_date,
+                    )
+                    .await?
+                else {
+                    add_missing_rate(
+                        &mut missing_rates,
+                        sums.account_id,
+                        &sums.account_name,
+                        &sums.currency,
+                    );
+                    continue;
+                };
+
+                // A credit on the other side brings cash in, a debit takes it out
+                for (amount_minor, is_inflow) in
+                    [(sums.total_credits, true), (sums.total_debits, false)]
+                {
+                    if amount_minor == 0 {
+                        continue;
                     }
+                    let value = Money::from_minor_units(amount_minor, sums.currency.clone())
+                        .convert(rate, reporting_currency.clone())?;
+                    let total = if is_inflow {
+                        &mut line.inflows[column]
+                    } else {
+                        &mut line.outflows[column]
+                    };
+                    *total = total.checked_add(&value)?;
                 }
             }
+
+            // Change in value of the opening cash from the rates of the day before
+            // the period to those of its last day...
+            let opening_at_closing_rates = self
+                .cash_balance(
+                    &cash,
+                    period.start,
+                    period.end,
+                    reporting_currency,
+                    &mut missing_rates,
+                )
+                .await?;
+            let mut effect = opening_at_closing_rates.checked_sub(&opening_cash[column])?;
+
+            // ...and of every cash movement from the rate of its day
+            let movements = self
+                .account_repository
+                .get_daily_account_sums(&cash_ids, period.start, before_date)
+                .await?;
+            let mut closing_rates: HashMap<String, Option<Decimal>> = HashMap::new();
+            for sums in movements {
+                let day_rate = self
+                    .rate_on(
+                        &mut cash_timelines,
+                        &sums.currency,
+                        reporting_currency,
+                        sums.transaction_date,
+                    )
+                    .await?;
+                let code = sums.currency.code();
+                let closing_rate = match closing_rates.get(code) {
+                    Some(&rate) => rate,
+                    None => {
+                        let rate = self
+                            .price_service
+                            .get_rate(code, reporting_currency.code(), period.end)
+                            .await?;
+                        closing_rates.insert(code.to_string(), rate);
+                        rate
+                    }
+                };
+                let (Some(day_rate), Some(closing_rate)) = (day_rate, closing_rate) else {
+                    add_missing_rate(
+                        &mut missing_rates,
+                        sums.account_id,
+                        &sums.account_name,
+                        &sums.currency,
+                    );
+                    continue;
+                };
+                let movement =
+                    Money::from_minor_units(sums.total_debits, sums.currency.clone()).checked_sub(
+                        &Money::from_minor_units(sums.total_credits, sums.currency.clone()),
+                    )?;
+                effect = effect
+                    .checked_add(&movement.convert(closing_rate, reporting_currency.clone())?)?
+                    .checked_sub(&movement.convert(day_rate, reporting_currency.clone())?)?;
+            }
+            exchange_rate_effect[column] = effect;
+        }
+
+        let mut total_inflows = zero.clone();
+        let mut total_outflows = zero.clone();
+        for line in &lines {
+            total_inflows = add_columns(&total_inflows, &line.inflows)?;
+            total_outflows = add_columns(&total_outflows, &line.outflows)?;
         }
 
-        Ok(Money::from_minor_units(total, currency))
+        Ok(CashFlowStatement {
+            cash_account_ids: cash_account_ids.to_vec(),
+            periods,
+            reporting_currency: reporting_currency.clone(),
+            opening_cash,
+            lines,
+            total_inflows,
+            total_outflows,
+            exchange_rate_effect,
+            closing_cash,
+            missing_rates,
+        })
     }
 
-    /// Helper method to calculate monthly total balance by account type with date filtering
-    async fn get_monthly_total_by_account_type(
+    /// Value of the cash accounts counting the transactions before `before_date`,
+    /// in the reporting currency with the rates known on `valued_on`
+    async fn cash_balance(
         &self,
-        account_type: AccountType,
-        year: i32,
-        month: u32,
+        cash: &HashSet<i64>,
+        before_date: NaiveDate,
+        valued_on: NaiveDate,
+        reporting_currency: &Currency,
+        missing_rates: &mut Vec<MissingRate>,
     ) -> Result<Money> {
-        // Calculate start and end dates for the month
-        let start_date = NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(|| {
-            crate::errors::WalletError::ValidationError("Invalid date".to_string())
-        })?;
+        let sums = self
+            .account_repository
+            .get_all_account_sums(Some(before_date))
+            .await?;
 
-        let end_date = if month == 12 {
-            NaiveDate::from_ymd_opt(year + 1, 1, 1)
-        } else {
-            NaiveDate::from_ymd_opt(year, month + 1, 1)
+        let mut total = Money::zero(reporting_currency.clone());
+        for sums in sums.iter().filter(|sums| cash.contains(&sums.account_id)) {
+            let balance = normal_balance(&AccountType::Asset, sums)?;
+            if balance.is_zero() {
+                continue;
+            }
+            match self
+                .price_service
+                .convert(&balance, reporting_currency, valued_on)
+                .await?
+            {
+                Some(value) => total = total.checked_add(&value)?,
+                None => add_missing_rate(
+                    missing_rates,
+                    sums.account_id,
+                    &sums.account_name,
+                    &sums.currency,
+                ),
+            }
         }
-        .ok_or_else(|| crate::errors::WalletError::ValidationError("Invalid date".to_string()))?
-        .pred_opt()
-        .ok_or_else(|| crate::errors::WalletError::ValidationError("Invalid date".to_string()))?;
+        Ok(total)
+    }
 
-        // Get all accounts of the specified type
-        let accounts = self.account_service.get_accounts().await?;
-        let currency = Currency::new("EUR", 2, "€")?;
-        let mut total = 0i64;
+    /// Spending from `from` to `to` by Expense account, rolled up `depth` levels
+    /// below `parent_id` (the Expense root when `None`). Every expense counts once,
+    /// in its account's ancestor at that depth, or in its own account when that is
+    /// shallower. Passing a category as `parent_id` drills into it.
+    pub async fn expense_breakdown(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        depth: u32,
+        parent_id: Option<i64>,
+    ) -> Result<ExpenseBreakdown> {
+        if from > to {
+            return Err(WalletError::ValidationError(
+                "Start date must not be after end date".to_string(),
+            ));
+        }
+        if depth == 0 {
+            return Err(WalletError::ValidationError(
+                "Depth must be at least 1".to_string(),
+            ));
+        }
+        let reporting_currency = self.get_reporting_currency().await?;
 
-        // Sum balances for accounts of the specified type within date range
-        for account in accounts
+        let nodes = self
+            .account_repository
+            .get_account_tree_filtered(true)
+            .await?;
+        let positions: HashMap<i64, usize> = nodes
             .iter()
-            .filter(|a| a.parent_id.is_none() && a.account_type == account_type)
-        {
-            if let Some(account_id) = account.id {
-                // Use the date-filtered balance calculation
+            .enumerate()
+            .filter_map(|(position, node)| node.account.id.map(|id| (id, position)))
+            .collect();
+        let parent = match parent_id {
+            Some(parent_id) => {
+                let position = positions
+                    .get(&parent_id)
+                    .copied()
+                    .filter(|&position| {
+                        nodes[position].account.account_type == AccountType::Expense
+                    })
+                    .ok_or_else(|| {
+                        WalletError::ValidationError(format!(
+                            "Expense account {} not found",
+                            parent_id
+                        ))
+                    })?;
+                Some(position)
+            }
+            None => None,
+        };
+        let target_level = parent.map_or(0, |position| nodes[position].level) + depth as i32;
+
+        let sums = self
+            .account_repository
+            .get_transaction_sums_by_type(&AccountType::Expense, from, day_after(to)?)
+            .await?;
+
+        // Spending per currency and transactions of every category
+        let mut categories: BTreeMap<usize, (BTreeMap<String, Money>, HashSet<i64>)> =
+            BTreeMap::new();
+        let mut transactions = HashSet::new();
+        for sums in sums {
+            let Some(&position) = positions.get(&sums.account_id) else {
+                continue;
+            };
+            // The account and its parents, deepest first
+            let mut chain = vec![position];
+            while let Some(&parent_position) = nodes[chain[chain.len() - 1]]
+                .account
+                .parent_id
+                .and_then(|parent_id| positions.get(&parent_id))
+            {
+                chain.push(parent_position);
+            }
+            if parent.is_some_and(|parent| !chain.contains(&parent)) {
+                continue;
+            }
+            let Some(&category) = chain
+                .iter()
+                .find(|&&position| nodes[position].level <= target_level)
+            else {
+                continue;
+            };
+
+            // Expenses are debit-normal, refunds are credits
+            let balance =
+                Money::from_minor_units(sums.total_debits, sums.currency.clone()).checked_sub(
+                    &Money::from_minor_units(sums.total_credits, sums.currency.clone()),
+                )?;
+            let (amounts, category_transactions) = categories.entry(category).or_default();
+            let amount = amounts
+                .entry(balance.currency().code().to_string())
+                .or_insert_with(|| Money::zero(balance.currency().clone()));
+            *amount = amount.checked_add(&balance)?;
+            category_transactions.insert(sums.transaction_id);
+            transactions.insert(sums.transaction_id);
+        }
+
+        let parent_ids: HashSet<i64> = nodes
+            .iter()
+            .filter_map(|node| node.account.parent_id)
+            .collect();
+        let mut missing_rates = Vec::new();
+        let mut total = Money::zero(reporting_currency.clone());
+        let mut items = Vec::new();
+        for (position, (amounts, category_transactions)) in categories {
+            let account = &nodes[position].account;
+            let account_id = account.id.unwrap_or_default();
+            let mut amount = Money::zero(reporting_currency.clone());
+            for value in amounts.values().filter(|value| !value.is_zero()) {
                 match self
-                    .account_service
-                    .calculate_account_balance(account_id, Some(end_date))
-                    .await
+                    .price_service
+                    .convert(value, &reporting_currency, to)
+                    .await?
                 {
-                    Ok(balance) => {
-                        // Subtract balance at start of month to get just this month's activity
-                        let start_balance = match self
-                            .account_service
-                            .calculate_account_balance(
-                                account_id,
-                                Some(start_date.pred_opt().unwrap_or(start_date)),
-                            )
-                            .await
-                        {
-                            Ok(start_bal) => start_bal.amount_minor(),
-                            Err(_) => 0, // No transactions before start date
-                        };
-
-                        total += balance.amount_minor() - start_balance;
-                    }
-                    Err(e) => {
-                        eprintln!(
-                            "Failed to calculate monthly balance for account {account_id}: {e}"
-                        );
-                    }
+                    Some(value) => amount = amount.checked_add(&value)?,
+                    None => add_missing_rate(
+                        &mut missing_rates,
+                        account_id,
+                        &account.name,
+                        value.currency(),
+                    ),
                 }
             }
+            total = total.checked_add(&amount)?;
+            items.push(ExpenseCategory {
+                account_id,
+                name: account.name.clone(),
+                level: nodes[position].level,
+                path: nodes[position].path.clone(),
+                amount,
+                share: Decimal::ZERO,
+                transaction_count: category_transactions.len() as u32,
+                has_children: parent_ids.contains(&account_id),
+            });
         }
 
-        Ok(Money::from_minor_units(total, currency))
+        for item in &mut items {
+            if !total.is_zero() {
+                item.share = (item.amount.to_decimal() / total.to_decimal()).round_dp(4);
+            }
+        }
+        items.sort_by(|a, b| {
+            b.amount
+                .amount_minor()
+                .cmp(&a.amount.amount_minor())
+                .then_with(|| a.path.cmp(&b.path))
+        });
+
+        Ok(ExpenseBreakdown {
+            from,
+            to,
+            parent_id,
+            depth,
+            reporting_currency,
+            total,
+            transaction_count: transactions.len() as u32,
+            categories: items,
+            missing_rates,
+        })
+    }
+
+    /// Get current month income
+    pub async fn get_monthly_income(&self, year: i32, month: u32) -> Result<Money> {
+        self.get_monthly_total_by_account_type(AccountType::Income, year, month)
+            .await
+    }
+
+    /// Get current month expenses  
+    pub async fn get_monthly_expenses(&self, year: i32, month: u32) -> Result<Money> {
+        self.get_monthly_total_by_account_type(AccountType::Expense, year, month)
+            .await
+    }
+
+    /// Get current month income (convenience method using current date)
+    pub async fn get_current_month_income(&self) -> Result<Money> {
+        let now = Local::now();
+        self.get_monthly_income(now.year(), now.month()).await
+    }
+
+    /// Get current month expenses (convenience method using current date)
+    pub async fn get_current_month_expenses(&self) -> Result<Money> {
+        let now = Local::now();
+        self.get_monthly_expenses(now.year(), now.month()).await
+    }
+
+    /// Total income or expenses of a calendar month in the reporting currency,
+    /// valued like the income statement with the rates known on its last day
+    async fn get_monthly_total_by_account_type(
+        &self,
+        account_type: AccountType,
+        year: i32,
+        month: u32,
+    ) -> Result<Money> {
+        let start_date = NaiveDate::from_ymd_opt(year, month, 1)
+            .ok_or_else(|| WalletError::ValidationError("Invalid date".to_string()))?;
+        let end_date = start_date
+            .checked_add_months(Months::new(1))
+            .and_then(|next| next.pred_opt())
+            .ok_or_else(|| WalletError::ValidationError("Invalid date".to_string()))?;
+
+        let reporting_currency = self.get_reporting_currency().await?;
+        let sums = self
+            .account_repository
+            .get_activity_sums_by_type(&account_type, start_date, day_after(end_date)?)
+            .await?;
+        let total = self
+            .value_sums(
+                &account_type,
+                sums,
+                &reporting_currency,
+                end_date,
+                &mut Vec::new(),
+            )
+            .await?;
+        Ok(total.converted)
     }
 
     /// Get recent transactions
     pub async fn get_recent_transactions(&self, limit: u32) -> Result<Vec<crate::Transaction>> {
         let filters = TransactionFilters {
-            account_id: None,
-            from_date: None,
-            to_date: None,
             limit: Some(limit),
-            offset: None,
+            ..Default::default()
         };
 
         self.transaction_service.get_transactions(filters).await
@@ -194,18 +1342,1138 @@ impl ReportService {
         .ok_or_else(|| crate::errors::WalletError::ValidationError("Invalid date".to_string()))?;
 
         let filters = TransactionFilters {
-            account_id: None,
             from_date: Some(start_date),
             to_date: Some(end_date),
-            limit: None,
-            offset: None,
+            ..Default::default()
         };
 
         self.transaction_service.get_transactions(filters).await
     }
 }
 
+/// Rates of one currency over time, read in increasing date order
+#[derive(Clone)]
+struct RateTimeline {
+    rates: Vec<(NaiveDate, Decimal)>,
+    next: usize,
+    current: Option<Decimal>,
+}
+
+impl RateTimeline {
+    /// Rate of the most recent price dated on or before `date`. Dates must not
+    /// go backwards between calls.
+    fn rate_on(&mut self, date: NaiveDate) -> Option<Decimal> {
+        while let Some(&(rate_date, rate)) = self.rates.get(self.next)
+            && rate_date <= date
+        {
+            self.current = Some(rate);
+            self.next += 1;
+        }
+        self.current
+    }
+}
+
+/// Balance of an account in one currency following its normal balance: debits
+/// minus credits for Assets and Expenses, credits minus debits otherwise
+fn normal_balance(account_type: &AccountType, sums: &AccountSums) -> Result<Money> {
+    let debits = Money::from_minor_units(sums.total_debits, sums.currency.clone());
+    let credits = Money::from_minor_units(sums.total_credits, sums.currency.clone());
+    if account_type.is_debit_normal() {
+        debits.checked_sub(&credits)
+    } else {
+        credits.checked_sub(&debits)
+    }
+}
+
+/// Add an amount to the column of an account and of all its parents, so every
+/// account carries the subtotal of its sub-accounts
+fn add_to_parents(
+    nodes: &[AccountNode],
+    positions: &HashMap<i64, usize>,
+    amounts: &mut [Vec<Money>],
+    position: usize,
+    column: usize,
+    value: &Money,
+) -> Result<()> {
+    let mut next = Some(position);
+    while let Some(position) = next {
+        amounts[position][column] = amounts[position][column].checked_add(value)?;
+        next = nodes[position]
+            .account
+            .parent_id
+            .and_then(|parent_id| positions.get(&parent_id).copied());
+    }
+    Ok(())
+}
+
+/// List an account whose balance could not be converted, once per currency
+fn add_missing_rate(
+    missing_rates: &mut Vec<MissingRate>,
+    account_id: i64,
+    account_name: &str,
+    currency: &Currency,
+) {
+    let already_listed = missing_rates
+        .iter()
+        .any(|missing| missing.account_id == account_id && missing.currency == currency.code());
+    if !already_listed {
+        missing_rates.push(MissingRate {
+            account_id,
+            account_name: account_name.to_string(),
+            currency: currency.code().to_string(),
+        });
+    }
+}
+
+/// Column-wise sum of two rows of report amounts
+fn add_columns(left: &[Money], right: &[Money]) -> Result<Vec<Money>> {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| left.checked_add(right))
+        .collect()
+}
+
+/// Column-wise difference of two rows of report amounts
+fn sub_columns(left: &[Money], right: &[Money]) -> Result<Vec<Money>> {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| left.checked_sub(right))
+        .collect()
+}
+
+/// Split `from` to `to` into calendar periods of `interval`, the first and last
+/// ones cut to the range
+fn report_periods(
+    from: NaiveDate,
+    to: NaiveDate,
+    interval: Option<ReportInterval>,
+) -> Result<Vec<ReportPeriod>> {
+    if from > to {
+        return Err(WalletError::ValidationError(
+            "Start date must not be after end date".to_string(),
+        ));
+    }
+    let Some(interval) = interval else {
+        return Ok(vec![ReportPeriod {
+            start: from,
+            end: to,
+        }]);
+    };
+
+    let mut periods = Vec::new();
+    let mut start = from;
+    while start <= to {
+        // First day of the next calendar period, weeks starting on Monday
+        let next = match interval {
+            ReportInterval::Day => start.succ_opt(),
+            ReportInterval::Week => {
+                start.checked_add_days(Days::new(7 - start.weekday().num_days_from_monday() as u64))
+            }
+            ReportInterval::Month | ReportInterval::Quarter | ReportInterval::Year => {
+                let months = match interval {
+                    ReportInterval::Quarter => 3 - (start.month0() % 3),
+                    ReportInterval::Year => 12 - start.month0(),
+                    _ => 1,
+                };
+                start
+                    .with_day(1)
+                    .and_then(|first| first.checked_add_months(Months::new(months)))
+            }
+        }
+        .ok_or_else(|| WalletError::ValidationError("Invalid date".to_string()))?;
+        let end = next
+            .pred_opt()
+            .ok_or_else(|| WalletError::ValidationError("Invalid date".to_string()))?
+            .min(to);
+        periods.push(ReportPeriod { start, end });
+        start = next;
+    }
+    Ok(periods)
+}
+
+/// First day after `date`, used to count transactions up to the end of `date`
+fn day_after(date: NaiveDate) -> Result<NaiveDate> {
+    date.succ_opt()
+        .ok_or_else(|| WalletError::ValidationError("Invalid date".to_string()))
+}
+
 #[cfg(test)]
 mod tests {
-    // TODO: Add tests once we have proper test fixtures
+    use super::*;
+    use crate::services::test_support::{
+        ASSETS, EQUITY, EXPENSES, INCOME, LIABILITIES, create_account, create_account_in, date,
+    };
+    use crate::{
+        AccountService, EntryPrice, EntryType, NewTransaction, PeriodService, TransactionEntryInput,
+    };
+    use rust_decimal::Decimal;
+
+    async fn transfer(db: &Arc<Database>, from: i64, to: i64, amount: Money, on: NaiveDate) {
+        let entries = vec![
+            TransactionEntryInput {
+                account_id: from,
+                amount: amount.clone(),
+                entry_type: EntryType::Credit,
+                description: None,
+                price: None,
+            },
+            TransactionEntryInput {
+                account_id: to,
+                amount,
+                entry_type: EntryType::Debit,
+                description: None,
+                price: None,
+            },
+        ];
+        TransactionService::new(db.clone())
+            .create_transaction(NewTransaction::new("Test".to_string(), on, entries))
+            .await
+            .unwrap();
+    }
+
+    /// Bank with 1000 EUR, wallet with 0.5 BTC, credit card owing 200 EUR
+    async fn setup_mixed_currency_ledger(db: &Arc<Database>) -> (i64, i64, i64) {
+        let bank = create_account(db, "Bank", AccountType::Asset, ASSETS).await;
+        let wallet =
+            create_account_in(db, "Wallet", AccountType::Asset, ASSETS, Currency::btc()).await;
+        let card = create_account(db, "Credit Card", AccountType::Liability, LIABILITIES).await;
+        let opening = create_account(db, "Opening Balances", AccountType::Equity, EQUITY).await;
+        let opening_btc = create_account_in(
+            db,
+            "Opening Balances BTC",
+            AccountType::Equity,
+            EQUITY,
+            Currency::btc(),
+        )
+        .await;
+        let groceries = create_account(db, "Groceries", AccountType::Expense, EXPENSES).await;
+
+        let day = date(2025, 1, 10);
+        transfer(
+            db,
+            opening,
+            bank,
+            Money::from_minor_units(100000, Currency::eur()),
+            day,
+        )
+        .await;
+        transfer(
+            db,
+            opening_btc,
+            wallet,
+            Money::from_minor_units(50000000, Currency::btc()),
+            day,
+        )
+        .await;
+        transfer(
+            db,
+            card,
+            groceries,
+            Money::from_minor_units(20000, Currency::eur()),
+            day,
+        )
+        .await;
+
+        (bank, wallet, card)
+    }
+
+    #[sqlx::test]
+    async fn test_net_worth_report_converts_currencies(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        setup_mixed_currency_ledger(&db).await;
+        PriceService::new(db.clone())
+            .record_price(
+                "BTC",
+                "EUR",
+                date(2025, 1, 1),
+                Decimal::new(40000, 0),
+                "test",
+            )
+            .await
+            .unwrap();
+
+        let report_service = ReportService::new(db);
+        let report = report_service
+            .get_net_worth_report(&Currency::eur(), Some(date(2025, 1, 31)))
+            .await
+            .unwrap();
+
+        // Amounts are kept apart per currency...
+        let assets: Vec<(&str, i64)> = report
+            .total_assets
+            .by_currency
+            .iter()
+            .map(|m| (m.currency().code(), m.amount_minor()))
+            .collect();
+        assert_eq!(assets, vec![("BTC", 50000000), ("EUR", 100000)]);
+
+        // ...and valued in the reporting currency
+        assert_eq!(report.total_assets.converted.amount_minor(), 2100000);
+        assert_eq!(report.total_liabilities.converted.amount_minor(), 20000);
+        assert_eq!(report.net_worth.converted.amount_minor(), 2080000);
+        assert_eq!(report.net_worth.converted.currency().code(), "EUR");
+        assert!(report.missing_rates.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_net_worth_report_lists_missing_rates(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (_, wallet, _) = setup_mixed_currency_ledger(&db).await;
+
+        let report_service = ReportService::new(db);
+        let report = report_service
+            .get_net_worth_report(&Currency::eur(), Some(date(2025, 1, 31)))
+            .await
+            .unwrap();
+
+        assert_eq!(report.total_assets.converted.amount_minor(), 100000);
+        assert_eq!(report.missing_rates.len(), 1);
+        assert_eq!(report.missing_rates[0].account_id, wallet);
+        assert_eq!(report.missing_rates[0].currency, "BTC");
+
+        // Before any transaction everything is zero
+        let report = report_service
+            .get_net_worth_report(&Currency::eur(), Some(date(2025, 1, 9)))
+            .await
+            .unwrap();
+        assert_eq!(report.net_worth.converted.amount_minor(), 0);
+        assert!(report.missing_rates.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_reporting_currency_setting(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let report_service = ReportService::new(db);
+
+        assert_eq!(
+            report_service
+                .get_reporting_currency()
+                .await
+                .unwrap()
+                .code(),
+            "EUR"
+        );
+        report_service.set_reporting_currency("usd").await.unwrap();
+        assert_eq!(
+            report_service
+                .get_reporting_currency()
+                .await
+                .unwrap()
+                .code(),
+            "USD"
+        );
+        assert!(report_service.set_reporting_currency("XYZ").await.is_err());
+
+        // Dashboard totals follow the setting
+        assert_eq!(
+            report_service
+                .get_net_worth()
+                .await
+                .unwrap()
+                .currency()
+                .code(),
+            "USD"
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_trial_balance(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, wallet, card) = setup_mixed_currency_ledger(&db).await;
+        let inactive = create_account(&db, "Old Savings", AccountType::Asset, ASSETS).await;
+        AccountService::new(db.clone())
+            .deactivate_account(inactive)
+            .await
+            .unwrap();
+
+        let report_service = ReportService::new(db);
+        let trial_balance = report_service
+            .trial_balance(date(2025, 1, 31))
+            .await
+            .unwrap();
+
+        assert!(trial_balance.is_balanced);
+        let totals: Vec<(&str, i64, i64)> = trial_balance
+            .totals
+            .iter()
+            .map(|total| {
+                (
+                    total.total_debits.currency().code(),
+                    total.total_debits.amount_minor(),
+                    total.total_credits.amount_minor(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            totals,
+            vec![("BTC", 50000000, 50000000), ("EUR", 120000, 120000)]
+        );
+
+        let line = |account_id: i64| {
+            trial_balance
+                .lines
+                .iter()
+                .find(|line| line.account_id == account_id)
+                .unwrap()
+        };
+        assert_eq!(line(bank).balance.amount_minor(), 100000);
+        assert_eq!(line(wallet).balance.currency().code(), "BTC");
+        assert_eq!(line(card).total_credits.amount_minor(), 20000);
+        assert_eq!(line(card).balance.amount_minor(), -20000);
+        assert!(line(inactive).balance.is_zero());
+        assert_eq!(line(ASSETS).level, 0);
+        assert_eq!(line(bank).path, "Assets > Bank");
+
+        // Nothing is posted before the first transaction
+        let trial_balance = report_service
+            .trial_balance(date(2025, 1, 9))
+            .await
+            .unwrap();
+        assert!(trial_balance.is_balanced);
+        assert!(
+            trial_balance
+                .lines
+                .iter()
+                .all(|line| line.balance.is_zero())
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_reports_with_unknown_entry_currency(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (_, wallet, _) = setup_mixed_currency_ledger(&db).await;
+        sqlx::query("UPDATE transaction_entries SET currency = 'XYZ' WHERE account_id = ?")
+            .bind(wallet)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        // The entries are reported, not left out of the totals
+        let is_unknown_currency = |error: WalletError| {
+            matches!(
+                error,
+                WalletError::CurrencyError(crate::errors::CurrencyError::UnknownCurrency(code))
+                    if code == "XYZ"
+            )
+        };
+        let report_service = ReportService::new(db.clone());
+        assert!(is_unknown_currency(
+            report_service
+                .trial_balance(date(2025, 1, 31))
+                .await
+                .unwrap_err()
+        ));
+        assert!(is_unknown_currency(
+            report_service
+                .balance_sheet(&[date(2025, 1, 31)], &Currency::eur())
+                .await
+                .unwrap_err()
+        ));
+        let register = AccountService::new(db)
+            .get_register(wallet, None, None)
+            .await;
+        assert!(register.is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_trial_balance_with_priced_transfer(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, wallet, _) = setup_mixed_currency_ledger(&db).await;
+
+        // Buy 0.01 BTC for 400 EUR: the BTC entry balances at its EUR price
+        let btc = Money::from_minor_units(1000000, Currency::btc());
+        let price = EntryPrice::new(&btc, Decimal::new(40000, 0), Currency::eur()).unwrap();
+        let entries = vec![
+            TransactionEntryInput {
+                account_id: bank,
+                amount: price.amount.clone(),
+                entry_type: EntryType::Credit,
+                description: None,
+                price: None,
+            },
+            TransactionEntryInput {
+                account_id: wallet,
+                amount: btc,
+                entry_type: EntryType::Debit,
+                description: None,
+                price: Some(price),
+            },
+        ];
+        TransactionService::new(db.clone())
+            .create_transaction(NewTransaction::new(
+                "Buy BTC".to_string(),
+                date(2025, 1, 15),
+                entries,
+            ))
+            .await
+            .unwrap();
+
+        let trial_balance = ReportService::new(db)
+            .trial_balance(date(2025, 1, 31))
+            .await
+            .unwrap();
+        assert!(trial_balance.is_balanced);
+
+        // The totals count the BTC entry at its EUR price, like the check
+        let totals: Vec<(&str, i64, i64)> = trial_balance
+            .totals
+            .iter()
+            .map(|total| {
+                (
+                    total.total_debits.currency().code(),
+                    total.total_debits.amount_minor(),
+                    total.total_credits.amount_minor(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            totals,
+            vec![("BTC", 50000000, 50000000), ("EUR", 160000, 160000)]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_balance_sheet(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, wallet, card) = setup_mixed_currency_ledger(&db).await;
+        let savings = create_account(&db, "Savings", AccountType::Asset, bank).await;
+        transfer(
+            &db,
+            bank,
+            savings,
+            Money::from_minor_units(30000, Currency::eur()),
+            date(2025, 1, 12),
+        )
+        .await;
+
+        // Buy 0.01 BTC for 400 EUR, then BTC goes from 40000 to 50000 EUR
+        let btc = Money::from_minor_units(1000000, Currency::btc());
+        let price = EntryPrice::new(&btc, Decimal::new(40000, 0), Currency::eur()).unwrap();
+        let entries = vec![
+            TransactionEntryInput {
+                account_id: bank,
+                amount: price.amount.clone(),
+                entry_type: EntryType::Credit,
+                description: None,
+                price: None,
+            },
+            TransactionEntryInput {
+                account_id: wallet,
+                amount: btc,
+                entry_type: EntryType::Debit,
+                description: None,
+                price: Some(price),
+            },
+        ];
+        TransactionService::new(db.clone())
+            .create_transaction(NewTransaction::new(
+                "Buy BTC".to_string(),
+                date(2025, 1, 15),
+                entries,
+            ))
+            .await
+            .unwrap();
+        let price_service = PriceService::new(db.clone());
+        for (day, rate) in [(date(2025, 1, 1), 40000), (date(2025, 1, 20), 50000)] {
+            price_service
+                .record_price("BTC", "EUR", day, Decimal::new(rate, 0), "test")
+                .await
+                .unwrap();
+        }
+
+        let report_service = ReportService::new(db);
+        let sheet = report_service
+            .balance_sheet(&[date(2025, 1, 31), date(2025, 1, 9)], &Currency::eur())
+            .await
+            .unwrap();
+
+        let amounts = |section: &BalanceSheetSection, name: &str| -> Vec<i64> {
+            section
+                .lines
+                .iter()
+                .find(|line| line.name == name)
+                .unwrap()
+                .amounts
+                .iter()
+                .map(Money::amount_minor)
+                .collect()
+        };
+        // Parents carry the subtotal of their sub-accounts
+        assert_eq!(amounts(&sheet.assets, "Savings"), vec![30000, 0]);
+        assert_eq!(amounts(&sheet.assets, "Bank"), vec![60000, 0]);
+        assert_eq!(amounts(&sheet.assets, "Wallet"), vec![2550000, 0]);
+        assert_eq!(amounts(&sheet.assets, "Assets"), vec![2610000, 0]);
+        assert_eq!(amounts(&sheet.liabilities, "Credit Card"), vec![20000, 0]);
+
+        // Unclosed groceries expense and the gain on the BTC bought at 40000
+        assert_eq!(amounts(&sheet.equity, "Current earnings"), vec![-20000, 0]);
+        assert_eq!(
+            amounts(&sheet.equity, "Exchange rate differences"),
+            vec![10000, 0]
+        );
+        assert_eq!(amounts(&sheet.equity, "Equity"), vec![2590000, 0]);
+
+        let totals =
+            |amounts: &[Money]| -> Vec<i64> { amounts.iter().map(Money::amount_minor).collect() };
+        assert_eq!(totals(&sheet.assets.totals), vec![2610000, 0]);
+        assert_eq!(
+            totals(&sheet.total_liabilities_and_equity),
+            vec![2610000, 0]
+        );
+        assert!(sheet.missing_rates.is_empty());
+        assert!(
+            sheet
+                .assets
+                .lines
+                .iter()
+                .all(|line| line.account_id != Some(card))
+        );
+
+        assert!(
+            report_service
+                .balance_sheet(&[], &Currency::eur())
+                .await
+                .is_err()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_balance_sheet_revaluation(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let usd = CurrencyService::new(db.clone())
+            .get_currency("USD")
+            .await
+            .unwrap();
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let us_bank =
+            create_account_in(&db, "US Bank", AccountType::Asset, ASSETS, usd.clone()).await;
+        let opening = create_account(&db, "Opening Balances", AccountType::Equity, EQUITY).await;
+        transfer(
+            &db,
+            opening,
+            bank,
+            Money::from_minor_units(100000, Currency::eur()),
+            date(2025, 1, 10),
+        )
+        .await;
+
+        // Buy 110 USD for 100 EUR
+        let dollars = Money::from_minor_units(11000, usd);
+        let price = EntryPrice::new(&dollars, Decimal::new(90909, 5), Currency::eur()).unwrap();
+        let entries = vec![
+            TransactionEntryInput {
+                account_id: bank,
+                amount: price.amount.clone(),
+                entry_type: EntryType::Credit,
+                description: None,
+                price: None,
+            },
+            TransactionEntryInput {
+                account_id: us_bank,
+                amount: dollars,
+                entry_type: EntryType::Debit,
+                description: None,
+                price: Some(price),
+            },
+        ];
+        TransactionService::new(db.clone())
+            .create_transaction(NewTransaction::new(
+                "Buy USD".to_string(),
+                date(2025, 1, 15),
+                entries,
+            ))
+            .await
+            .unwrap();
+
+        let report_service = ReportService::new(db.clone());
+        let minor =
+            |amounts: &[Money]| -> Vec<i64> { amounts.iter().map(Money::amount_minor).collect() };
+
+        // Without a USD rate the dollars are left out rather than made up for
+        let sheet = report_service
+            .balance_sheet(&[date(2025, 1, 31)], &Currency::eur())
+            .await
+            .unwrap();
+        assert_eq!(sheet.missing_rates.len(), 1);
+        assert_eq!(sheet.missing_rates[0].account_id, us_bank);
+        assert_eq!(minor(&sheet.assets.totals), vec![90000]);
+        assert_eq!(minor(&sheet.total_liabilities_and_equity), vec![100000]);
+        assert!(
+            sheet
+                .equity
+                .lines
+                .iter()
+                .all(|line| line.name != "Exchange rate differences")
+        );
+
+        // At 0.95 the dollars bought for 100 EUR are worth 104.50 EUR
+        PriceService::new(db)
+            .record_price("USD", "EUR", date(2025, 1, 20), Decimal::new(95, 2), "test")
+            .await
+            .unwrap();
+        let sheet = report_service
+            .balance_sheet(&[date(2025, 1, 31)], &Currency::eur())
+            .await
+            .unwrap();
+        assert!(sheet.missing_rates.is_empty());
+        let differences = sheet
+            .equity
+            .lines
+            .iter()
+            .find(|line| line.name == "Exchange rate differences")
+            .unwrap();
+        assert_eq!(minor(&differences.amounts), vec![450]);
+        assert_eq!(minor(&sheet.assets.totals), vec![100450]);
+        assert_eq!(minor(&sheet.total_liabilities_and_equity), vec![100450]);
+    }
+
+    #[sqlx::test]
+    async fn test_income_statement(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, _, _) = setup_mixed_currency_ledger(&db).await;
+        let salary = create_account(&db, "Salary", AccountType::Income, INCOME).await;
+        let food = create_account(&db, "Food", AccountType::Expense, EXPENSES).await;
+        let restaurants = create_account(&db, "Restaurants", AccountType::Expense, food).await;
+
+        let eur = |amount_minor| Money::from_minor_units(amount_minor, Currency::eur());
+        transfer(&db, salary, bank, eur(300000), date(2025, 2, 5)).await;
+        transfer(&db, bank, restaurants, eur(5000), date(2025, 2, 10)).await;
+        transfer(&db, salary, bank, eur(100000), date(2025, 4, 5)).await;
+        // The closing transaction must not cancel the year's income and expenses
+        PeriodService::new(db.clone())
+            .close_fiscal_year(date(2025, 12, 31))
+            .await
+            .unwrap();
+
+        let report_service = ReportService::new(db);
+        let minor =
+            |amounts: &[Money]| -> Vec<i64> { amounts.iter().map(Money::amount_minor).collect() };
+        let line = |section: &IncomeStatementSection, account_id: i64| -> Vec<i64> {
+            minor(
+                &section
+                    .lines
+                    .iter()
+                    .find(|line| line.account_id == account_id)
+                    .unwrap()
+                    .amounts,
+            )
+        };
+
+        let statement = report_service
+            .income_statement(
+                date(2025, 1, 1),
+                date(2025, 4, 30),
+                Some(ReportInterval::Month),
+                &Currency::eur(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(statement.periods.len(), 4);
+        assert_eq!(statement.periods[1].start, date(2025, 2, 1));
+        assert_eq!(statement.periods[1].end, date(2025, 2, 28));
+        assert_eq!(line(&statement.income, salary), vec![0, 300000, 0, 100000]);
+        assert_eq!(line(&statement.expenses, restaurants), vec![0, 5000, 0, 0]);
+        assert_eq!(line(&statement.expenses, food), vec![0, 5000, 0, 0]);
+        assert_eq!(line(&statement.expenses, EXPENSES), vec![20000, 5000, 0, 0]);
+        assert_eq!(
+            minor(&statement.net_income),
+            vec![-20000, 295000, 0, 100000]
+        );
+
+        // Quarters are calendar quarters, the first one cut to the range
+        let statement = report_service
+            .income_statement(
+                date(2025, 1, 15),
+                date(2025, 12, 31),
+                Some(ReportInterval::Quarter),
+                &Currency::eur(),
+            )
+            .await
+            .unwrap();
+        let periods: Vec<(NaiveDate, NaiveDate)> = statement
+            .periods
+            .iter()
+            .map(|period| (period.start, period.end))
+            .collect();
+        assert_eq!(
+            periods,
+            vec![
+                (date(2025, 1, 15), date(2025, 3, 31)),
+                (date(2025, 4, 1), date(2025, 6, 30)),
+                (date(2025, 7, 1), date(2025, 9, 30)),
+                (date(2025, 10, 1), date(2025, 12, 31)),
+            ]
+        );
+        assert_eq!(minor(&statement.expenses.totals), vec![5000, 0, 0, 0]);
+        assert_eq!(minor(&statement.net_income), vec![295000, 100000, 0, 0]);
+
+        let statement = report_service
+            .income_statement(date(2025, 1, 1), date(2025, 12, 31), None, &Currency::eur())
+            .await
+            .unwrap();
+        assert_eq!(minor(&statement.income.totals), vec![400000]);
+        assert_eq!(minor(&statement.expenses.totals), vec![25000]);
+        assert_eq!(minor(&statement.net_income), vec![375000]);
+        assert_eq!(
+            report_service
+                .get_monthly_expenses(2025, 2)
+                .await
+                .unwrap()
+                .amount_minor(),
+            5000
+        );
+        let monthly_income = |month| report_service.get_monthly_income(2025, month);
+        assert_eq!(monthly_income(4).await.unwrap().amount_minor(), 100000);
+        // The closing transaction dated December 31 is left out
+        assert!(monthly_income(12).await.unwrap().is_zero());
+
+        assert!(
+            report_service
+                .income_statement(date(2025, 2, 1), date(2025, 1, 1), None, &Currency::eur())
+                .await
+                .is_err()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_cash_flow(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, wallet, card) = setup_mixed_currency_ledger(&db).await;
+        let savings = create_account(&db, "Savings", AccountType::Asset, bank).await;
+        let salary = create_account(&db, "Salary", AccountType::Income, INCOME).await;
+        let rent = create_account(&db, "Rent", AccountType::Expense, EXPENSES).await;
+
+        let eur = |amount_minor| Money::from_minor_units(amount_minor, Currency::eur());
+        transfer(&db, salary, bank, eur(300000), date(2025, 2, 5)).await;
+        // Moving money into a sub-account of a cash account is not a cash flow
+        transfer(&db, bank, savings, eur(30000), date(2025, 2, 10)).await;
+        transfer(&db, bank, card, eur(20000), date(2025, 2, 15)).await;
+        transfer(&db, savings, rent, eur(50000), date(2025, 3, 3)).await;
+
+        // Buying BTC counts at the price paid
+        let btc = Money::from_minor_units(1000000, Currency::btc());
+        let price = EntryPrice::new(&btc, Decimal::new(40000, 0), Currency::eur()).unwrap();
+        let entries = vec![
+            TransactionEntryInput {
+                account_id: bank,
+                amount: price.amount.clone(),
+                entry_type: EntryType::Credit,
+                description: None,
+                price: None,
+            },
+            TransactionEntryInput {
+                account_id: wallet,
+                amount: btc,
+                entry_type: EntryType::Debit,
+                description: None,
+                price: Some(price),
+            },
+        ];
+        TransactionService::new(db.clone())
+            .create_transaction(NewTransaction::new(
+                "Buy BTC".to_string(),
+                date(2025, 2, 20),
+                entries,
+            ))
+            .await
+            .unwrap();
+
+        let report_service = ReportService::new(db);
+        let statement = report_service
+            .cash_flow(
+                &[bank],
+                date(2025, 1, 1),
+                date(2025, 3, 31),
+                Some(ReportInterval::Month),
+                &Currency::eur(),
+            )
+            .await
+            .unwrap();
+
+        let minor =
+            |amounts: &[Money]| -> Vec<i64> { amounts.iter().map(Money::amount_minor).collect() };
+        let line = |category: CashFlowCategory| {
+            let line = statement
+                .lines
+                .iter()
+                .find(|line| line.category == category)
+                .unwrap();
+            (minor(&line.inflows), minor(&line.outflows))
+        };
+        assert_eq!(minor(&statement.opening_cash), vec![0, 100000, 340000]);
+        assert_eq!(
+            line(CashFlowCategory::Equity),
+            (vec![100000, 0, 0], vec![0, 0, 0])
+        );
+        assert_eq!(
+            line(CashFlowCategory::Income),
+            (vec![0, 300000, 0], vec![0, 0, 0])
+        );
+        assert_eq!(
+            line(CashFlowCategory::Liability),
+            (vec![0, 0, 0], vec![0, 20000, 0])
+        );
+        assert_eq!(
+            line(CashFlowCategory::Transfer),
+            (vec![0, 0, 0], vec![0, 40000, 0])
+        );
+        assert_eq!(
+            line(CashFlowCategory::Expense),
+            (vec![0, 0, 0], vec![0, 0, 50000])
+        );
+        assert_eq!(minor(&statement.total_inflows), vec![100000, 300000, 0]);
+        assert_eq!(minor(&statement.total_outflows), vec![0, 60000, 50000]);
+        assert_eq!(minor(&statement.exchange_rate_effect), vec![0, 0, 0]);
+        assert_eq!(minor(&statement.closing_cash), vec![100000, 340000, 290000]);
+
+        assert!(
+            report_service
+                .cash_flow(
+                    &[],
+                    date(2025, 1, 1),
+                    date(2025, 3, 31),
+                    None,
+                    &Currency::eur()
+                )
+                .await
+                .is_err()
+        );
+        assert!(
+            report_service
+                .cash_flow(
+                    &[9999],
+                    date(2025, 1, 1),
+                    date(2025, 3, 31),
+                    None,
+                    &Currency::eur()
+                )
+                .await
+                .is_err()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_cash_flow_exchange_rate_effect(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let usd = CurrencyService::new(db.clone())
+            .get_currency("USD")
+            .await
+            .unwrap();
+        let us_bank =
+            create_account_in(&db, "US Bank", AccountType::Asset, ASSETS, usd.clone()).await;
+        let opening = create_account_in(
+            &db,
+            "Opening Balances",
+            AccountType::Equity,
+            EQUITY,
+            usd.clone(),
+        )
+        .await;
+        let salary =
+            create_account_in(&db, "Salary", AccountType::Income, INCOME, usd.clone()).await;
+
+        let dollars = |amount_minor| Money::from_minor_units(amount_minor, usd.clone());
+        transfer(&db, opening, us_bank, dollars(100000), date(2025, 1, 10)).await;
+        transfer(&db, salary, us_bank, dollars(50000), date(2025, 2, 10)).await;
+        let price_service = PriceService::new(db.clone());
+        for (day, rate) in [
+            (date(2025, 1, 1), Decimal::new(90, 2)),
+            (date(2025, 2, 1), Decimal::new(95, 2)),
+            (date(2025, 2, 20), Decimal::ONE),
+        ] {
+            price_service
+                .record_price("USD", "EUR", day, rate, "test")
+                .await
+                .unwrap();
+        }
+
+        let statement = ReportService::new(db)
+            .cash_flow(
+                &[us_bank],
+                date(2025, 2, 1),
+                date(2025, 2, 28),
+                None,
+                &Currency::eur(),
+            )
+            .await
+            .unwrap();
+
+        let minor =
+            |amounts: &[Money]| -> Vec<i64> { amounts.iter().map(Money::amount_minor).collect() };
+        // 1000 USD at 0.90 on the eve of the period, the salary at 0.95 on its day
+        assert_eq!(minor(&statement.opening_cash), vec![90000]);
+        assert_eq!(minor(&statement.total_inflows), vec![47500]);
+        assert_eq!(minor(&statement.total_outflows), vec![0]);
+        // Both are worth more at the closing rate of 1.00: 100 EUR on the
+        // opening cash and 25 EUR on the salary
+        assert_eq!(minor(&statement.exchange_rate_effect), vec![12500]);
+        assert_eq!(minor(&statement.closing_cash), vec![150000]);
+        assert!(statement.missing_rates.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_net_worth_series(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, _, card) = setup_mixed_currency_ledger(&db).await;
+        transfer(
+            &db,
+            bank,
+            card,
+            Money::from_minor_units(10000, Currency::eur()),
+            date(2025, 1, 22),
+        )
+        .await;
+        let price_service = PriceService::new(db.clone());
+        for (base, quote, day, rate) in [
+            ("BTC", "EUR", date(2025, 1, 1), Decimal::new(40000, 0)),
+            ("BTC", "EUR", date(2025, 1, 20), Decimal::new(50000, 0)),
+            // Recorded the other way round: 1 BTC = 100000 EUR
+            ("EUR", "BTC", date(2025, 2, 1), Decimal::new(1, 5)),
+        ] {
+            price_service
+                .record_price(base, quote, day, rate, "test")
+                .await
+                .unwrap();
+        }
+
+        let report_service = ReportService::new(db);
+        let series = report_service
+            .net_worth_series(date(2025, 1, 8), date(2025, 2, 9), ReportInterval::Week)
+            .await
+            .unwrap();
+
+        let dates: Vec<NaiveDate> = series.points.iter().map(|point| point.date).collect();
+        assert_eq!(
+            dates,
+            vec![
+                date(2025, 1, 12),
+                date(2025, 1, 19),
+                date(2025, 1, 26),
+                date(2025, 2, 2),
+                date(2025, 2, 9),
+            ]
+        );
+        assert_eq!(series.points[0].net_worth.amount_minor(), 2080000);
+        assert_eq!(series.points[3].net_worth.amount_minor(), 5080000);
+        assert!(series.missing_rates.is_empty());
+
+        // Every point matches the single-date report
+        for point in &series.points {
+            let report = report_service
+                .get_net_worth_report(&Currency::eur(), Some(point.date))
+                .await
+                .unwrap();
+            assert_eq!(point.total_assets, report.total_assets.converted);
+            assert_eq!(point.total_liabilities, report.total_liabilities.converted);
+            assert_eq!(point.net_worth, report.net_worth.converted);
+        }
+
+        let series = report_service
+            .net_worth_series(date(2025, 1, 1), date(2025, 1, 31), ReportInterval::Day)
+            .await
+            .unwrap();
+        assert_eq!(series.points.len(), 31);
+        assert!(series.points[8].net_worth.is_zero());
+    }
+
+    #[sqlx::test]
+    async fn test_expense_breakdown(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, _, _) = setup_mixed_currency_ledger(&db).await;
+        let housing = create_account(&db, "Housing", AccountType::Expense, EXPENSES).await;
+        let rent = create_account(&db, "Rent", AccountType::Expense, housing).await;
+        let utilities = create_account(&db, "Utilities", AccountType::Expense, housing).await;
+
+        let eur = |amount_minor| Money::from_minor_units(amount_minor, Currency::eur());
+        transfer(&db, bank, rent, eur(80000), date(2025, 1, 3)).await;
+        transfer(&db, bank, housing, eur(5000), date(2025, 1, 20)).await;
+        // One transaction split over two categories
+        let entry = |account_id, amount_minor, entry_type| TransactionEntryInput {
+            account_id,
+            amount: eur(amount_minor),
+            entry_type,
+            description: None,
+            price: None,
+        };
+        TransactionService::new(db.clone())
+            .create_transaction(NewTransaction::new(
+                "Landlord".to_string(),
+                date(2025, 1, 15),
+                vec![
+                    entry(bank, 15000, EntryType::Credit),
+                    entry(rent, 10000, EntryType::Debit),
+                    entry(utilities, 5000, EntryType::Debit),
+                ],
+            ))
+            .await
+            .unwrap();
+
+        let report_service = ReportService::new(db);
+        let summary = |breakdown: &ExpenseBreakdown| -> Vec<(String, i64, Decimal, u32)> {
+            breakdown
+                .categories
+                .iter()
+                .map(|category| {
+                    (
+                        category.name.clone(),
+                        category.amount.amount_minor(),
+                        category.share,
+                        category.transaction_count,
+                    )
+                })
+                .collect()
+        };
+
+        let breakdown = report_service
+            .expense_breakdown(date(2025, 1, 1), date(2025, 1, 31), 1, None)
+            .await
+            .unwrap();
+        assert_eq!(breakdown.total.amount_minor(), 120000);
+        assert_eq!(breakdown.transaction_count, 4);
+        assert_eq!(
+            summary(&breakdown),
+            vec![
+                ("Housing".to_string(), 100000, Decimal::new(8333, 4), 3),
+                ("Groceries".to_string(), 20000, Decimal::new(1667, 4), 1),
+            ]
+        );
+        assert!(breakdown.categories[0].has_children);
+        assert!(!breakdown.categories[1].has_children);
+
+        // Drilling into Housing keeps what was booked on Housing itself
+        let breakdown = report_service
+            .expense_breakdown(date(2025, 1, 1), date(2025, 1, 31), 1, Some(housing))
+            .await
+            .unwrap();
+        assert_eq!(breakdown.total.amount_minor(), 100000);
+        assert_eq!(breakdown.transaction_count, 3);
+        assert_eq!(
+            summary(&breakdown),
+            vec![
+                ("Rent".to_string(), 90000, Decimal::new(9, 1), 2),
+                ("Housing".to_string(), 5000, Decimal::new(5, 2), 1),
+                ("Utilities".to_string(), 5000, Decimal::new(5, 2), 1),
+            ]
+        );
+
+        let breakdown = report_service
+            .expense_breakdown(date(2025, 1, 4), date(2025, 1, 31), 2, None)
+            .await
+            .unwrap();
+        assert_eq!(breakdown.total.amount_minor(), 40000);
+        assert_eq!(breakdown.categories.len(), 4);
+
+        assert!(
+            report_service
+                .expense_breakdown(date(2025, 1, 1), date(2025, 1, 31), 1, Some(bank))
+                .await
+                .is_err()
+        );
+        assert!(
+            report_service
+                .expense_breakdown(date(2025, 1, 1), date(2025, 1, 31), 0, None)
+                .await
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,46 @@
+use chrono::NaiveDate;
+use std::sync::Arc;
+
+use crate::db::connection::Database;
+use crate::{AccountService, AccountType, Currency, Money};
+
+// Root accounts created by the initial migration
+pub(crate) const ASSETS: i64 = 1;
+pub(crate) const LIABILITIES: i64 = 2;
+pub(crate) const EQUITY: i64 = 3;
+pub(crate) const INCOME: i64 = 4;
+pub(crate) const EXPENSES: i64 = 5;
+
+pub(crate) fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+pub(crate) fn eur(amount_minor: i64) -> Money {
+    Money::from_minor_units(amount_minor, Currency::eur())
+}
+
+/// Creates an EUR account and returns its id
+pub(crate) async fn create_account(
+    db: &Arc<Database>,
+    name: &str,
+    account_type: AccountType,
+    parent_id: i64,
+) -> i64 {
+    create_account_in(db, name, account_type, parent_id, Currency::eur()).await
+}
+
+/// Creates an account in `currency` and returns its id
+pub(crate) async fn create_account_in(
+    db: &Arc<Database>,
+    name: &str,
+    account_type: AccountType,
+    parent_id: i64,
+    currency: Currency,
+) -> i64 {
+    AccountService::new(db.clone())
+        .create_account(name.to_string(), account_type, Some(parent_id), currency)
+        .await
+        .unwrap()
+        .id
+        .unwrap()
+}
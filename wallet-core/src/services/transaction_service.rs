@@ -1,51 +1,245 @@
-use chrono::NaiveDate;
+use chrono::{Local, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
+use crate::db::accounts::AccountRepository;
 use crate::db::connection::Database;
 use crate::db::transactions::TransactionRepository;
-use crate::errors::Result;
-use crate::{Money, Transaction};
+use crate::errors::{MoneyError, Result, WalletError};
+use crate::{
+    EntryPrice, EntryType, Money, Transaction, TransactionCursor, TransactionEntry,
+    TransactionPage, TransactionSearchHit,
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
 pub struct TransactionEntryInput {
     pub account_id: i64,
     pub amount: Money,
     pub entry_type: crate::EntryType,
     pub description: Option<String>,
+    pub price: Option<EntryPrice>,
+}
+
+impl TransactionEntryInput {
+    /// Amount this entry contributes to the transaction balance: the converted
+    /// amount when the entry carries a price, the entry amount otherwise
+    pub fn balance_amount(&self) -> &Money {
+        self.price
+            .as_ref()
+            .map(|price| &price.amount)
+            .unwrap_or(&self.amount)
+    }
+}
+
+impl From<TransactionEntry> for TransactionEntryInput {
+    fn from(entry: TransactionEntry) -> Self {
+        Self {
+            account_id: entry.account_id,
+            amount: entry.amount,
+            entry_type: entry.entry_type,
+            description: entry.description,
+            price: entry.price,
+        }
+    }
 }
 
+/// Everything needed to record a new transaction
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct NewTransaction {
+    pub description: String,
+    pub transaction_date: NaiveDate,
+    #[serde(default)]
+    pub reference: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Identifier in the source system, unique across the ledger
+    #[serde(default)]
+    pub external_id: Option<String>,
+    pub entries: Vec<TransactionEntryInput>,
+}
+
+impl NewTransaction {
+    pub fn new(
+        description: String,
+        transaction_date: NaiveDate,
+        entries: Vec<TransactionEntryInput>,
+    ) -> Self {
+        Self {
+            description,
+            transaction_date,
+            reference: None,
+            notes: None,
+            tags: Vec::new(),
+            external_id: None,
+            entries,
+        }
+    }
+}
+
+/// Number of search results returned when no limit is given
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+
+/// How transactions are matched against several accounts
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type,
+)]
+pub enum AccountMatch {
+    /// Touches at least one of the accounts
+    #[default]
+    Any,
+    /// Touches every one of the accounts
+    All,
+}
+
+/// Order of a transaction listing. Ties are broken by id, newest first.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type,
+)]
+pub enum TransactionSort {
+    #[default]
+    DateDesc,
+    DateAsc,
+    /// By the sum of the debit entries, in entry currency units
+    AmountDesc,
+    AmountAsc,
+    Description,
+}
+
+impl TransactionSort {
+    /// Whether the listing can be paged with a date/id cursor
+    pub fn is_by_date(self) -> bool {
+        matches!(self, TransactionSort::DateDesc | TransactionSort::DateAsc)
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(default)]
 pub struct TransactionFilters {
     pub account_id: Option<i64>,
+    /// Further accounts, combined with `account_id` according to `account_match`
+    pub account_ids: Vec<i64>,
+    pub account_match: AccountMatch,
+    /// Let each account also match through any of its descendants
+    pub include_descendants: bool,
+    pub tag: Option<String>,
+    /// Further tags; a transaction must carry all of them
+    pub tags: Vec<String>,
+    /// Case-insensitive text in the description, notes or reference
+    pub text: Option<String>,
+    /// Bounds on an entry amount, in the entry's currency units. With accounts
+    /// selected only their entries count, as do `entry_type` and `currency`.
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+    pub entry_type: Option<EntryType>,
+    pub currency: Option<String>,
     pub from_date: Option<NaiveDate>,
     pub to_date: Option<NaiveDate>,
+    pub sort: TransactionSort,
     pub limit: Option<u32>,
     pub offset: Option<u32>,
+    /// Only list transactions after this position, for keyset pagination
+    pub cursor: Option<TransactionCursor>,
 }
 
 pub struct TransactionService {
     repository: TransactionRepository,
+    account_repository: AccountRepository,
 }
 
 impl TransactionService {
     pub fn new(db: Arc<Database>) -> Self {
         Self {
-            repository: TransactionRepository::new(db),
+            repository: TransactionRepository::new(db.clone()),
+            account_repository: AccountRepository::new(db),
         }
     }
 
     // Core transaction operations
     pub async fn create_transaction(
         &self,
-        description: String,
-        transaction_date: NaiveDate,
-        entries: Vec<TransactionEntryInput>,
+        mut new_transaction: NewTransaction,
     ) -> Result<Transaction> {
         // Validate transaction balance before creating
+        Self::validate_transaction_balance(&new_transaction.entries)?;
+
+        new_transaction.tags = Self::normalize_tags(new_transaction.tags);
+        if let Some(external_id) = &new_transaction.external_id
+            && let Some(existing_id) = self
+                .repository
+                .get_transaction_id_by_external_id(external_id)
+                .await?
+        {
+            return Err(WalletError::ValidationError(format!(
+                "External id {external_id} is already used by transaction {existing_id}"
+            )));
+        }
+
+        self.repository
+            .create_transaction(new_transaction, None)
+            .await
+    }
+
+    /// Replace the description, date, reference, notes, tags and entries of an
+    /// existing transaction. Its external id is kept, as it ties the transaction
+    /// to the statement line it was imported from.
+    pub async fn update_transaction(
+        &self,
+        id: i64,
+        mut transaction: NewTransaction,
+    ) -> Result<Transaction> {
+        Self::validate_transaction_balance(&transaction.entries)?;
+        transaction.tags = Self::normalize_tags(transaction.tags);
+
+        self.repository.update_transaction(id, transaction).await
+    }
+
+    pub async fn delete_transaction(&self, id: i64) -> Result<()> {
+        self.repository.delete_transaction(id).await
+    }
+
+    /// Post a mirror-image transaction dated today that cancels out the original
+    pub async fn reverse_transaction(&self, id: i64) -> Result<Transaction> {
+        let original = self.repository.get_transaction(id).await?;
+
+        if original.reverses_transaction_id.is_some() {
+            return Err(WalletError::ValidationError(
+                "A reversal cannot itself be reversed".to_string(),
+            ));
+        }
+        if let Some(reversal_id) = self.repository.get_reversal_id(id).await? {
+            return Err(WalletError::ValidationError(format!(
+                "Transaction {id} is already reversed by transaction {reversal_id}"
+            )));
+        }
+
+        let entries: Vec<TransactionEntryInput> = original
+            .entries
+            .into_iter()
+            .map(|entry| TransactionEntryInput {
+                account_id: entry.account_id,
+                amount: entry.amount,
+                entry_type: match entry.entry_type {
+                    EntryType::Debit => EntryType::Credit,
+                    EntryType::Credit => EntryType::Debit,
+                },
+                description: entry.description,
+                price: entry.price,
+            })
+            .collect();
         Self::validate_transaction_balance(&entries)?;
 
         self.repository
-            .create_transaction(description, transaction_date, entries)
+            .create_transaction(
+                NewTransaction::new(
+                    format!("Reversal of: {}", original.description),
+                    Local::now().date_naive(),
+                    entries,
+                ),
+                Some(id),
+            )
             .await
     }
 
@@ -54,21 +248,114 @@ impl TransactionService {
     }
 
     pub async fn get_transactions(&self, filters: TransactionFilters) -> Result<Vec<Transaction>> {
+        if filters.cursor.is_some() && !filters.sort.is_by_date() {
+            return Err(WalletError::ValidationError(
+                "A cursor can only be used when sorting by date".to_string(),
+            ));
+        }
+        if let (Some(min), Some(max)) = (filters.min_amount, filters.max_amount)
+            && min > max
+        {
+            return Err(WalletError::ValidationError(
+                "Minimum amount is above the maximum amount".to_string(),
+            ));
+        }
+
+        // Each selected account becomes a group of ids that all count as that account
+        let mut account_groups = Vec::new();
+        for account_id in filters.account_id.iter().chain(&filters.account_ids) {
+            let group = if filters.include_descendants {
+                self.account_repository
+                    .get_descendant_account_ids(*account_id)
+                    .await?
+            } else {
+                vec![*account_id]
+            };
+            account_groups.push(group);
+        }
+
         self.repository
-            .get_transactions(
-                filters.account_id,
-                filters.from_date,
-                filters.to_date,
-                filters.limit,
-                filters.offset,
-            )
+            .get_transactions(&filters, &account_groups)
+            .await
+    }
+
+    /// One page of transactions in date order. Pass the returned cursor back in
+    /// the filters to get the next page; unlike an offset it stays cheap deep
+    /// into the history and does not skip or repeat rows when transactions are
+    /// added meanwhile.
+    pub async fn get_transaction_page(
+        &self,
+        filters: TransactionFilters,
+    ) -> Result<TransactionPage> {
+        let limit = filters.limit.ok_or_else(|| {
+            WalletError::ValidationError("A page size (limit) is required".to_string())
+        })?;
+        if filters.offset.is_some() {
+            return Err(WalletError::ValidationError(
+                "Use either a cursor or an offset".to_string(),
+            ));
+        }
+        if !filters.sort.is_by_date() {
+            return Err(WalletError::ValidationError(
+                "Pages can only be sorted by date".to_string(),
+            ));
+        }
+
+        let transactions = self.get_transactions(filters).await?;
+        let next_cursor = if transactions.len() == limit as usize {
+            transactions.last().and_then(|transaction| {
+                transaction.id.map(|id| TransactionCursor {
+                    transaction_date: transaction.transaction_date,
+                    id,
+                })
+            })
+        } else {
+            None
+        };
+        Ok(TransactionPage {
+            transactions,
+            next_cursor,
+        })
+    }
+
+    /// Full-text search over descriptions, notes, references, tags and entry
+    /// descriptions, best matches first. Every word of the query must match
+    /// the start of a word in the text, so results narrow as the user types;
+    /// FTS5 operators in the query are taken literally.
+    pub async fn search(
+        &self,
+        query: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<TransactionSearchHit>> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+            .collect();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.repository
+            .search(&terms.join(" "), limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
             .await
     }
 
+    /// Tags in use across all transactions
+    pub async fn get_tags(&self) -> Result<Vec<String>> {
+        self.repository.get_tags().await
+    }
+
+    /// Trim tags, drop empty ones and remove duplicates
+    fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+        let tags: BTreeSet<String> = tags
+            .into_iter()
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        tags.into_iter().collect()
+    }
+
     // Transaction validation
     pub fn validate_transaction_balance(entries: &[TransactionEntryInput]) -> Result<()> {
-        use crate::errors::WalletError;
-
         // Must have at least 2 entries
         if entries.len() < 2 {
             return Err(WalletError::ValidationError(
@@ -83,34 +370,66 @@ impl TransactionService {
             ));
         }
 
-        // Validate all currencies are the same (MVP limitation)
-        let first_currency = &entries[0].amount.currency().code();
-        if entries
-            .iter()
-            .any(|e| e.amount.currency().code() != *first_currency)
-        {
-            return Err(WalletError::ValidationError(
-                "Multi-currency transactions not supported yet".to_string(),
-            ));
+        // Validate conversions into a second currency
+        for entry in entries.iter() {
+            if let Some(price) = &entry.price {
+                if price.rate <= rust_decimal::Decimal::ZERO || price.amount.amount_minor() <= 0 {
+                    return Err(WalletError::ValidationError(
+                        "Exchange rates and converted amounts must be positive".to_string(),
+                    ));
+                }
+                if price.amount.currency().code() == entry.amount.currency().code() {
+                    return Err(WalletError::ValidationError(format!(
+                        "Price currency must differ from the entry currency {}",
+                        entry.amount.currency().code()
+                    )));
+                }
+                // The converted amount may be rounded either way, but by less
+                // than one minor unit of the price currency
+                let exact = entry
+                    .amount
+                    .to_decimal()
+                    .checked_mul(price.rate)
+                    .ok_or(MoneyError::Overflow)?;
+                let minor_unit =
+                    Decimal::new(1, u32::from(price.amount.currency().minor_unit_scale()));
+                if (price.amount.to_decimal() - exact).abs() >= minor_unit {
+                    return Err(WalletError::ValidationError(format!(
+                        "Converted amount {} {} does not match {} {} @ {}",
+                        price.amount.to_decimal(),
+                        price.amount.currency().code(),
+                        entry.amount.to_decimal(),
+                        entry.amount.currency().code(),
+                        price.rate
+                    )));
+                }
+            }
         }
 
-        // Calculate total debits and credits
-        let total_debits: i64 = entries
-            .iter()
-            .filter(|e| matches!(e.entry_type, crate::EntryType::Debit))
-            .map(|e| e.amount.amount_minor())
-            .sum();
-
-        let total_credits: i64 = entries
-            .iter()
-            .filter(|e| matches!(e.entry_type, crate::EntryType::Credit))
-            .map(|e| e.amount.amount_minor())
-            .sum();
+        // Calculate total debits and credits per currency, after conversion
+        let mut totals: BTreeMap<&str, (Money, Money)> = BTreeMap::new();
+        for entry in entries {
+            let amount = entry.balance_amount();
+            let (debits, credits) = totals.entry(amount.currency().code()).or_insert_with(|| {
+                (
+                    Money::zero(amount.currency().clone()),
+                    Money::zero(amount.currency().clone()),
+                )
+            });
+            match entry.entry_type {
+                crate::EntryType::Debit => *debits = debits.checked_add(amount)?,
+                crate::EntryType::Credit => *credits = credits.checked_add(amount)?,
+            }
+        }
 
-        if total_debits != total_credits {
-            return Err(WalletError::ValidationError(format!(
-                "Transaction is not balanced: debits={total_debits}, credits={total_credits}"
-            )));
+        for (currency, (total_debits, total_credits)) in totals {
+            if total_debits != total_credits {
+                return Err(WalletError::ValidationError(format!(
+                    "Transaction is not balanced in {currency}: debits={}, credits={}",
+                    total_debits.amount_minor(),
+                    total_credits.amount_minor()
+                )));
+            }
         }
 
         Ok(())
@@ -131,23 +450,31 @@ impl TransactionService {
                 amount: amount.clone(),
                 entry_type: crate::EntryType::Credit, // Money leaves the FROM account
                 description: None,
+                price: None,
             },
             TransactionEntryInput {
                 account_id: to_account_id,
                 amount,
                 entry_type: crate::EntryType::Debit, // Money enters the TO account
                 description: None,
+                price: None,
             },
         ];
 
-        self.create_transaction(description, date, entries).await
+        self.create_transaction(NewTransaction::new(description, date, entries))
+            .await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Currency, EntryType, Money};
+    use crate::services::test_support::{
+        ASSETS, EXPENSES, create_account, create_account_in, date, eur,
+    };
+    use crate::{AccountService, AccountType, Currency, EntryType, Money, SnippetSegment};
+    use rust_decimal::Decimal;
+    use std::time::Instant;
 
     #[test]
     fn test_validate_transaction_balance_success() {
@@ -160,12 +487,14 @@ mod tests {
                 amount: amount.clone(),
                 entry_type: EntryType::Credit,
                 description: None,
+                price: None,
             },
             TransactionEntryInput {
                 account_id: 2,
                 amount,
                 entry_type: EntryType::Debit,
                 description: None,
+                price: None,
             },
         ];
 
@@ -184,12 +513,14 @@ mod tests {
                 amount: amount1,
                 entry_type: EntryType::Credit,
                 description: None,
+                price: None,
             },
             TransactionEntryInput {
                 account_id: 2,
                 amount: amount2,
                 entry_type: EntryType::Debit,
                 description: None,
+                price: None,
             },
         ];
 
@@ -208,6 +539,7 @@ mod tests {
             amount,
             entry_type: EntryType::Credit,
             description: None,
+            price: None,
         }];
 
         let result = TransactionService::validate_transaction_balance(&entries);
@@ -232,12 +564,14 @@ mod tests {
                 amount,
                 entry_type: EntryType::Credit,
                 description: None,
+                price: None,
             },
             TransactionEntryInput {
                 account_id: 2,
                 amount: amount2,
                 entry_type: EntryType::Debit,
                 description: None,
+                price: None,
             },
         ];
 
@@ -245,4 +579,870 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("must be positive"));
     }
+
+    #[test]
+    fn test_validate_transaction_balance_mixed_currencies_without_price() {
+        let eur = Money::from_minor_units(1000, Currency::eur());
+        let btc = Money::from_minor_units(1000, Currency::btc());
+
+        let entries = vec![
+            TransactionEntryInput {
+                account_id: 1,
+                amount: eur,
+                entry_type: EntryType::Credit,
+                description: None,
+                price: None,
+            },
+            TransactionEntryInput {
+                account_id: 2,
+                amount: btc,
+                entry_type: EntryType::Debit,
+                description: None,
+                price: None,
+            },
+        ];
+
+        let result = TransactionService::validate_transaction_balance(&entries);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not balanced in"));
+    }
+
+    #[test]
+    fn test_validate_transaction_balance_with_price() {
+        // Buy 0.002 BTC paying 120.00 EUR @ 0.00001666666 BTC
+        let eur = Money::from_minor_units(12000, Currency::eur());
+        let price = EntryPrice::new(&eur, Decimal::new(166666666, 13), Currency::btc()).unwrap();
+        assert_eq!(price.amount.amount_minor(), 200000);
+
+        let entries = vec![
+            TransactionEntryInput {
+                account_id: 1,
+                amount: eur,
+                entry_type: EntryType::Credit,
+                description: None,
+                price: Some(price),
+            },
+            TransactionEntryInput {
+                account_id: 2,
+                amount: Money::from_minor_units(200000, Currency::btc()),
+                entry_type: EntryType::Debit,
+                description: None,
+                price: None,
+            },
+        ];
+
+        assert!(TransactionService::validate_transaction_balance(&entries).is_ok());
+
+        // A converted amount that does not follow from the rate is rejected
+        let mut inconsistent = entries.clone();
+        if let Some(price) = inconsistent[0].price.as_mut() {
+            price.amount = Money::from_minor_units(210000, Currency::btc());
+        }
+        inconsistent[1].amount = Money::from_minor_units(210000, Currency::btc());
+        assert!(TransactionService::validate_transaction_balance(&inconsistent).is_err());
+    }
+
+    #[test]
+    fn test_entry_price_rejects_same_currency() {
+        let eur = Money::from_minor_units(1000, Currency::eur());
+        assert!(EntryPrice::new(&eur, Decimal::new(15, 1), Currency::eur()).is_err());
+        assert!(EntryPrice::new(&eur, Decimal::NEGATIVE_ONE, Currency::btc()).is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_create_transaction_with_price_roundtrip(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let wallet =
+            create_account_in(&db, "Wallet", AccountType::Asset, ASSETS, Currency::btc()).await;
+        let transaction_service = TransactionService::new(db);
+
+        let eur = Money::from_minor_units(12000, Currency::eur());
+        let price = EntryPrice::new(&eur, Decimal::new(166666666, 13), Currency::btc()).unwrap();
+        let entries = vec![
+            TransactionEntryInput {
+                account_id: bank,
+                amount: eur,
+                entry_type: EntryType::Credit,
+                description: None,
+                price: Some(price.clone()),
+            },
+            TransactionEntryInput {
+                account_id: wallet,
+                amount: Money::from_minor_units(200000, Currency::btc()),
+                entry_type: EntryType::Debit,
+                description: None,
+                price: None,
+            },
+        ];
+
+        let created = transaction_service
+            .create_transaction(NewTransaction::new(
+                "Buy bitcoin".to_string(),
+                date(2025, 7, 6),
+                entries,
+            ))
+            .await
+            .unwrap();
+
+        let loaded = transaction_service
+            .get_transaction(created.id.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(loaded.entries[0].price, Some(price));
+        assert_eq!(loaded.entries[1].price, None);
+    }
+
+    #[sqlx::test]
+    async fn test_entries_must_use_account_currency(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, groceries, transaction) = setup_purchase(&db).await;
+        let transaction_service = TransactionService::new(db.clone());
+        let usd = crate::CurrencyService::new(db)
+            .get_currency("USD")
+            .await
+            .unwrap();
+
+        // 10 USD spent from the EUR bank account, balanced in USD
+        let dollars = Money::from_minor_units(1000, usd.clone());
+        let mut foreign = entries(bank, groceries, 0, 0);
+        for entry in &mut foreign {
+            entry.amount = dollars.clone();
+        }
+        let error = transaction_service
+            .create_transaction(NewTransaction::new(
+                "Souvenir".to_string(),
+                date(2025, 7, 3),
+                foreign.clone(),
+            ))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("USD"));
+        assert!(
+            transaction_service
+                .update_transaction(
+                    transaction.id.unwrap(),
+                    NewTransaction::new("Supermarket".to_string(), date(2025, 7, 1), foreign,),
+                )
+                .await
+                .is_err()
+        );
+
+        // The same purchase in the account currency, with the dollar amount as its price
+        let euros = eur(920);
+        let price = EntryPrice::new(&euros, Decimal::new(10870, 3), usd).unwrap();
+        let mut priced = entries(bank, groceries, 920, 920);
+        for entry in &mut priced {
+            entry.price = Some(price.clone());
+        }
+        assert!(
+            transaction_service
+                .create_transaction(NewTransaction::new(
+                    "Souvenir".to_string(),
+                    date(2025, 7, 3),
+                    priced,
+                ))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_entries_with_unknown_currency(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool: pool.clone() });
+        let (_, _, transaction) = setup_purchase(&db).await;
+        let transaction_service = TransactionService::new(db);
+        let id = transaction.id.unwrap();
+        let is_unknown_currency = |error: WalletError| {
+            matches!(
+                error,
+                WalletError::CurrencyError(crate::errors::CurrencyError::UnknownCurrency(code))
+                    if code == "XYZ"
+            )
+        };
+
+        // An entry amount in a currency missing from the registry
+        sqlx::query("UPDATE transaction_entries SET currency = 'XYZ' WHERE transaction_id = ?")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(is_unknown_currency(
+            transaction_service.get_transaction(id).await.unwrap_err()
+        ));
+        assert!(is_unknown_currency(
+            transaction_service
+                .get_transactions(TransactionFilters::default())
+                .await
+                .unwrap_err()
+        ));
+
+        // A price in a currency missing from the registry
+        sqlx::query(
+            r#"
+            UPDATE transaction_entries
+            SET currency = 'EUR', price_rate = '1.1', price_currency = 'XYZ', price_amount_minor = 5500
+            WHERE transaction_id = ?
+            "#,
+        )
+        .bind(id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert!(is_unknown_currency(
+            transaction_service.get_transaction(id).await.unwrap_err()
+        ));
+    }
+
+    /// Bank and groceries accounts in EUR with one 50.00 purchase between them
+    async fn setup_purchase(db: &Arc<Database>) -> (i64, i64, Transaction) {
+        let bank = create_account(db, "Bank", AccountType::Asset, ASSETS).await;
+        let groceries = create_account(db, "Groceries", AccountType::Expense, EXPENSES).await;
+
+        let transaction = TransactionService::new(db.clone())
+            .create_simple_transaction(
+                "Supermarket".to_string(),
+                date(2025, 7, 1),
+                eur(5000),
+                bank,
+                groceries,
+            )
+            .await
+            .unwrap();
+
+        (bank, groceries, transaction)
+    }
+
+    fn entries(from: i64, to: i64, credit: i64, debit: i64) -> Vec<TransactionEntryInput> {
+        vec![
+            TransactionEntryInput {
+                account_id: from,
+                amount: eur(credit),
+                entry_type: EntryType::Credit,
+                description: None,
+                price: None,
+            },
+            TransactionEntryInput {
+                account_id: to,
+                amount: eur(debit),
+                entry_type: EntryType::Debit,
+                description: None,
+                price: None,
+            },
+        ]
+    }
+
+    #[sqlx::test]
+    async fn test_update_transaction(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, groceries, transaction) = setup_purchase(&db).await;
+        let transaction_service = TransactionService::new(db.clone());
+        let id = transaction.id.unwrap();
+
+        let mut correction = NewTransaction::new(
+            "Supermarket (corrected)".to_string(),
+            date(2025, 7, 2),
+            entries(bank, groceries, 4500, 4500),
+        );
+        correction.reference = Some("R-42".to_string());
+        correction.notes = Some("Receipt in the drawer".to_string());
+        correction.tags = vec!["food".to_string(), " weekly ".to_string()];
+        let updated = transaction_service
+            .update_transaction(id, correction.clone())
+            .await
+            .unwrap();
+        assert_eq!(updated.description, "Supermarket (corrected)");
+        assert_eq!(updated.entries.len(), 2);
+        assert_eq!(updated.entries[0].amount.amount_minor(), 4500);
+        assert_eq!(updated.reference.as_deref(), Some("R-42"));
+        assert_eq!(updated.notes.as_deref(), Some("Receipt in the drawer"));
+        assert_eq!(updated.tags, vec!["food", "weekly"]);
+
+        // Tags are replaced, not added to
+        correction.tags = vec!["household".to_string()];
+        correction.notes = None;
+        let updated = transaction_service
+            .update_transaction(id, correction)
+            .await
+            .unwrap();
+        assert_eq!(updated.tags, vec!["household"]);
+        assert_eq!(updated.notes, None);
+
+        let balance = AccountService::new(db)
+            .calculate_balance(bank)
+            .await
+            .unwrap();
+        assert_eq!(balance.amount_minor(), -4500);
+    }
+
+    #[sqlx::test]
+    async fn test_update_transaction_rejects_unbalanced_entries(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, groceries, transaction) = setup_purchase(&db).await;
+        let transaction_service = TransactionService::new(db);
+        let id = transaction.id.unwrap();
+
+        let result = transaction_service
+            .update_transaction(
+                id,
+                NewTransaction::new(
+                    "Broken".to_string(),
+                    date(2025, 7, 2),
+                    entries(bank, groceries, 4500, 5000),
+                ),
+            )
+            .await;
+        assert!(result.is_err());
+
+        // The original transaction is left untouched
+        let loaded = transaction_service.get_transaction(id).await.unwrap();
+        assert_eq!(loaded.description, "Supermarket");
+        assert_eq!(loaded.entries[0].amount.amount_minor(), 5000);
+
+        // Updating a missing transaction fails
+        assert!(
+            transaction_service
+                .update_transaction(
+                    id + 100,
+                    NewTransaction::new(
+                        "Missing".to_string(),
+                        date(2025, 7, 2),
+                        entries(bank, groceries, 100, 100)
+                    ),
+                )
+                .await
+                .is_err()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_delete_transaction(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, _, transaction) = setup_purchase(&db).await;
+        let transaction_service = TransactionService::new(db.clone());
+        let id = transaction.id.unwrap();
+
+        transaction_service.delete_transaction(id).await.unwrap();
+
+        assert!(transaction_service.get_transaction(id).await.is_err());
+        assert!(transaction_service.delete_transaction(id).await.is_err());
+        let balance = AccountService::new(db)
+            .calculate_balance(bank)
+            .await
+            .unwrap();
+        assert!(balance.is_zero());
+    }
+
+    #[sqlx::test]
+    async fn test_reverse_transaction(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, groceries, transaction) = setup_purchase(&db).await;
+        let transaction_service = TransactionService::new(db.clone());
+        let id = transaction.id.unwrap();
+
+        let reversal = transaction_service.reverse_transaction(id).await.unwrap();
+        assert_eq!(reversal.description, "Reversal of: Supermarket");
+        assert_eq!(reversal.reverses_transaction_id, Some(id));
+        assert_eq!(reversal.transaction_date, Local::now().date_naive());
+        assert_eq!(reversal.entries[0].account_id, bank);
+        assert_eq!(reversal.entries[0].entry_type, EntryType::Debit);
+        assert_eq!(reversal.entries[1].account_id, groceries);
+        assert_eq!(reversal.entries[1].entry_type, EntryType::Credit);
+
+        // Both accounts are back to zero
+        let account_service = AccountService::new(db);
+        for account_id in [bank, groceries] {
+            let balance = account_service.calculate_balance(account_id).await.unwrap();
+            assert!(balance.is_zero());
+        }
+
+        // Neither the original nor the reversal can be reversed again
+        assert!(transaction_service.reverse_transaction(id).await.is_err());
+        assert!(
+            transaction_service
+                .reverse_transaction(reversal.id.unwrap())
+                .await
+                .is_err()
+        );
+
+        // The original stays as long as its reversal refers to it
+        let error = transaction_service
+            .delete_transaction(id)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("reversed by"));
+        assert!(transaction_service.get_transaction(id).await.is_ok());
+        transaction_service
+            .delete_transaction(reversal.id.unwrap())
+            .await
+            .unwrap();
+        transaction_service.delete_transaction(id).await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_create_transaction_with_details(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, groceries, _) = setup_purchase(&db).await;
+        let transaction_service = TransactionService::new(db);
+
+        let mut new_transaction = NewTransaction::new(
+            "Farmers market".to_string(),
+            date(2025, 7, 3),
+            entries(bank, groceries, 1200, 1200),
+        );
+        new_transaction.reference = Some("RCPT-42".to_string());
+        new_transaction.notes = Some("Paid by card".to_string());
+        new_transaction.tags = vec![
+            " food ".to_string(),
+            "market".to_string(),
+            "food".to_string(),
+            "".to_string(),
+        ];
+        new_transaction.external_id = Some("bank:2025-07-03:1".to_string());
+        new_transaction.entries[1].description = Some("Vegetables".to_string());
+
+        let created = transaction_service
+            .create_transaction(new_transaction.clone())
+            .await
+            .unwrap();
+        let loaded = transaction_service
+            .get_transaction(created.id.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(loaded.reference.as_deref(), Some("RCPT-42"));
+        assert_eq!(loaded.notes.as_deref(), Some("Paid by card"));
+        assert_eq!(loaded.tags, vec!["food", "market"]);
+        assert_eq!(loaded.external_id.as_deref(), Some("bank:2025-07-03:1"));
+        assert_eq!(loaded.entries[1].description.as_deref(), Some("Vegetables"));
+        assert_eq!(created.tags, loaded.tags);
+
+        // External ids are unique
+        assert!(
+            transaction_service
+                .create_transaction(new_transaction)
+                .await
+                .is_err()
+        );
+
+        // Filter by tag
+        let filters = TransactionFilters {
+            tag: Some("market".to_string()),
+            ..Default::default()
+        };
+        let tagged = transaction_service.get_transactions(filters).await.unwrap();
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].id, created.id);
+        assert_eq!(
+            transaction_service.get_tags().await.unwrap(),
+            vec!["food", "market"]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_transaction_page(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, groceries, _) = setup_purchase(&db).await;
+        let transaction_service = TransactionService::new(db.clone());
+        // Several transactions share a date, so the cursor must also use the id
+        for day in [1, 1, 2, 3, 3] {
+            let mut new_transaction = NewTransaction::new(
+                format!("Purchase on day {day}"),
+                date(2025, 7, day),
+                entries(bank, groceries, 1000, 1000),
+            );
+            new_transaction.tags = vec!["food".to_string()];
+            transaction_service
+                .create_transaction(new_transaction)
+                .await
+                .unwrap();
+        }
+
+        let all_filters = TransactionFilters {
+            account_id: Some(bank),
+            ..Default::default()
+        };
+        let all = transaction_service
+            .get_transactions(all_filters.clone())
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 6);
+        assert!(all.iter().all(|transaction| transaction.entries.len() == 2));
+        assert_eq!(all[0].tags, vec!["food"]);
+
+        let mut filters = TransactionFilters {
+            limit: Some(4),
+            ..all_filters
+        };
+        let mut paged = Vec::new();
+        loop {
+            let page = transaction_service
+                .get_transaction_page(filters.clone())
+                .await
+                .unwrap();
+            paged.extend(page.transactions);
+            match page.next_cursor {
+                Some(cursor) => filters.cursor = Some(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(paged, all);
+
+        // Offsets still work for simple listings, but not mixed with a cursor
+        filters.offset = Some(4);
+        filters.cursor = None;
+        let page = transaction_service
+            .get_transactions(filters.clone())
+            .await
+            .unwrap();
+        assert_eq!(page, all[4..]);
+        assert!(
+            transaction_service
+                .get_transaction_page(filters)
+                .await
+                .is_err()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_transaction_filters(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, groceries, _) = setup_purchase(&db).await;
+        let transaction_service = TransactionService::new(db.clone());
+        let fresh_food = create_account(&db, "Fresh food", AccountType::Expense, groceries).await;
+        let rent = create_account(&db, "Rent", AccountType::Expense, EXPENSES).await;
+
+        let mut market = NewTransaction::new(
+            "Farmers market".to_string(),
+            date(2025, 7, 2),
+            entries(bank, fresh_food, 1250, 1250),
+        );
+        market.notes = Some("Apples 50%_off".to_string());
+        market.tags = vec!["food".to_string(), "market".to_string()];
+        transaction_service
+            .create_transaction(market)
+            .await
+            .unwrap();
+        let mut rent_payment = NewTransaction::new(
+            "Rent July".to_string(),
+            date(2025, 7, 3),
+            entries(bank, rent, 80000, 80000),
+        );
+        rent_payment.reference = Some("INV-7".to_string());
+        transaction_service
+            .create_transaction(rent_payment)
+            .await
+            .unwrap();
+
+        let descriptions = async |filters: TransactionFilters| -> Vec<String> {
+            transaction_service
+                .get_transactions(filters)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|transaction| transaction.description)
+                .collect()
+        };
+
+        // Accounts, with descendants and combined
+        let groceries_only = TransactionFilters {
+            account_id: Some(groceries),
+            ..Default::default()
+        };
+        assert_eq!(descriptions(groceries_only.clone()).await, ["Supermarket"]);
+        let groceries_tree = TransactionFilters {
+            include_descendants: true,
+            ..groceries_only
+        };
+        assert_eq!(
+            descriptions(groceries_tree).await,
+            ["Farmers market", "Supermarket"]
+        );
+        let any_expense = TransactionFilters {
+            account_ids: vec![groceries, rent],
+            ..Default::default()
+        };
+        assert_eq!(
+            descriptions(any_expense).await,
+            ["Rent July", "Supermarket"]
+        );
+        let both = TransactionFilters {
+            account_ids: vec![bank, rent],
+            account_match: AccountMatch::All,
+            ..Default::default()
+        };
+        assert_eq!(descriptions(both).await, ["Rent July"]);
+
+        // Free text, with LIKE wildcards taken literally
+        let text = |text: &str| TransactionFilters {
+            text: Some(text.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(descriptions(text("%")).await, ["Farmers market"]);
+        assert_eq!(descriptions(text("inv-7")).await, ["Rent July"]);
+        assert_eq!(
+            descriptions(text("MARKET")).await,
+            ["Farmers market", "Supermarket"]
+        );
+
+        // Entry amount, type and currency
+        let mid_range = TransactionFilters {
+            min_amount: Some(Decimal::new(1250, 2)),
+            max_amount: Some(Decimal::new(50, 0)),
+            ..Default::default()
+        };
+        assert_eq!(
+            descriptions(mid_range).await,
+            ["Farmers market", "Supermarket"]
+        );
+        let large_bank_credits = TransactionFilters {
+            account_id: Some(bank),
+            entry_type: Some(EntryType::Credit),
+            min_amount: Some(Decimal::new(1251, 2)),
+            ..Default::default()
+        };
+        assert_eq!(
+            descriptions(large_bank_credits).await,
+            ["Rent July", "Supermarket"]
+        );
+        let bank_debits = TransactionFilters {
+            account_id: Some(bank),
+            entry_type: Some(EntryType::Debit),
+            ..Default::default()
+        };
+        assert!(descriptions(bank_debits).await.is_empty());
+        let dollars = TransactionFilters {
+            currency: Some("usd".to_string()),
+            ..Default::default()
+        };
+        assert!(descriptions(dollars).await.is_empty());
+
+        // Every tag must match
+        let tagged = TransactionFilters {
+            tag: Some("food".to_string()),
+            tags: vec!["market".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(descriptions(tagged).await, ["Farmers market"]);
+
+        // Sorting
+        let sorted = |sort| TransactionFilters {
+            sort,
+            ..Default::default()
+        };
+        assert_eq!(
+            descriptions(sorted(TransactionSort::DateAsc)).await,
+            ["Supermarket", "Farmers market", "Rent July"]
+        );
+        assert_eq!(
+            descriptions(sorted(TransactionSort::AmountDesc)).await,
+            ["Rent July", "Supermarket", "Farmers market"]
+        );
+        assert_eq!(
+            descriptions(sorted(TransactionSort::Description)).await,
+            ["Farmers market", "Rent July", "Supermarket"]
+        );
+
+        // Ascending pages continue after the cursor
+        let first = transaction_service
+            .get_transaction_page(TransactionFilters {
+                limit: Some(2),
+                ..sorted(TransactionSort::DateAsc)
+            })
+            .await
+            .unwrap();
+        let rest = TransactionFilters {
+            cursor: first.next_cursor,
+            ..sorted(TransactionSort::DateAsc)
+        };
+        assert_eq!(descriptions(rest).await, ["Rent July"]);
+
+        // A date cursor means nothing in another order
+        let by_amount = TransactionFilters {
+            cursor: first.next_cursor,
+            ..sorted(TransactionSort::AmountAsc)
+        };
+        assert!(
+            transaction_service
+                .get_transactions(by_amount)
+                .await
+                .is_err()
+        );
+        let inverted = TransactionFilters {
+            min_amount: Some(Decimal::new(10, 0)),
+            max_amount: Some(Decimal::new(5, 0)),
+            ..Default::default()
+        };
+        assert!(
+            transaction_service
+                .get_transactions(inverted)
+                .await
+                .is_err()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_search(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let (bank, groceries, supermarket) = setup_purchase(&db).await;
+        let transaction_service = TransactionService::new(db.clone());
+
+        let mut entries_with_note = entries(bank, groceries, 1250, 1250);
+        entries_with_note[1].description = Some("Organic apples".to_string());
+        let mut market = NewTransaction::new(
+            "Farmers market".to_string(),
+            date(2025, 7, 2),
+            entries_with_note,
+        );
+        market.notes = Some("Bought at the Supermarket car park".to_string());
+        market.tags = vec!["weekend".to_string()];
+        let market = transaction_service
+            .create_transaction(market)
+            .await
+            .unwrap();
+
+        let found = |query: &'static str| {
+            let transaction_service = &transaction_service;
+            async move {
+                transaction_service
+                    .search(query, None)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|hit| hit.transaction.id.unwrap())
+                    .collect::<Vec<_>>()
+            }
+        };
+        let supermarket_id = supermarket.id.unwrap();
+        let market_id = market.id.unwrap();
+
+        // A match in the description ranks above one in the notes
+        let hits = transaction_service
+            .search("supermarket", None)
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].transaction.id, Some(supermarket_id));
+        assert_eq!(hits[0].transaction.entries.len(), 2);
+        assert!(hits[0].rank < hits[1].rank);
+        assert_eq!(
+            hits[0].snippet,
+            vec![SnippetSegment {
+                text: "Supermarket".to_string(),
+                highlighted: true,
+            }]
+        );
+        assert!(hits[1].snippet.contains(&SnippetSegment {
+            text: "Supermarket".to_string(),
+            highlighted: true,
+        }));
+
+        // Tags, entry descriptions and word prefixes, with every word required
+        assert_eq!(found("weekend").await, [market_id]);
+        assert_eq!(found("organ appl").await, [market_id]);
+        assert_eq!(found("super car").await, [market_id]);
+        assert!(found("apples pears").await.is_empty());
+        // Operators and quotes are plain text, not query syntax
+        assert!(found("\"market OR NEAR(").await.is_empty());
+        assert!(found("   ").await.is_empty());
+        assert!(found("- ...").await.is_empty());
+
+        // The index follows updates and deletes
+        transaction_service
+            .update_transaction(
+                supermarket_id,
+                NewTransaction::new(
+                    "Corner shop".to_string(),
+                    date(2025, 7, 1),
+                    entries(bank, groceries, 5000, 5000),
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(found("corner").await, [supermarket_id]);
+        assert_eq!(found("supermarket").await, [market_id]);
+        transaction_service
+            .delete_transaction(market_id)
+            .await
+            .unwrap();
+        assert!(found("weekend").await.is_empty());
+    }
+
+    /// Lists 100k transactions with the set-based listing, then the way the
+    /// listing used to: one entries query per transaction. Run with
+    /// `cargo test --release -- --ignored bench_transaction_listing --nocapture`
+    /// to see the timings.
+    #[sqlx::test]
+    #[ignore]
+    async fn bench_transaction_listing(pool: sqlx::SqlitePool) {
+        const TRANSACTIONS: i64 = 100_000;
+
+        let db = Arc::new(Database { pool: pool.clone() });
+        let (bank, groceries, _) = setup_purchase(&db).await;
+        sqlx::query(
+            r#"
+            WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < ?)
+            INSERT INTO transactions (description, transaction_date, created_at)
+            SELECT 'Purchase ' || i, date('2000-01-01', '+' || (i / 20) || ' days'), CURRENT_TIMESTAMP
+            FROM n
+            "#,
+        )
+        .bind(TRANSACTIONS - 1)
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (account_id, entry_type) in [(bank, "credit"), (groceries, "debit")] {
+            sqlx::query(
+                r#"
+                INSERT INTO transaction_entries (transaction_id, account_id, amount_minor, currency, entry_type)
+                SELECT id, ?, 5000, 'EUR', ? FROM transactions WHERE description LIKE 'Purchase %'
+                "#,
+            )
+            .bind(account_id)
+            .bind(entry_type)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let transaction_service = TransactionService::new(db);
+
+        let started = Instant::now();
+        let transactions = transaction_service
+            .get_transactions(TransactionFilters::default())
+            .await
+            .unwrap();
+        let set_based = started.elapsed();
+        assert_eq!(transactions.len() as i64, TRANSACTIONS);
+
+        // Each entries query goes through the pool, as the listing used to do
+        let started = Instant::now();
+        let ids: Vec<i64> = sqlx::query_scalar(
+            "SELECT id FROM transactions ORDER BY transaction_date DESC, id DESC",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let mut entry_count = 0;
+        for id in ids {
+            let mut conn = pool.acquire().await.unwrap();
+            entry_count += TransactionRepository::get_entries_for_transaction(&mut conn, id)
+                .await
+                .unwrap()
+                .len();
+        }
+        let per_transaction = started.elapsed();
+        assert_eq!(
+            entry_count,
+            transactions
+                .iter()
+                .map(|transaction| transaction.entries.len())
+                .sum::<usize>()
+        );
+
+        eprintln!(
+            "Listing {TRANSACTIONS} transactions: {set_based:?} set-based, \
+             {per_transaction:?} with one entries query per transaction"
+        );
+        assert!(set_based < per_transaction);
+    }
 }
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use crate::AuditEntry;
+use crate::db::connection::Database;
+use crate::db::undo::UndoRepository;
+use crate::errors::{Result, WalletError};
+
+/// Undo and redo of ledger changes, driven by the audit log
+pub struct UndoService {
+    repository: UndoRepository,
+}
+
+impl UndoService {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            repository: UndoRepository::new(db),
+        }
+    }
+
+    /// Revert the last `count` changes. Either all of them are reverted or none.
+    pub async fn undo(&self, count: u32) -> Result<Vec<AuditEntry>> {
+        let (undoable, _) = self.repository.get_counts().await?;
+        Self::validate_count(count, undoable, "undo")?;
+        self.repository.undo(count).await
+    }
+
+    /// Re-apply the last `count` undone changes. Either all of them are
+    /// re-applied or none.
+    pub async fn redo(&self, count: u32) -> Result<Vec<AuditEntry>> {
+        let (_, redoable) = self.repository.get_counts().await?;
+        Self::validate_count(count, redoable, "redo")?;
+        self.repository.redo(count).await
+    }
+
+    /// Number of changes that can currently be undone and redone
+    pub async fn get_undo_redo_counts(&self) -> Result<(u32, u32)> {
+        self.repository.get_counts().await
+    }
+
+    fn validate_count(count: u32, available: u32, operation: &str) -> Result<()> {
+        if count == 0 {
+            return Err(WalletError::ValidationError(format!(
+                "Number of changes to {operation} must be positive"
+            )));
+        }
+        if count > available {
+            return Err(WalletError::ValidationError(format!(
+                "Cannot {operation} {count} changes, only {available} available"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::{ASSETS, EXPENSES, create_account};
+    use crate::{
+        AccountService, AccountType, AuditAction, Currency, EntryType, Money, NewTransaction,
+        TransactionEntryInput, TransactionService,
+    };
+    use chrono::NaiveDate;
+
+    #[sqlx::test]
+    async fn test_undo_and_redo_transaction_changes(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        let groceries = create_account(&db, "Groceries", AccountType::Expense, EXPENSES).await;
+        let transaction_service = TransactionService::new(db.clone());
+        let account_service = AccountService::new(db.clone());
+        let undo_service = UndoService::new(db.clone());
+
+        let date = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap();
+        let created = transaction_service
+            .create_simple_transaction(
+                "Supermarket".to_string(),
+                date,
+                Money::from_minor_units(5000, Currency::eur()),
+                bank,
+                groceries,
+            )
+            .await
+            .unwrap();
+        let id = created.id.unwrap();
+        let corrected = vec![
+            TransactionEntryInput {
+                account_id: bank,
+                amount: Money::from_minor_units(4500, Currency::eur()),
+                entry_type: EntryType::Credit,
+                description: None,
+                price: None,
+            },
+            TransactionEntryInput {
+                account_id: groceries,
+                amount: Money::from_minor_units(4500, Currency::eur()),
+                entry_type: EntryType::Debit,
+                description: None,
+                price: None,
+            },
+        ];
+        transaction_service
+            .update_transaction(
+                id,
+                NewTransaction::new("Supermarket".to_string(), date, corrected),
+            )
+            .await
+            .unwrap();
+        assert_eq!(undo_service.get_undo_redo_counts().await.unwrap(), (4, 0));
+
+        // Undo the edit, then the creation
+        let undone = undo_service.undo(1).await.unwrap();
+        assert_eq!(undone[0].action, AuditAction::Update);
+        let balance = account_service.calculate_balance(bank).await.unwrap();
+        assert_eq!(balance.amount_minor(), -5000);
+
+        undo_service.undo(1).await.unwrap();
+        assert!(transaction_service.get_transaction(id).await.is_err());
+        assert!(
+            account_service
+                .calculate_balance(bank)
+                .await
+                .unwrap()
+                .is_zero()
+        );
+
+        // Redo both: the transaction comes back with its id and the edit
+        let redone = undo_service.redo(2).await.unwrap();
+        assert_eq!(redone.len(), 2);
+        let restored = transaction_service.get_transaction(id).await.unwrap();
+        assert_eq!(restored.entries[0].amount.amount_minor(), 4500);
+        assert_eq!(restored.created_at, created.created_at);
+        assert_eq!(undo_service.get_undo_redo_counts().await.unwrap(), (4, 0));
+        assert!(undo_service.redo(1).await.is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_undo_account_changes(pool: sqlx::SqlitePool) {
+        let db = Arc::new(Database { pool });
+        let account_service = AccountService::new(db.clone());
+        let undo_service = UndoService::new(db.clone());
+
+        let bank = create_account(&db, "Bank", AccountType::Asset, ASSETS).await;
+        account_service.deactivate_account(bank).await.unwrap();
+
+        undo_service.undo(1).await.unwrap();
+        assert!(account_service.get_account(bank).await.unwrap().is_active);
+
+        undo_service.undo(1).await.unwrap();
+        assert!(account_service.get_account(bank).await.is_err());
+
+        // Asking for more than available changes nothing
+        assert!(undo_service.redo(3).await.is_err());
+        assert!(account_service.get_account(bank).await.is_err());
+
+        undo_service.redo(1).await.unwrap();
+        let account = account_service.get_account(bank).await.unwrap();
+        assert_eq!(account.name, "Bank");
+        assert!(account.is_active);
+
+        // A new change discards what is left to redo
+        create_account(&db, "Savings", AccountType::Asset, ASSETS).await;
+        assert_eq!(undo_service.get_undo_redo_counts().await.unwrap(), (2, 0));
+        assert!(undo_service.redo(1).await.is_err());
+        assert!(undo_service.undo(0).await.is_err());
+    }
+}
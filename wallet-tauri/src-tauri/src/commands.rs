@@ -2,8 +2,15 @@ use chrono::NaiveDate;
 use tauri::State;
 use wallet_core::AccountNode;
 use wallet_core::{
-    Account, AccountService, AccountType, Currency, Money, ReportService, Transaction,
-    TransactionFilters, TransactionService,
+    Account, AccountService, AccountType, AuditEntityType, AuditEntry, AuditService,
+    BalanceAssertion, BalanceAssertionService, BalanceSheet, CashFlowStatement, ClosedPeriod,
+    CsvImportProfile, Currency, CurrencyInfo, CurrencyKind, CurrencyService, Decimal,
+    DraftTransaction, EntryStatus, ExpenseBreakdown, FailedAssertion, FiscalYearClose,
+    ImportPreview, ImportService, ImportSummary, IncomeStatement, Money, NetWorthReport,
+    NetWorthSeries, NewTransaction, PeriodService, Price, PriceFileFormat, PriceImportSummary,
+    PriceService, Reconciliation, ReconciliationService, ReconciliationSummary, RegisterEntry,
+    ReportInterval, ReportService, Transaction, TransactionFilters, TransactionPage,
+    TransactionSearchHit, TransactionService, TrialBalance, UndoService,
 };
 
 use crate::AppState;
@@ -38,9 +45,11 @@ pub async fn create_account(
         _ => return Err("Invalid account type".to_string()),
     };
 
-    // Create currency object (for now, just EUR)
-    let currency =
-        Currency::new(&currency, 2, "€").map_err(|e| format!("Invalid currency: {}", e))?;
+    // Look the currency up in the registry
+    let currency = CurrencyService::new(state.db.clone())
+        .get_currency(&currency)
+        .await
+        .map_err(|e| format!("Invalid currency: {}", e))?;
 
     let account_service = wallet_core::AccountService::new(state.db.clone());
 
@@ -114,8 +123,10 @@ pub async fn create_simple_transaction(
     to_account_id: i64,
 ) -> Result<Transaction, String> {
     // Create Money object
-    let currency =
-        Currency::new(&currency_code, 2, "€").map_err(|e| format!("Invalid currency: {}", e))?;
+    let currency = CurrencyService::new(state.db.clone())
+        .get_currency(&currency_code)
+        .await
+        .map_err(|e| format!("Invalid currency: {}", e))?;
     let amount = Money::from_minor_units(amount_cents, currency);
 
     let transaction_service = TransactionService::new(state.db.clone());
@@ -128,6 +139,69 @@ pub async fn create_simple_transaction(
     }
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn create_transaction(
+    state: State<'_, AppState>,
+    transaction: NewTransaction,
+) -> Result<Transaction, String> {
+    let transaction_service = TransactionService::new(state.db.clone());
+    match transaction_service.create_transaction(transaction).await {
+        Ok(transaction) => Ok(transaction),
+        Err(e) => Err(format!("Failed to create transaction: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_tags(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let transaction_service = TransactionService::new(state.db.clone());
+    match transaction_service.get_tags().await {
+        Ok(tags) => Ok(tags),
+        Err(e) => Err(format!("Failed to get tags: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_transaction(
+    state: State<'_, AppState>,
+    id: i64,
+    transaction: NewTransaction,
+) -> Result<Transaction, String> {
+    let transaction_service = TransactionService::new(state.db.clone());
+    match transaction_service
+        .update_transaction(id, transaction)
+        .await
+    {
+        Ok(transaction) => Ok(transaction),
+        Err(e) => Err(format!("Failed to update transaction: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_transaction(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    let transaction_service = TransactionService::new(state.db.clone());
+    match transaction_service.delete_transaction(id).await {
+        Ok(()) => Ok(()),
+        Err(e) => Err(format!("Failed to delete transaction: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn reverse_transaction(
+    state: State<'_, AppState>,
+    id: i64,
+) -> Result<Transaction, String> {
+    let transaction_service = TransactionService::new(state.db.clone());
+    match transaction_service.reverse_transaction(id).await {
+        Ok(transaction) => Ok(transaction),
+        Err(e) => Err(format!("Failed to reverse transaction: {}", e)),
+    }
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_account_balance(
@@ -146,7 +220,7 @@ pub async fn get_account_balance(
 pub async fn get_account_balance_with_children(
     state: State<'_, AppState>,
     account_id: i64,
-) -> Result<Money, String> {
+) -> Result<Vec<Money>, String> {
     let account_service = AccountService::new(state.db.clone());
     match account_service
         .calculate_balance_with_children(account_id)
@@ -157,6 +231,73 @@ pub async fn get_account_balance_with_children(
     }
 }
 
+// Currency registry commands
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_currencies(state: State<'_, AppState>) -> Result<Vec<CurrencyInfo>, String> {
+    let currency_service = CurrencyService::new(state.db.clone());
+    match currency_service.list_currencies().await {
+        Ok(currencies) => Ok(currencies),
+        Err(e) => Err(format!("Failed to get currencies: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_commodity(
+    state: State<'_, AppState>,
+    code: String,
+    name: String,
+    minor_unit_scale: u8,
+    symbol: String,
+    kind: CurrencyKind,
+) -> Result<CurrencyInfo, String> {
+    let currency_service = CurrencyService::new(state.db.clone());
+    match currency_service
+        .create_commodity(&code, name, minor_unit_scale, &symbol, kind)
+        .await
+    {
+        Ok(currency) => Ok(currency),
+        Err(e) => Err(format!("Failed to create commodity: {}", e)),
+    }
+}
+
+// Price commands
+
+#[tauri::command]
+#[specta::specta]
+pub async fn record_price(
+    state: State<'_, AppState>,
+    base_currency: String,
+    quote_currency: String,
+    price_date: NaiveDate,
+    rate: Decimal,
+) -> Result<Price, String> {
+    let price_service = PriceService::new(state.db.clone());
+    match price_service
+        .record_price(&base_currency, &quote_currency, price_date, rate, "manual")
+        .await
+    {
+        Ok(price) => Ok(price),
+        Err(e) => Err(format!("Failed to record price: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn import_price_file(
+    state: State<'_, AppState>,
+    path: String,
+    format: PriceFileFormat,
+) -> Result<PriceImportSummary, String> {
+    let price_service = PriceService::new(state.db.clone());
+    match price_service.import_file(path, format).await {
+        Ok(summary) => Ok(summary),
+        Err(e) => Err(format!("Failed to import prices: {}", e)),
+    }
+}
+
 // Dashboard metric commands
 
 #[tauri::command]
@@ -169,6 +310,64 @@ pub async fn get_net_worth(state: State<'_, AppState>) -> Result<Money, String>
     }
 }
 
+/// The currency with the given code, or the configured reporting currency
+async fn resolve_reporting_currency(
+    state: &AppState,
+    code: Option<String>,
+) -> Result<Currency, String> {
+    match code {
+        Some(code) => {
+            CurrencyService::new(state.db.clone())
+                .get_currency(&code)
+                .await
+        }
+        None => {
+            ReportService::new(state.db.clone())
+                .get_reporting_currency()
+                .await
+        }
+    }
+    .map_err(|e| format!("Invalid currency: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_net_worth_report(
+    state: State<'_, AppState>,
+    reporting_currency: Option<String>,
+    as_of: Option<NaiveDate>,
+) -> Result<NetWorthReport, String> {
+    let currency = resolve_reporting_currency(&state, reporting_currency).await?;
+    let report_service = ReportService::new(state.db.clone());
+    match report_service.get_net_worth_report(&currency, as_of).await {
+        Ok(report) => Ok(report),
+        Err(e) => Err(format!("Failed to calculate net worth: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_reporting_currency(state: State<'_, AppState>) -> Result<Currency, String> {
+    let report_service = ReportService::new(state.db.clone());
+    match report_service.get_reporting_currency().await {
+        Ok(currency) => Ok(currency),
+        Err(e) => Err(format!("Failed to get reporting currency: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_reporting_currency(
+    state: State<'_, AppState>,
+    code: String,
+) -> Result<Currency, String> {
+    let report_service = ReportService::new(state.db.clone());
+    match report_service.set_reporting_currency(&code).await {
+        Ok(currency) => Ok(currency),
+        Err(e) => Err(format!("Failed to set reporting currency: {}", e)),
+    }
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_total_assets(state: State<'_, AppState>) -> Result<Money, String> {
@@ -260,3 +459,512 @@ pub async fn deactivate_account(state: State<'_, AppState>, account_id: i64) ->
         Err(e) => Err(format!("Failed to deactivate account: {}", e)),
     }
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_audit_history(
+    state: State<'_, AppState>,
+    entity_type: AuditEntityType,
+    entity_id: i64,
+) -> Result<Vec<AuditEntry>, String> {
+    let audit_service = AuditService::new(state.db.clone());
+    match audit_service.get_history(entity_type, entity_id).await {
+        Ok(history) => Ok(history),
+        Err(e) => Err(format!("Failed to get audit history: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recent_changes(
+    state: State<'_, AppState>,
+    limit: u32,
+) -> Result<Vec<AuditEntry>, String> {
+    let audit_service = AuditService::new(state.db.clone());
+    match audit_service.get_recent_changes(limit).await {
+        Ok(changes) => Ok(changes),
+        Err(e) => Err(format!("Failed to get recent changes: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn undo(state: State<'_, AppState>, count: u32) -> Result<Vec<AuditEntry>, String> {
+    let undo_service = UndoService::new(state.db.clone());
+    match undo_service.undo(count).await {
+        Ok(undone) => Ok(undone),
+        Err(e) => Err(format!("Failed to undo: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn redo(state: State<'_, AppState>, count: u32) -> Result<Vec<AuditEntry>, String> {
+    let undo_service = UndoService::new(state.db.clone());
+    match undo_service.redo(count).await {
+        Ok(redone) => Ok(redone),
+        Err(e) => Err(format!("Failed to redo: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_undo_redo_counts(state: State<'_, AppState>) -> Result<(u32, u32), String> {
+    let undo_service = UndoService::new(state.db.clone());
+    match undo_service.get_undo_redo_counts().await {
+        Ok(counts) => Ok(counts),
+        Err(e) => Err(format!("Failed to get undo history: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_entry_status(
+    state: State<'_, AppState>,
+    entry_id: i64,
+    status: EntryStatus,
+) -> Result<(), String> {
+    let reconciliation_service = ReconciliationService::new(state.db.clone());
+    match reconciliation_service
+        .set_entry_status(entry_id, status)
+        .await
+    {
+        Ok(()) => Ok(()),
+        Err(e) => Err(format!("Failed to update entry status: {}", e)),
+    }
+}
+
+/// Amount in minor units expressed in the currency of an account
+async fn account_amount(
+    state: &State<'_, AppState>,
+    account_id: i64,
+    amount_cents: i64,
+) -> Result<Money, String> {
+    let account = AccountService::new(state.db.clone())
+        .get_account(account_id)
+        .await
+        .map_err(|e| format!("Failed to get account: {}", e))?;
+    Ok(Money::from_minor_units(amount_cents, account.currency))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_reconciliation_summary(
+    state: State<'_, AppState>,
+    account_id: i64,
+    statement_date: NaiveDate,
+    statement_balance_cents: i64,
+) -> Result<ReconciliationSummary, String> {
+    let balance = account_amount(&state, account_id, statement_balance_cents).await?;
+    let reconciliation_service = ReconciliationService::new(state.db.clone());
+    match reconciliation_service
+        .get_summary(account_id, statement_date, balance)
+        .await
+    {
+        Ok(summary) => Ok(summary),
+        Err(e) => Err(format!("Failed to get reconciliation summary: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn finish_reconciliation(
+    state: State<'_, AppState>,
+    account_id: i64,
+    statement_date: NaiveDate,
+    statement_balance_cents: i64,
+) -> Result<Reconciliation, String> {
+    let balance = account_amount(&state, account_id, statement_balance_cents).await?;
+    let reconciliation_service = ReconciliationService::new(state.db.clone());
+    match reconciliation_service
+        .finish_reconciliation(account_id, statement_date, balance)
+        .await
+    {
+        Ok(reconciliation) => Ok(reconciliation),
+        Err(e) => Err(format!("Failed to finish reconciliation: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_reconciliations(
+    state: State<'_, AppState>,
+    account_id: i64,
+) -> Result<Vec<Reconciliation>, String> {
+    let reconciliation_service = ReconciliationService::new(state.db.clone());
+    match reconciliation_service.get_reconciliations(account_id).await {
+        Ok(reconciliations) => Ok(reconciliations),
+        Err(e) => Err(format!("Failed to get reconciliations: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_balance_assertion(
+    state: State<'_, AppState>,
+    account_id: i64,
+    date: NaiveDate,
+    amount_cents: i64,
+) -> Result<BalanceAssertion, String> {
+    let expected = account_amount(&state, account_id, amount_cents).await?;
+    let assertion_service = BalanceAssertionService::new(state.db.clone());
+    match assertion_service
+        .create_assertion(account_id, date, expected)
+        .await
+    {
+        Ok(assertion) => Ok(assertion),
+        Err(e) => Err(format!("Failed to create balance assertion: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_balance_assertions(
+    state: State<'_, AppState>,
+    account_id: Option<i64>,
+) -> Result<Vec<BalanceAssertion>, String> {
+    let assertion_service = BalanceAssertionService::new(state.db.clone());
+    match assertion_service.get_assertions(account_id).await {
+        Ok(assertions) => Ok(assertions),
+        Err(e) => Err(format!("Failed to get balance assertions: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_balance_assertion(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    let assertion_service = BalanceAssertionService::new(state.db.clone());
+    match assertion_service.delete_assertion(id).await {
+        Ok(()) => Ok(()),
+        Err(e) => Err(format!("Failed to delete balance assertion: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn check_balance_assertions(
+    state: State<'_, AppState>,
+) -> Result<Vec<FailedAssertion>, String> {
+    let assertion_service = BalanceAssertionService::new(state.db.clone());
+    match assertion_service.check_assertions().await {
+        Ok(failures) => Ok(failures),
+        Err(e) => Err(format!("Failed to check balance assertions: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_lock_date(state: State<'_, AppState>) -> Result<Option<NaiveDate>, String> {
+    let period_service = PeriodService::new(state.db.clone());
+    match period_service.get_lock_date().await {
+        Ok(lock_date) => Ok(lock_date),
+        Err(e) => Err(format!("Failed to get lock date: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn close_period(
+    state: State<'_, AppState>,
+    lock_date: NaiveDate,
+) -> Result<ClosedPeriod, String> {
+    let period_service = PeriodService::new(state.db.clone());
+    match period_service.close_period(lock_date).await {
+        Ok(period) => Ok(period),
+        Err(e) => Err(format!("Failed to close period: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn reopen_period(state: State<'_, AppState>) -> Result<Option<NaiveDate>, String> {
+    let period_service = PeriodService::new(state.db.clone());
+    match period_service.reopen_period().await {
+        Ok(lock_date) => Ok(lock_date),
+        Err(e) => Err(format!("Failed to reopen period: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn close_fiscal_year(
+    state: State<'_, AppState>,
+    fiscal_year_end: NaiveDate,
+) -> Result<FiscalYearClose, String> {
+    let period_service = PeriodService::new(state.db.clone());
+    match period_service.close_fiscal_year(fiscal_year_end).await {
+        Ok(close) => Ok(close),
+        Err(e) => Err(format!("Failed to close fiscal year: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_fiscal_year_closes(
+    state: State<'_, AppState>,
+) -> Result<Vec<FiscalYearClose>, String> {
+    let period_service = PeriodService::new(state.db.clone());
+    match period_service.get_fiscal_year_closes().await {
+        Ok(closes) => Ok(closes),
+        Err(e) => Err(format!("Failed to get fiscal year closes: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_trial_balance(
+    state: State<'_, AppState>,
+    as_of: NaiveDate,
+) -> Result<TrialBalance, String> {
+    let report_service = ReportService::new(state.db.clone());
+    match report_service.trial_balance(as_of).await {
+        Ok(trial_balance) => Ok(trial_balance),
+        Err(e) => Err(format!("Failed to get trial balance: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_balance_sheet(
+    state: State<'_, AppState>,
+    dates: Vec<NaiveDate>,
+    reporting_currency: Option<String>,
+) -> Result<BalanceSheet, String> {
+    let currency = resolve_reporting_currency(&state, reporting_currency).await?;
+    let report_service = ReportService::new(state.db.clone());
+    match report_service.balance_sheet(&dates, &currency).await {
+        Ok(balance_sheet) => Ok(balance_sheet),
+        Err(e) => Err(format!("Failed to get balance sheet: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_income_statement(
+    state: State<'_, AppState>,
+    from: NaiveDate,
+    to: NaiveDate,
+    interval: Option<ReportInterval>,
+    reporting_currency: Option<String>,
+) -> Result<IncomeStatement, String> {
+    let currency = resolve_reporting_currency(&state, reporting_currency).await?;
+    let report_service = ReportService::new(state.db.clone());
+    match report_service
+        .income_statement(from, to, interval, &currency)
+        .await
+    {
+        Ok(income_statement) => Ok(income_statement),
+        Err(e) => Err(format!("Failed to get income statement: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_cash_flow(
+    state: State<'_, AppState>,
+    cash_account_ids: Vec<i64>,
+    from: NaiveDate,
+    to: NaiveDate,
+    interval: Option<ReportInterval>,
+    reporting_currency: Option<String>,
+) -> Result<CashFlowStatement, String> {
+    let currency = resolve_reporting_currency(&state, reporting_currency).await?;
+    let report_service = ReportService::new(state.db.clone());
+    match report_service
+        .cash_flow(&cash_account_ids, from, to, interval, &currency)
+        .await
+    {
+        Ok(cash_flow) => Ok(cash_flow),
+        Err(e) => Err(format!("Failed to get cash flow: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_net_worth_series(
+    state: State<'_, AppState>,
+    from: NaiveDate,
+    to: NaiveDate,
+    interval: ReportInterval,
+) -> Result<NetWorthSeries, String> {
+    let report_service = ReportService::new(state.db.clone());
+    match report_service.net_worth_series(from, to, interval).await {
+        Ok(series) => Ok(series),
+        Err(e) => Err(format!("Failed to get net worth series: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_expense_breakdown(
+    state: State<'_, AppState>,
+    from: NaiveDate,
+    to: NaiveDate,
+    depth: u32,
+    parent_id: Option<i64>,
+) -> Result<ExpenseBreakdown, String> {
+    let report_service = ReportService::new(state.db.clone());
+    match report_service
+        .expense_breakdown(from, to, depth, parent_id)
+        .await
+    {
+        Ok(breakdown) => Ok(breakdown),
+        Err(e) => Err(format!("Failed to get expense breakdown: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_account_register(
+    state: State<'_, AppState>,
+    account_id: i64,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<RegisterEntry>, String> {
+    let account_service = AccountService::new(state.db.clone());
+    match account_service
+        .get_register(account_id, limit, offset)
+        .await
+    {
+        Ok(register) => Ok(register),
+        Err(e) => Err(format!("Failed to get account register: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_transaction_page(
+    state: State<'_, AppState>,
+    filters: TransactionFilters,
+) -> Result<TransactionPage, String> {
+    let transaction_service = TransactionService::new(state.db.clone());
+    match transaction_service.get_transaction_page(filters).await {
+        Ok(page) => Ok(page),
+        Err(e) => Err(format!("Failed to get transactions: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn search_transactions(
+    state: State<'_, AppState>,
+    query: String,
+    limit: Option<u32>,
+) -> Result<Vec<TransactionSearchHit>, String> {
+    let transaction_service = TransactionService::new(state.db.clone());
+    match transaction_service.search(&query, limit).await {
+        Ok(hits) => Ok(hits),
+        Err(e) => Err(format!("Failed to search transactions: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_csv_import_profile(
+    state: State<'_, AppState>,
+    profile: CsvImportProfile,
+) -> Result<CsvImportProfile, String> {
+    let import_service = ImportService::new(state.db.clone());
+    match import_service.create_csv_profile(profile).await {
+        Ok(profile) => Ok(profile),
+        Err(e) => Err(format!("Failed to create import profile: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_csv_import_profile(
+    state: State<'_, AppState>,
+    profile: CsvImportProfile,
+) -> Result<CsvImportProfile, String> {
+    let import_service = ImportService::new(state.db.clone());
+    match import_service.update_csv_profile(profile).await {
+        Ok(profile) => Ok(profile),
+        Err(e) => Err(format!("Failed to update import profile: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_csv_import_profiles(
+    state: State<'_, AppState>,
+) -> Result<Vec<CsvImportProfile>, String> {
+    let import_service = ImportService::new(state.db.clone());
+    match import_service.get_csv_profiles().await {
+        Ok(profiles) => Ok(profiles),
+        Err(e) => Err(format!("Failed to get import profiles: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_csv_import_profile(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    let import_service = ImportService::new(state.db.clone());
+    match import_service.delete_csv_profile(id).await {
+        Ok(()) => Ok(()),
+        Err(e) => Err(format!("Failed to delete import profile: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_csv_import(
+    state: State<'_, AppState>,
+    profile_id: i64,
+    path: String,
+) -> Result<ImportPreview, String> {
+    let import_service = ImportService::new(state.db.clone());
+    match import_service.preview_csv_file(profile_id, path).await {
+        Ok(preview) => Ok(preview),
+        Err(e) => Err(format!("Failed to read statement: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_ofx_import(
+    state: State<'_, AppState>,
+    account_id: i64,
+    counter_account_id: i64,
+    path: String,
+) -> Result<ImportPreview, String> {
+    let import_service = ImportService::new(state.db.clone());
+    match import_service
+        .preview_ofx_file(account_id, counter_account_id, path)
+        .await
+    {
+        Ok(preview) => Ok(preview),
+        Err(e) => Err(format!("Failed to read statement: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_camt_import(
+    state: State<'_, AppState>,
+    account_id: i64,
+    counter_account_id: i64,
+    path: String,
+) -> Result<ImportPreview, String> {
+    let import_service = ImportService::new(state.db.clone());
+    match import_service
+        .preview_camt_file(account_id, counter_account_id, path)
+        .await
+    {
+        Ok(preview) => Ok(preview),
+        Err(e) => Err(format!("Failed to read statement: {}", e)),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn commit_import(
+    state: State<'_, AppState>,
+    drafts: Vec<DraftTransaction>,
+) -> Result<ImportSummary, String> {
+    let import_service = ImportService::new(state.db.clone());
+    match import_service.commit(drafts).await {
+        Ok(summary) => Ok(summary),
+        Err(e) => Err(format!("Failed to import transactions: {}", e)),
+    }
+}
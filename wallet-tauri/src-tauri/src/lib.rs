@@ -22,15 +22,62 @@ pub fn run() {
         commands::get_transactions,
         commands::get_transaction,
         commands::create_simple_transaction,
+        commands::create_transaction,
+        commands::update_transaction,
+        commands::get_tags,
+        commands::delete_transaction,
+        commands::reverse_transaction,
         commands::get_account_balance,
         commands::get_account_balance_with_children,
+        commands::get_currencies,
+        commands::create_commodity,
+        commands::record_price,
+        commands::import_price_file,
         commands::get_net_worth,
+        commands::get_net_worth_report,
+        commands::get_reporting_currency,
+        commands::set_reporting_currency,
         commands::get_total_assets,
         commands::get_current_month_income,
         commands::get_current_month_expenses,
         commands::get_recent_transactions,
         commands::update_account,
         commands::deactivate_account,
+        commands::get_audit_history,
+        commands::get_recent_changes,
+        commands::undo,
+        commands::redo,
+        commands::get_undo_redo_counts,
+        commands::set_entry_status,
+        commands::get_reconciliation_summary,
+        commands::finish_reconciliation,
+        commands::get_reconciliations,
+        commands::create_balance_assertion,
+        commands::get_balance_assertions,
+        commands::delete_balance_assertion,
+        commands::check_balance_assertions,
+        commands::get_lock_date,
+        commands::close_period,
+        commands::reopen_period,
+        commands::close_fiscal_year,
+        commands::get_fiscal_year_closes,
+        commands::get_trial_balance,
+        commands::get_balance_sheet,
+        commands::get_income_statement,
+        commands::get_cash_flow,
+        commands::get_net_worth_series,
+        commands::get_expense_breakdown,
+        commands::get_account_register,
+        commands::get_transaction_page,
+        commands::search_transactions,
+        commands::create_csv_import_profile,
+        commands::update_csv_import_profile,
+        commands::get_csv_import_profiles,
+        commands::delete_csv_import_profile,
+        commands::preview_csv_import,
+        commands::preview_ofx_import,
+        commands::preview_camt_import,
+        commands::commit_import,
     ]);
     #[cfg(debug_assertions)]
     {
@@ -30,6 +30,14 @@ async getAccountTree() : Promise<Result<AccountNode[], string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async getAccountTreeFiltered(includeInactive: boolean) : Promise<Result<AccountNode[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_account_tree_filtered", { includeInactive }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getTransactions(filters: TransactionFilters) : Promise<Result<Transaction[], string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_transactions", { filters }) };
@@ -54,6 +62,46 @@ async createSimpleTransaction(description: string, date: string, amountCents: bi
     else return { status: "error", error: e  as any };
 }
 },
+async createTransaction(transaction: NewTransaction) : Promise<Result<Transaction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_transaction", { transaction }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateTransaction(id: bigint, transaction: NewTransaction) : Promise<Result<Transaction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_transaction", { id, transaction }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTags() : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_tags") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteTransaction(id: bigint) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_transaction", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async reverseTransaction(id: bigint) : Promise<Result<Transaction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reverse_transaction", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getAccountBalance(accountId: bigint) : Promise<Result<Money, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_account_balance", { accountId }) };
@@ -62,7 +110,7 @@ async getAccountBalance(accountId: bigint) : Promise<Result<Money, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async getAccountBalanceWithChildren(accountId: bigint) : Promise<Result<Money, string>> {
+async getAccountBalanceWithChildren(accountId: bigint) : Promise<Result<Money[], string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_account_balance_with_children", { accountId }) };
 } catch (e) {
@@ -70,6 +118,38 @@ async getAccountBalanceWithChildren(accountId: bigint) : Promise<Result<Money, s
     else return { status: "error", error: e  as any };
 }
 },
+async getCurrencies() : Promise<Result<CurrencyInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_currencies") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createCommodity(code: string, name: string, minorUnitScale: number, symbol: string, kind: CurrencyKind) : Promise<Result<CurrencyInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_commodity", { code, name, minorUnitScale, symbol, kind }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async recordPrice(baseCurrency: string, quoteCurrency: string, priceDate: string, rate: string) : Promise<Result<Price, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("record_price", { baseCurrency, quoteCurrency, priceDate, rate }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importPriceFile(path: string, format: PriceFileFormat) : Promise<Result<PriceImportSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_price_file", { path, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getNetWorth() : Promise<Result<Money, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_net_worth") };
@@ -78,6 +158,30 @@ async getNetWorth() : Promise<Result<Money, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async getNetWorthReport(reportingCurrency: string | null, asOf: string | null) : Promise<Result<NetWorthReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_net_worth_report", { reportingCurrency, asOf }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getReportingCurrency() : Promise<Result<Currency, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_reporting_currency") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setReportingCurrency(code: string) : Promise<Result<Currency, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_reporting_currency", { code }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getTotalAssets() : Promise<Result<Money, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_total_assets") };
@@ -109,6 +213,302 @@ async getRecentTransactions(limit: number | null) : Promise<Result<Transaction[]
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async updateAccount(accountId: bigint, name: string, description: string | null) : Promise<Result<Account, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_account", { accountId, name, description }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deactivateAccount(accountId: bigint) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("deactivate_account", { accountId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getAuditHistory(entityType: AuditEntityType, entityId: bigint) : Promise<Result<AuditEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_audit_history", { entityType, entityId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRecentChanges(limit: number) : Promise<Result<AuditEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recent_changes", { limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async undo(count: number) : Promise<Result<AuditEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("undo", { count }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async redo(count: number) : Promise<Result<AuditEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("redo", { count }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getUndoRedoCounts() : Promise<Result<[number, number], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_undo_redo_counts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setEntryStatus(entryId: bigint, status: EntryStatus) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_entry_status", { entryId, status }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getReconciliationSummary(accountId: bigint, statementDate: string, statementBalanceCents: bigint) : Promise<Result<ReconciliationSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_reconciliation_summary", { accountId, statementDate, statementBalanceCents }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async finishReconciliation(accountId: bigint, statementDate: string, statementBalanceCents: bigint) : Promise<Result<Reconciliation, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("finish_reconciliation", { accountId, statementDate, statementBalanceCents }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getReconciliations(accountId: bigint) : Promise<Result<Reconciliation[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_reconciliations", { accountId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createBalanceAssertion(accountId: bigint, date: string, amountCents: bigint) : Promise<Result<BalanceAssertion, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_balance_assertion", { accountId, date, amountCents }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getBalanceAssertions(accountId: bigint | null) : Promise<Result<BalanceAssertion[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_balance_assertions", { accountId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteBalanceAssertion(id: bigint) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_balance_assertion", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async checkBalanceAssertions() : Promise<Result<FailedAssertion[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_balance_assertions") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLockDate() : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_lock_date") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async closePeriod(lockDate: string) : Promise<Result<ClosedPeriod, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("close_period", { lockDate }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async reopenPeriod() : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reopen_period") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async closeFiscalYear(fiscalYearEnd: string) : Promise<Result<FiscalYearClose, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("close_fiscal_year", { fiscalYearEnd }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getFiscalYearCloses() : Promise<Result<FiscalYearClose[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_fiscal_year_closes") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTrialBalance(asOf: string) : Promise<Result<TrialBalance, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_trial_balance", { asOf }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getBalanceSheet(dates: string[], reportingCurrency: string | null) : Promise<Result<BalanceSheet, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_balance_sheet", { dates, reportingCurrency }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getIncomeStatement(from: string, to: string, interval: ReportInterval | null, reportingCurrency: string | null) : Promise<Result<IncomeStatement, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_income_statement", { from, to, interval, reportingCurrency }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCashFlow(cashAccountIds: bigint[], from: string, to: string, interval: ReportInterval | null, reportingCurrency: string | null) : Promise<Result<CashFlowStatement, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_cash_flow", { cashAccountIds, from, to, interval, reportingCurrency }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getNetWorthSeries(from: string, to: string, interval: ReportInterval) : Promise<Result<NetWorthSeries, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_net_worth_series", { from, to, interval }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getExpenseBreakdown(from: string, to: string, depth: number, parentId: bigint | null) : Promise<Result<ExpenseBreakdown, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_expense_breakdown", { from, to, depth, parentId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getAccountRegister(accountId: bigint, limit: number | null, offset: number | null) : Promise<Result<RegisterEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_account_register", { accountId, limit, offset }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTransactionPage(filters: TransactionFilters) : Promise<Result<TransactionPage, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_transaction_page", { filters }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async searchTransactions(query: string, limit: number | null) : Promise<Result<TransactionSearchHit[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_transactions", { query, limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createCsvImportProfile(profile: CsvImportProfile) : Promise<Result<CsvImportProfile, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_csv_import_profile", { profile }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateCsvImportProfile(profile: CsvImportProfile) : Promise<Result<CsvImportProfile, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_csv_import_profile", { profile }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCsvImportProfiles() : Promise<Result<CsvImportProfile[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_csv_import_profiles") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteCsvImportProfile(id: bigint) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_csv_import_profile", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async previewCsvImport(profileId: bigint, path: string) : Promise<Result<ImportPreview, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_csv_import", { profileId, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async previewOfxImport(accountId: bigint, counterAccountId: bigint, path: string) : Promise<Result<ImportPreview, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_ofx_import", { accountId, counterAccountId, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async previewCamtImport(accountId: bigint, counterAccountId: bigint, path: string) : Promise<Result<ImportPreview, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_camt_import", { accountId, counterAccountId, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async commitImport(drafts: DraftTransaction[]) : Promise<Result<ImportSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("commit_import", { drafts }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 }
 }
 
@@ -123,14 +523,561 @@ async getRecentTransactions(limit: number | null) : Promise<Result<Transaction[]
 /** user-defined types **/
 
 export type Account = { id: bigint | null; name: string; account_type: AccountType; parent_id: bigint | null; currency: Currency; description: string | null; is_active: boolean; created_at: string; updated_at: string }
+/**
+ * How transactions are matched against several accounts
+ */
+export type AccountMatch = 
+/**
+ * Touches at least one of the accounts
+ */
+"Any" | 
+/**
+ * Touches every one of the accounts
+ */
+"All"
 export type AccountNode = { account: Account; level: number; path: string }
 export type AccountType = "Asset" | "Liability" | "Equity" | "Income" | "Expense"
+export type AuditAction = "Create" | "Update" | "Deactivate" | "Delete"
+export type AuditEntityType = "Account" | "Transaction"
+/**
+ * One row of the audit log: the state of an entity before and after a change,
+ * serialized as JSON
+ */
+export type AuditEntry = { id: bigint; entity_type: AuditEntityType; entity_id: bigint; action: AuditAction; actor: string; before_json: string | null; after_json: string | null; created_at: string }
+/**
+ * Expected balance of an account at the start of a date, before any
+ * transaction of that date
+ */
+export type BalanceAssertion = { id: bigint | null; account_id: bigint; assertion_date: string; expected: Money; created_at: string }
+export type BalanceSheet = { 
+/**
+ * Report dates, one column each; balances are taken at the end of the day
+ */
+dates: string[]; reporting_currency: Currency; assets: BalanceSheetSection; liabilities: BalanceSheetSection; 
+/**
+ * Equity accounts plus current earnings (Income minus Expenses not yet
+ * closed into retained earnings) and exchange rate differences
+ */
+equity: BalanceSheetSection; 
+/**
+ * Liabilities plus equity, equal to total assets
+ */
+total_liabilities_and_equity: Money[]; missing_rates: MissingRate[] }
+/**
+ * Account of a balance sheet with its balance including sub-accounts
+ */
+export type BalanceSheetLine = { 
+/**
+ * `None` for computed lines such as current earnings
+ */
+account_id: bigint | null; name: string; level: number; path: string; 
+/**
+ * One amount per report date, in the reporting currency
+ */
+amounts: Money[] }
+export type BalanceSheetSection = { account_type: AccountType; 
+/**
+ * Accounts in tree order; each line is the subtotal of its sub-accounts
+ */
+lines: BalanceSheetLine[]; 
+/**
+ * One total per report date
+ */
+totals: Money[] }
+/**
+ * Kind of counter-account a cash movement is booked against
+ */
+export type CashFlowCategory = "Income" | "Expense" | 
+/**
+ * Borrowing and repayments
+ */
+"Liability" | "Equity" | 
+/**
+ * Movements to or from asset accounts that are not cash
+ */
+"Transfer"
+export type CashFlowLine = { category: CashFlowCategory; 
+/**
+ * Cash received, one amount per period
+ */
+inflows: Money[]; 
+/**
+ * Cash paid, as positive amounts, one per period
+ */
+outflows: Money[] }
+export type CashFlowStatement = { 
+/**
+ * Cash accounts the report was asked for; their sub-accounts count as cash too
+ */
+cash_account_ids: bigint[]; periods: ReportPeriod[]; reporting_currency: Currency; 
+/**
+ * Cash at the start of each period
+ */
+opening_cash: Money[]; 
+/**
+ * One line per category, in a fixed order
+ */
+lines: CashFlowLine[]; total_inflows: Money[]; total_outflows: Money[]; 
+/**
+ * Change in the value of cash held in other currencies than the reporting
+ * one: of the opening cash and of every movement, up to the rates of the
+ * last day of the period
+ */
+exchange_rate_effect: Money[]; 
+/**
+ * Cash at the end of each period: opening cash, plus inflows, minus
+ * outflows, plus the exchange rate effect
+ */
+closing_cash: Money[]; missing_rates: MissingRate[] }
+/**
+ * Accounting period closed up to and including `lock_date`
+ */
+export type ClosedPeriod = { id: bigint; lock_date: string; created_at: string }
+/**
+ * Saved mapping from the columns of a bank's CSV export to transactions on
+ * one account. Column indices are zero-based.
+ */
+export type CsvImportProfile = { id: bigint | null; name: string; 
+/**
+ * Account the statement belongs to
+ */
+account_id: bigint; 
+/**
+ * Account the other side of each row is booked to, unless changed in the preview
+ */
+counter_account_id: bigint; delimiter: string; 
+/**
+ * Header and other lines to skip at the start of the file
+ */
+skip_rows: number; date_column: number; 
+/**
+ * chrono format of the dates, e.g. `%d.%m.%Y`
+ */
+date_format: string; description_column: number; reference_column: number | null; 
+/**
+ * Signed amount, positive for money coming in unless `negate_amounts`
+ */
+amount_column: number | null; 
+/**
+ * Unsigned money going out and coming in, used instead of `amount_column`
+ */
+withdrawal_column: number | null; deposit_column: number | null; 
+/**
+ * Flip the sign of `amount_column`, for exports where spending is positive
+ */
+negate_amounts: boolean; decimal_separator: DecimalSeparator; created_at: string }
 export type Currency = { code: string; minor_unit_scale: number; symbol: string }
+/**
+ * Entry of the currency registry: ISO 4217 currencies and user-defined commodities
+ */
+export type CurrencyInfo = { currency: Currency; name: string; kind: CurrencyKind; is_builtin: boolean }
+export type CurrencyKind = "Fiat" | "Crypto" | "Security" | "Commodity"
+/**
+ * Character separating the integer and fractional part of amounts. The other
+ * one of `.` and `,`, spaces and apostrophes are read as digit grouping.
+ */
+export type DecimalSeparator = 
+/**
+ * `1,234.56`
+ */
+"Point" | 
+/**
+ * `1.234,56`
+ */
+"Comma"
+/**
+ * Statement line parsed into a transaction that is not in the ledger yet
+ */
+export type DraftTransaction = { transaction_date: string; description: string; reference: string | null; notes: string | null; 
+/**
+ * Account the statement belongs to
+ */
+account_id: bigint; counter_account_id: bigint; 
+/**
+ * Amount in the account currency, positive for money coming in
+ */
+amount: Money; 
+/**
+ * Identifies the statement line, so importing it twice is detected
+ */
+external_id: string; 
+/**
+ * Transaction already imported from the same statement line
+ */
+duplicate_of: bigint | null }
+/**
+ * Price of an entry expressed in a second currency, as in `EUR 92.10 @ 1.0857 USD`
+ */
+export type EntryPrice = { 
+/**
+ * Units of the price currency for one unit of the entry currency
+ */
+rate: string; 
+/**
+ * Entry amount converted into the price currency
+ */
+amount: Money }
+/**
+ * Progress of an entry through bank reconciliation
+ */
+export type EntryStatus = 
+/**
+ * Not yet seen on a bank statement
+ */
+"Pending" | 
+/**
+ * Ticked off against a statement being reconciled
+ */
+"Cleared" | 
+/**
+ * Part of a finished reconciliation, locked against edits
+ */
+"Reconciled"
 export type EntryType = "Credit" | "Debit"
+export type ExpenseBreakdown = { from: string; to: string; 
+/**
+ * Category drilled into, all Expense accounts when `None`
+ */
+parent_id: bigint | null; depth: number; reporting_currency: Currency; total: Money; transaction_count: number; 
+/**
+ * Largest spending first
+ */
+categories: ExpenseCategory[]; missing_rates: MissingRate[] }
+/**
+ * Expense account with the spending of its sub-accounts rolled up into it
+ */
+export type ExpenseCategory = { account_id: bigint; name: string; level: number; path: string; 
+/**
+ * Spending in the reporting currency
+ */
+amount: Money; 
+/**
+ * Fraction of the breakdown total, between 0 and 1 unless there are refunds
+ */
+share: string; 
+/**
+ * Transactions booked on the account or its rolled-up sub-accounts
+ */
+transaction_count: number; 
+/**
+ * Whether the category can be drilled into
+ */
+has_children: boolean }
+/**
+ * Assertion that does not hold against the ledger, or whose balance could
+ * not be computed
+ */
+export type FailedAssertion = { assertion: BalanceAssertion; 
+/**
+ * `None` when the balance could not be computed
+ */
+actual: Money | null; 
+/**
+ * Actual minus expected balance
+ */
+discrepancy: Money | null; 
+/**
+ * Why the balance could not be computed
+ */
+error: string | null }
+/**
+ * Year-end close: the transaction moving Income and Expense balances into
+ * retained earnings
+ */
+export type FiscalYearClose = { id: bigint; fiscal_year_end: string; transaction_id: bigint; retained_earnings_account_id: bigint; created_at: string }
+/**
+ * Parsed statement to review before committing
+ */
+export type ImportPreview = { drafts: DraftTransaction[]; 
+/**
+ * Statement lines that could not be read, each with where it is and why
+ */
+issues: string[]; 
+/**
+ * Statement balances checked against the ledger, for formats stating them
+ */
+balance_checks: StatementBalanceCheck[] }
+export type ImportSummary = { 
+/**
+ * Ids of the transactions created
+ */
+transaction_ids: bigint[]; 
+/**
+ * Drafts skipped because they were imported before
+ */
+duplicates: number }
+export type IncomeStatement = { from: string; to: string; 
+/**
+ * Period columns covering `from` to `to`, a single one without interval
+ */
+periods: ReportPeriod[]; reporting_currency: Currency; income: IncomeStatementSection; expenses: IncomeStatementSection; 
+/**
+ * Income minus expenses, one amount per period
+ */
+net_income: Money[]; missing_rates: MissingRate[] }
+/**
+ * Income or Expense account of an income statement with its activity including
+ * sub-accounts
+ */
+export type IncomeStatementLine = { account_id: bigint; name: string; level: number; path: string; 
+/**
+ * One amount per period, in the reporting currency
+ */
+amounts: Money[] }
+export type IncomeStatementSection = { account_type: AccountType; 
+/**
+ * Accounts in tree order; each line is the subtotal of its sub-accounts
+ */
+lines: IncomeStatementLine[]; 
+/**
+ * One total per period
+ */
+totals: Money[] }
+/**
+ * Account whose balance could not be valued for lack of an exchange rate
+ */
+export type MissingRate = { account_id: bigint; account_name: string; currency: string }
 export type Money = { amount_minor: bigint; currency: Currency }
-export type Transaction = { id: bigint | null; description: string; reference: string | null; transaction_date: string; created_at: string; tags: string | null; notes: string | null; entries: TransactionEntry[] }
-export type TransactionEntry = { id: bigint | null; transaction_id: bigint; account_id: bigint; amount: Money; entry_type: EntryType; description: string | null; created_at: string }
-export type TransactionFilters = { account_id: bigint | null; from_date: string | null; to_date: string | null; limit: number | null; offset: number | null }
+/**
+ * Net worth at the end of one day
+ */
+export type NetWorthPoint = { date: string; total_assets: Money; total_liabilities: Money; net_worth: Money }
+export type NetWorthReport = { as_of: string; reporting_currency: Currency; total_assets: ValuedTotal; total_liabilities: ValuedTotal; net_worth: ValuedTotal; missing_rates: MissingRate[] }
+export type NetWorthSeries = { from: string; to: string; interval: ReportInterval; reporting_currency: Currency; 
+/**
+ * One point at the end of every period of the interval, the last one on `to`
+ */
+points: NetWorthPoint[]; missing_rates: MissingRate[] }
+/**
+ * Everything needed to record a new transaction
+ */
+export type NewTransaction = { description: string; transaction_date: string; reference?: string | null; notes?: string | null; tags?: string[]; 
+/**
+ * Identifier in the source system, unique across the ledger
+ */
+external_id?: string | null; entries: TransactionEntryInput[] }
+/**
+ * Value of one unit of `base_currency` expressed in `quote_currency` on a given date
+ */
+export type Price = { id: bigint | null; base_currency: string; quote_currency: string; price_date: string; rate: string; source: string; created_at: string }
+/**
+ * Supported price file formats
+ */
+export type PriceFileFormat = 
+/**
+ * ECB euro foreign exchange reference rates (eurofxref-hist.csv)
+ */
+"EcbCsv" | 
+/**
+ * Ledger `P` directives (also accepts beancount `price` directives)
+ */
+"Ledger"
+export type PriceImportSummary = { imported: number; 
+/**
+ * Prices involving currencies missing from the registry
+ */
+skipped: number }
+/**
+ * Finished reconciliation of an account against a bank statement
+ */
+export type Reconciliation = { id: bigint; account_id: bigint; statement_date: string; statement_balance: Money; created_at: string }
+/**
+ * Entry of the account being reconciled
+ */
+export type ReconciliationEntry = { entry_id: bigint; transaction_id: bigint; transaction_date: string; 
+/**
+ * Entry description, or the transaction description when the entry has none
+ */
+description: string; entry_type: EntryType; 
+/**
+ * Effect of the entry on the account balance: negative when it decreases it
+ */
+amount: Money; status: EntryStatus }
+/**
+ * State of a reconciliation in progress
+ */
+export type ReconciliationSummary = { account_id: bigint; statement_date: string; statement_balance: Money; 
+/**
+ * Balance of the cleared and reconciled entries up to the statement date
+ */
+cleared_balance: Money; 
+/**
+ * Statement balance minus cleared balance; the reconciliation can be
+ * finished once it is zero
+ */
+difference: Money; 
+/**
+ * Pending entries up to the statement date
+ */
+uncleared_entries: ReconciliationEntry[] }
+/**
+ * Other account of a transaction shown in a register
+ */
+export type RegisterCounterpart = { account_id: bigint; account_name: string }
+/**
+ * Entry of an account register with the account balance right after it
+ */
+export type RegisterEntry = { entry_id: bigint; transaction_id: bigint; transaction_date: string; 
+/**
+ * Transaction description
+ */
+description: string; entry_description: string | null; reference: string | null; 
+/**
+ * Other accounts of the transaction
+ */
+counterparts: RegisterCounterpart[]; entry_type: EntryType; amount: Money; status: EntryStatus; 
+/**
+ * Running balance of the account in the entry currency, following the
+ * account's normal balance
+ */
+balance: Money }
+/**
+ * Length of the period columns of a report
+ */
+export type ReportInterval = "Day" | 
+/**
+ * Weeks run from Monday to Sunday
+ */
+"Week" | "Month" | "Quarter" | "Year"
+/**
+ * Column of a report, both dates included
+ */
+export type ReportPeriod = { start: string; end: string }
+/**
+ * Piece of a search snippet, highlighted where it matched the query
+ */
+export type SnippetSegment = { text: string; highlighted: boolean }
+/**
+ * Balance stated in a bank statement next to the ledger balance at the same
+ * moment. Both are from the account holder's view, like draft amounts.
+ */
+export type StatementBalanceCheck = { 
+/**
+ * ISO 20022 balance type: `OPBD` for opening, `CLBD` for closing
+ */
+balance_type: string; 
+/**
+ * Opening balances hold at the start of this date, closing ones at its end
+ */
+date: string; statement_balance: Money; 
+/**
+ * Ledger balance once the drafts not imported yet are committed
+ */
+ledger_balance: Money; 
+/**
+ * Statement minus ledger balance, zero when they agree
+ */
+difference: Money }
+export type Transaction = { id: bigint | null; description: string; reference: string | null; transaction_date: string; created_at: string; tags: string[]; notes: string | null; 
+/**
+ * Identifier of the transaction in the system it was imported from
+ */
+external_id: string | null; 
+/**
+ * Original transaction this one reverses, if it is a reversal
+ */
+reverses_transaction_id: bigint | null; entries: TransactionEntry[] }
+/**
+ * Position in a listing sorted by date: the date and id of the last
+ * transaction already seen
+ */
+export type TransactionCursor = { transaction_date: string; id: bigint }
+export type TransactionEntry = { id: bigint | null; transaction_id: bigint; account_id: bigint; amount: Money; entry_type: EntryType; description: string | null; price: EntryPrice | null; status?: EntryStatus; 
+/**
+ * Reconciliation that locked the entry
+ */
+reconciliation_id?: bigint | null; created_at: string }
+export type TransactionEntryInput = { account_id: bigint; amount: Money; entry_type: EntryType; description: string | null; price: EntryPrice | null }
+export type TransactionFilters = { account_id: bigint | null; 
+/**
+ * Further accounts, combined with `account_id` according to `account_match`
+ */
+account_ids: bigint[]; account_match: AccountMatch; 
+/**
+ * Let each account also match through any of its descendants
+ */
+include_descendants: boolean; tag: string | null; 
+/**
+ * Further tags; a transaction must carry all of them
+ */
+tags: string[]; 
+/**
+ * Case-insensitive text in the description, notes or reference
+ */
+text: string | null; 
+/**
+ * Bounds on an entry amount, in the entry's currency units. With accounts
+ * selected only their entries count, as do `entry_type` and `currency`.
+ */
+min_amount: string | null; max_amount: string | null; entry_type: EntryType | null; currency: string | null; from_date: string | null; to_date: string | null; sort: TransactionSort; limit: number | null; offset: number | null; 
+/**
+ * Only list transactions after this position, for keyset pagination
+ */
+cursor: TransactionCursor | null }
+export type TransactionPage = { transactions: Transaction[]; 
+/**
+ * Cursor to fetch the next page with, `None` on the last page
+ */
+next_cursor: TransactionCursor | null }
+/**
+ * Transaction found by a full-text search
+ */
+export type TransactionSearchHit = { transaction: Transaction; 
+/**
+ * BM25 rank, lower is a better match
+ */
+rank: number; 
+/**
+ * Best matching part of the searched text, cut around the matches
+ */
+snippet: SnippetSegment[] }
+/**
+ * Order of a transaction listing. Ties are broken by id, newest first.
+ */
+export type TransactionSort = "DateDesc" | "DateAsc" | 
+/**
+ * By the sum of the debit entries, in entry currency units
+ */
+"AmountDesc" | "AmountAsc" | "Description"
+export type TrialBalance = { as_of: string; 
+/**
+ * Every account of the tree, including inactive ones, in tree order; one line
+ * per currency for accounts holding several currencies
+ */
+lines: TrialBalanceLine[]; 
+/**
+ * Totals per currency the entries balance in, counting entries with a
+ * price at their converted amount
+ */
+totals: TrialBalanceTotal[]; 
+/**
+ * Whether total debits equal total credits in every currency of `totals`
+ */
+is_balanced: boolean }
+/**
+ * Debit and credit totals of one account in one currency
+ */
+export type TrialBalanceLine = { account_id: bigint; account_name: string; account_type: AccountType; level: number; path: string; total_debits: Money; total_credits: Money; 
+/**
+ * Debits minus credits: positive for a debit balance, negative for a credit balance
+ */
+balance: Money }
+/**
+ * Totals of all accounts in one currency
+ */
+export type TrialBalanceTotal = { total_debits: Money; total_credits: Money }
+/**
+ * Total kept per currency and also valued in the reporting currency
+ */
+export type ValuedTotal = { 
+/**
+ * One amount per currency, in the accounts' own currencies
+ */
+by_currency: Money[]; 
+/**
+ * Sum of the amounts that could be converted into the reporting currency
+ */
+converted: Money }
 
 /** tauri-specta globals **/
 
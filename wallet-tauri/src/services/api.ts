@@ -127,8 +127,10 @@ const mockCommands = {
       reference: null,
       transaction_date: date,
       created_at: new Date().toISOString(),
-      tags: null,
+      tags: [],
       notes: null,
+      external_id: null,
+      reverses_transaction_id: null,
       entries: [
         {
           id: BigInt(Date.now() + 1),
@@ -140,6 +142,7 @@ const mockCommands = {
           },
           entry_type: "Credit", // Money comes FROM this account
           description: null,
+          price: null,
           created_at: new Date().toISOString(),
         },
         {
@@ -152,6 +155,7 @@ const mockCommands = {
           },
           entry_type: "Debit", // Money goes TO this account
           description: null,
+          price: null,
           created_at: new Date().toISOString(),
         },
       ],
@@ -232,7 +236,7 @@ const mockCommands = {
     };
   },
   
-  async getAccountBalanceWithChildren(_accountId: bigint): Promise<Result<Money, string>> {
+  async getAccountBalanceWithChildren(_accountId: bigint): Promise<Result<Money[], string>> {
     await delay(10);
     
     // Mock hierarchical balance - slightly higher than individual balance
@@ -240,10 +244,12 @@ const mockCommands = {
     
     return {
       status: "ok",
-      data: {
-        amount_minor: balanceAmount,
-        currency: EUR,
-      },
+      data: [
+        {
+          amount_minor: balanceAmount,
+          currency: EUR,
+        },
+      ],
     };
   },
   
@@ -290,7 +296,7 @@ const mockCommands = {
     return { status: "ok", data: updatedAccount };
   },
 
-  async deactivateAccount(accountId: bigint): Promise<Result<void, string>> {
+  async deactivateAccount(accountId: bigint): Promise<Result<null, string>> {
     await delay(10);
     
     // Find the account to deactivate
@@ -315,7 +321,7 @@ const mockCommands = {
       updated_at: new Date().toISOString(),
     };
     
-    return { status: "ok", data: undefined };
+    return { status: "ok", data: null };
   },
 
   async getAccountTreeFiltered(includeInactive: boolean): Promise<Result<AccountNode[], string>> {
@@ -347,37 +353,8 @@ const mockCommands = {
   },
 };
 
-// Extend Tauri commands with new functions (until bindings are regenerated)
-const extendedTauriCommands = isTauri() ? {
-  ...tauriCommands,
-  async updateAccount(accountId: bigint, name: string, description: string | null): Promise<Result<Account, string>> {
-    try {
-      const data = await (window as any).__TAURI_INTERNALS__.invoke("update_account", { accountId, name, description });
-      return { status: "ok", data };
-    } catch (error) {
-      return { status: "error", error: String(error) };
-    }
-  },
-  async deactivateAccount(accountId: bigint): Promise<Result<void, string>> {
-    try {
-      await (window as any).__TAURI_INTERNALS__.invoke("deactivate_account", { accountId });
-      return { status: "ok", data: undefined };
-    } catch (error) {
-      return { status: "error", error: String(error) };
-    }
-  },
-  async getAccountTreeFiltered(includeInactive: boolean): Promise<Result<AccountNode[], string>> {
-    try {
-      const data = await (window as any).__TAURI_INTERNALS__.invoke("get_account_tree_filtered", { includeInactive });
-      return { status: "ok", data };
-    } catch (error) {
-      return { status: "error", error: String(error) };
-    }
-  }
-} : mockCommands;
-
 // Export either real or mock commands based on environment
-export const commands = extendedTauriCommands;
+export const commands = isTauri() ? tauriCommands : mockCommands;
 
 // Helper to extract data from Result type
 export function unwrapResult<T>(result: Result<T, string>): T {
@@ -9,8 +9,10 @@ export const mockTransactions: Transaction[] = [
     reference: null,
     transaction_date: "2025-07-01",
     created_at: mockDate(5),
-    tags: null,
+    tags: [],
     notes: null,
+    external_id: null,
+    reverses_transaction_id: null,
     entries: [
       {
         id: BigInt(1),
@@ -19,6 +21,7 @@ export const mockTransactions: Transaction[] = [
         amount: { amount_minor: BigInt(300000), currency: EUR }, // €3000
         entry_type: "Credit",
         description: "Salaire net",
+        price: null,
         created_at: mockDate(5),
       },
       {
@@ -28,6 +31,7 @@ export const mockTransactions: Transaction[] = [
         amount: { amount_minor: BigInt(300000), currency: EUR },
         entry_type: "Debit",
         description: "Salaire reçu",
+        price: null,
         created_at: mockDate(5),
       },
     ],
@@ -38,8 +42,10 @@ export const mockTransactions: Transaction[] = [
     reference: null,
     transaction_date: "2025-07-02",
     created_at: mockDate(4),
-    tags: null,
+    tags: [],
     notes: null,
+    external_id: null,
+    reverses_transaction_id: null,
     entries: [
       {
         id: BigInt(3),
@@ -48,6 +54,7 @@ export const mockTransactions: Transaction[] = [
         amount: { amount_minor: BigInt(7500), currency: EUR }, // €75
         entry_type: "Credit",
         description: "Paiement courses",
+        price: null,
         created_at: mockDate(4),
       },
       {
@@ -57,6 +64,7 @@ export const mockTransactions: Transaction[] = [
         amount: { amount_minor: BigInt(7500), currency: EUR },
         entry_type: "Debit",
         description: "Courses alimentaires",
+        price: null,
         created_at: mockDate(4),
       },
     ],
@@ -67,8 +75,10 @@ export const mockTransactions: Transaction[] = [
     reference: null,
     transaction_date: "2025-07-03",
     created_at: mockDate(3),
-    tags: null,
+    tags: [],
     notes: null,
+    external_id: null,
+    reverses_transaction_id: null,
     entries: [
       {
         id: BigInt(5),
@@ -77,6 +87,7 @@ export const mockTransactions: Transaction[] = [
         amount: { amount_minor: BigInt(50000), currency: EUR }, // €500
         entry_type: "Credit",
         description: "Virement épargne",
+        price: null,
         created_at: mockDate(3),
       },
       {
@@ -86,6 +97,7 @@ export const mockTransactions: Transaction[] = [
         amount: { amount_minor: BigInt(50000), currency: EUR },
         entry_type: "Debit",
         description: "Placement Livret A",
+        price: null,
         created_at: mockDate(3),
       },
     ],
@@ -96,8 +108,10 @@ export const mockTransactions: Transaction[] = [
     reference: null,
     transaction_date: "2025-07-06",
     created_at: mockDate(0),
-    tags: null,
+    tags: [],
     notes: null,
+    external_id: null,
+    reverses_transaction_id: null,
     entries: [
       {
         id: BigInt(7),
@@ -106,6 +120,7 @@ export const mockTransactions: Transaction[] = [
         amount: { amount_minor: BigInt(4500), currency: EUR }, // €45
         entry_type: "Credit",
         description: "Paiement restaurant",
+        price: null,
         created_at: mockDate(0),
       },
       {
@@ -115,6 +130,7 @@ export const mockTransactions: Transaction[] = [
         amount: { amount_minor: BigInt(4500), currency: EUR },
         entry_type: "Debit",
         description: "Repas restaurant",
+        price: null,
         created_at: mockDate(0),
       },
     ],